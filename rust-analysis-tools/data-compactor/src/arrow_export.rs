@@ -0,0 +1,243 @@
+//! Arrow/Parquet export for `EfficientCompactor` output.
+//!
+//! `TypedResourceGroup` already stores one typed column per field; this
+//! module turns each group directly into an Arrow `RecordBatch` instead of
+//! reconstructing JSON first, so the compacted data can be read by DuckDB,
+//! Polars, or pandas without going through `reconstruct_data`.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use arrow::array::{
+    ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray, UInt16Array,
+};
+use arrow::datatypes::{DataType, Field, Schema, UInt16Type};
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+use parquet::file::properties::WriterProperties;
+
+use crate::efficient_compactor::{Dictionaries, EfficientCompactedData, ResourceSchema, TypedResourceGroup};
+
+impl EfficientCompactedData {
+    /// Build one Arrow `RecordBatch` per resource type, keyed by type name.
+    pub fn to_arrow(&self) -> Result<HashMap<String, RecordBatch>> {
+        self.data
+            .resources
+            .iter()
+            .map(|(type_name, group)| {
+                let schema = self
+                    .schema
+                    .resource_types
+                    .get(type_name)
+                    .with_context(|| format!("no resource schema recorded for type '{type_name}'"))?;
+                let batch = Self::record_batch_for_group(type_name, schema, group, &self.dictionaries)?;
+                Ok((type_name.clone(), batch))
+            })
+            .collect()
+    }
+
+    /// Write one `<type_name>.parquet` file per resource type under `path`.
+    pub fn write_parquet(&self, path: impl AsRef<Path>) -> Result<()> {
+        let dir = path.as_ref();
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create parquet output directory '{}'", dir.display()))?;
+
+        for (type_name, batch) in self.to_arrow()? {
+            let file_path = dir.join(format!("{type_name}.parquet"));
+            let file = std::fs::File::create(&file_path)
+                .with_context(|| format!("failed to create parquet file '{}'", file_path.display()))?;
+            let props = WriterProperties::builder().build();
+            let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(props))
+                .with_context(|| format!("failed to open parquet writer for '{type_name}'"))?;
+            writer
+                .write(&batch)
+                .with_context(|| format!("failed to write parquet row group for '{type_name}'"))?;
+            writer
+                .close()
+                .with_context(|| format!("failed to finalize parquet file '{}'", file_path.display()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Build a `RecordBatch` for a single resource type, with a constant
+    /// `resource_type` column prepended. Iterates `group`'s own columns
+    /// (the abbreviated field names that actually hold data) and resolves
+    /// each one back to its original property name via
+    /// `dictionaries.properties`, the same reverse lookup
+    /// `EfficientCompactor::reconstruct_data` uses. Nullability comes from
+    /// whether the resolved original name is in `resource_schema.optional`;
+    /// a name `ResourceSchema` doesn't recognize defaults to nullable.
+    fn record_batch_for_group(
+        type_name: &str,
+        resource_schema: &ResourceSchema,
+        group: &TypedResourceGroup,
+        dictionaries: &Dictionaries,
+    ) -> Result<RecordBatch> {
+        let mut fields = vec![Field::new("resource_type", DataType::Utf8, false)];
+        let mut arrays: Vec<ArrayRef> =
+            vec![Arc::new(StringArray::from(vec![type_name.to_string(); group.count]))];
+
+        let original_name = |field_name: &str| -> String {
+            dictionaries
+                .properties
+                .get(field_name)
+                .cloned()
+                .unwrap_or_else(|| field_name.to_string())
+        };
+        let is_nullable = |original: &str| -> bool {
+            resource_schema.required.iter().all(|f| f != original)
+        };
+
+        let decoded = group.decode();
+
+        for (field_name, values) in &decoded.str_cols {
+            let original = original_name(field_name);
+            let (data_type, array) = Self::dictionary_array(values, &dictionaries.strings);
+            fields.push(Field::new(&original, data_type, is_nullable(&original)));
+            arrays.push(array);
+        }
+        for (field_name, values) in &decoded.url_cols {
+            let original = original_name(field_name);
+            let (data_type, array) = Self::dictionary_array(values, &dictionaries.urls);
+            fields.push(Field::new(&original, data_type, is_nullable(&original)));
+            arrays.push(array);
+        }
+        for (field_name, values) in &decoded.json_cols {
+            let original = original_name(field_name);
+            let (data_type, array) = Self::dictionary_array(values, &dictionaries.strings);
+            fields.push(Field::new(&original, data_type, is_nullable(&original)));
+            arrays.push(array);
+        }
+        for (field_name, values) in &decoded.int_cols {
+            let original = original_name(field_name);
+            fields.push(Field::new(&original, DataType::Int64, is_nullable(&original)));
+            arrays.push(Arc::new(Int64Array::from(values.clone())));
+        }
+        for (field_name, values) in &decoded.float_cols {
+            let original = original_name(field_name);
+            fields.push(Field::new(&original, DataType::Float64, is_nullable(&original)));
+            arrays.push(Arc::new(Float64Array::from(values.clone())));
+        }
+        for (field_name, values) in &decoded.bool_cols {
+            let original = original_name(field_name);
+            // `bool_cols` has no `Option` wrapper, so these are never nullable.
+            fields.push(Field::new(&original, DataType::Boolean, false));
+            arrays.push(Arc::new(BooleanArray::from(values.clone())));
+        }
+
+        let schema = Arc::new(Schema::new(fields));
+        RecordBatch::try_new(schema, arrays)
+            .with_context(|| format!("failed to build RecordBatch for resource type '{type_name}'"))
+    }
+
+    /// Build a dictionary-encoded `Utf8` column straight from one of the
+    /// crate's existing `u16`-keyed string/URL dictionaries, with no
+    /// re-deduplication: the dictionary values are the existing entries
+    /// (sorted by id) and the keys are each row's id remapped to its
+    /// position in that values array.
+    fn dictionary_array(values: &[Option<u16>], dict: &HashMap<u16, String>) -> (DataType, ArrayRef) {
+        let mut ids: Vec<u16> = dict.keys().copied().collect();
+        ids.sort_unstable();
+        let position_of: HashMap<u16, u16> = ids
+            .iter()
+            .enumerate()
+            .map(|(pos, &id)| (id, pos as u16))
+            .collect();
+        let dict_values = StringArray::from(
+            ids.iter()
+                .map(|id| dict.get(id).map(|s| s.as_str()))
+                .collect::<Vec<_>>(),
+        );
+
+        let keys = UInt16Array::from(
+            values
+                .iter()
+                .map(|v| v.and_then(|id| position_of.get(&id).copied()))
+                .collect::<Vec<_>>(),
+        );
+
+        let data_type = DataType::Dictionary(Box::new(DataType::UInt16), Box::new(DataType::Utf8));
+        let array = arrow::array::DictionaryArray::<UInt16Type>::try_new(keys, Arc::new(dict_values))
+            .expect("keys index only into positions produced from dict_values");
+        (data_type, Arc::new(array))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::efficient_compactor::EfficientCompactor;
+    use serde_json::json;
+
+    #[test]
+    fn test_to_arrow_builds_one_batch_per_resource_type_with_resource_type_column() {
+        let mut compactor = EfficientCompactor::new();
+        let data = json!({
+            "subresources": [
+                {
+                    "url": "https://example.com/1",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "Acme",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation": 2020
+                },
+                {
+                    "url": "https://example.com/2",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "Acme"
+                }
+            ]
+        });
+
+        let compacted = compactor.compact_comprehensive_data(&data).unwrap();
+        let batches = compacted.to_arrow().unwrap();
+
+        assert_eq!(batches.len(), 1);
+        let batch = batches.values().next().unwrap();
+        assert_eq!(batch.num_rows(), 2);
+
+        let resource_type_col = batch
+            .column_by_name("resource_type")
+            .expect("constant resource_type column should be present");
+        let resource_type_col = resource_type_col.as_any().downcast_ref::<StringArray>().unwrap();
+        assert!(resource_type_col.iter().all(|v| v.is_some()));
+    }
+
+    #[test]
+    fn test_optional_field_missing_from_some_rows_is_nullable() {
+        let mut compactor = EfficientCompactor::new();
+        let data = json!({
+            "subresources": [
+                {
+                    "url": "https://example.com/1",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "Acme",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation": 2020
+                },
+                {
+                    "url": "https://example.com/2",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "Acme"
+                }
+            ]
+        });
+
+        let compacted = compactor.compact_comprehensive_data(&data).unwrap();
+        let batches = compacted.to_arrow().unwrap();
+        let batch = batches.values().next().unwrap();
+
+        let year_field = batch
+            .schema()
+            .field_with_name("https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation")
+            .unwrap();
+        assert!(year_field.is_nullable(), "field present on only some resources should be nullable");
+
+        let name_field = batch
+            .schema()
+            .field_with_name("https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name")
+            .unwrap();
+        assert!(!name_field.is_nullable(), "field present on every resource should be non-nullable");
+    }
+}
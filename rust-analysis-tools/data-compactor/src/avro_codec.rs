@@ -0,0 +1,322 @@
+//! Hand-rolled Avro binary encoding for `EfficientCompactor`'s inferred
+//! schema (no Avro crate is vendored into this tree, so the binary rules
+//! are implemented directly from the spec): signed integers as zig-zag
+//! varints, `double` as 8 little-endian IEEE-754 bytes, strings as a
+//! zig-zag-varint length prefix followed by UTF-8 bytes, and arrays as a
+//! count-prefixed block terminated by a zero count.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+use crate::efficient_compactor::{CompactionSchema, CompressionStats, Dictionaries, FieldType};
+
+/// Avro-encoded resources alongside the schema/dictionaries needed to
+/// decode them, produced by `EfficientCompactor::compact_to_avro`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvroCompactedData {
+    pub schema: CompactionSchema,
+    pub dictionaries: Dictionaries,
+    pub stats: CompressionStats,
+    /// One Avro-encoded record per resource, keyed by resource type
+    pub records: HashMap<String, Vec<Vec<u8>>>,
+}
+
+fn zigzag_encode(n: i64) -> u64 {
+    ((n << 1) ^ (n >> 63)) as u64
+}
+
+fn zigzag_decode(n: u64) -> i64 {
+    ((n >> 1) as i64) ^ -((n & 1) as i64)
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut n: u64) {
+    loop {
+        let byte = (n & 0x7f) as u8;
+        n >>= 7;
+        if n == 0 {
+            buf.push(byte);
+            break;
+        }
+        buf.push(byte | 0x80);
+    }
+}
+
+fn read_varint(bytes: &[u8], pos: &mut usize) -> Result<u64> {
+    let mut result: u64 = 0;
+    let mut shift = 0;
+    loop {
+        let byte = *bytes
+            .get(*pos)
+            .context("unexpected end of Avro buffer while reading varint")?;
+        *pos += 1;
+        result |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    Ok(result)
+}
+
+pub(crate) fn write_long(buf: &mut Vec<u8>, n: i64) {
+    write_varint(buf, zigzag_encode(n));
+}
+
+pub(crate) fn read_long(bytes: &[u8], pos: &mut usize) -> Result<i64> {
+    Ok(zigzag_decode(read_varint(bytes, pos)?))
+}
+
+pub(crate) fn write_double(buf: &mut Vec<u8>, n: f64) {
+    buf.extend_from_slice(&n.to_le_bytes());
+}
+
+pub(crate) fn read_double(bytes: &[u8], pos: &mut usize) -> Result<f64> {
+    let slice = bytes
+        .get(*pos..*pos + 8)
+        .context("unexpected end of Avro buffer while reading double")?;
+    *pos += 8;
+    Ok(f64::from_le_bytes(slice.try_into().expect("slice is exactly 8 bytes")))
+}
+
+pub(crate) fn write_boolean(buf: &mut Vec<u8>, b: bool) {
+    buf.push(if b { 1 } else { 0 });
+}
+
+pub(crate) fn read_boolean(bytes: &[u8], pos: &mut usize) -> Result<bool> {
+    let byte = *bytes
+        .get(*pos)
+        .context("unexpected end of Avro buffer while reading boolean")?;
+    *pos += 1;
+    Ok(byte != 0)
+}
+
+pub(crate) fn write_string(buf: &mut Vec<u8>, s: &str) {
+    write_long(buf, s.len() as i64);
+    buf.extend_from_slice(s.as_bytes());
+}
+
+pub(crate) fn read_string(bytes: &[u8], pos: &mut usize) -> Result<String> {
+    let len = read_long(bytes, pos)? as usize;
+    let slice = bytes
+        .get(*pos..*pos + len)
+        .context("unexpected end of Avro buffer while reading string")?;
+    *pos += len;
+    String::from_utf8(slice.to_vec()).context("Avro string field was not valid UTF-8")
+}
+
+/// Encode `value` per `field_type`'s Avro binary rules
+pub(crate) fn encode_value(value: &Value, field_type: &FieldType, buf: &mut Vec<u8>) -> Result<()> {
+    match field_type {
+        FieldType::Int => {
+            let n = value
+                .as_i64()
+                .with_context(|| format!("expected an integer, got {value}"))?;
+            write_long(buf, n);
+        }
+        FieldType::Float => {
+            let n = value
+                .as_f64()
+                .with_context(|| format!("expected a float, got {value}"))?;
+            write_double(buf, n);
+        }
+        FieldType::Bool => {
+            let b = value
+                .as_bool()
+                .with_context(|| format!("expected a boolean, got {value}"))?;
+            write_boolean(buf, b);
+        }
+        FieldType::Str | FieldType::Url => {
+            let s = value
+                .as_str()
+                .with_context(|| format!("expected a string, got {value}"))?;
+            write_string(buf, s);
+        }
+        FieldType::Json => {
+            let s = serde_json::to_string(value).context("failed to serialize Json field for Avro encoding")?;
+            write_string(buf, &s);
+        }
+        FieldType::Array(inner) => {
+            let items = value
+                .as_array()
+                .with_context(|| format!("expected an array, got {value}"))?;
+            if !items.is_empty() {
+                write_long(buf, items.len() as i64);
+                for item in items {
+                    encode_value(item, inner, buf)?;
+                }
+            }
+            write_long(buf, 0);
+        }
+    }
+    Ok(())
+}
+
+/// Decode a value per `field_type`'s Avro binary rules, advancing `pos`
+pub(crate) fn decode_value(field_type: &FieldType, bytes: &[u8], pos: &mut usize) -> Result<Value> {
+    match field_type {
+        FieldType::Int => Ok(Value::Number(read_long(bytes, pos)?.into())),
+        FieldType::Float => Ok(serde_json::json!(read_double(bytes, pos)?)),
+        FieldType::Bool => Ok(Value::Bool(read_boolean(bytes, pos)?)),
+        FieldType::Str | FieldType::Url => Ok(Value::String(read_string(bytes, pos)?)),
+        FieldType::Json => {
+            let s = read_string(bytes, pos)?;
+            serde_json::from_str(&s).context("Avro Json field did not contain valid JSON")
+        }
+        FieldType::Array(inner) => {
+            let mut items = Vec::new();
+            loop {
+                let count = read_long(bytes, pos)?;
+                if count == 0 {
+                    break;
+                }
+                for _ in 0..count {
+                    items.push(decode_value(inner, bytes, pos)?);
+                }
+            }
+            Ok(Value::Array(items))
+        }
+    }
+}
+
+/// Encode one resource's declared `fields` (sorted by name for a
+/// deterministic, decodable layout) in order: required fields are written
+/// directly, optional fields as a `["null", T]` union (index `0` for
+/// absent/null, `1` followed by the value otherwise).
+pub(crate) fn encode_record(
+    resource: &Value,
+    fields: &[(String, FieldType)],
+    required: &HashSet<String>,
+) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+
+    for (field_name, field_type) in fields {
+        let value = resource.get(field_name);
+        if required.contains(field_name) {
+            let value = value.with_context(|| format!("resource missing required field '{field_name}'"))?;
+            encode_value(value, field_type, &mut buf)?;
+        } else {
+            match value.filter(|v| !v.is_null()) {
+                Some(v) => {
+                    write_long(&mut buf, 1);
+                    encode_value(v, field_type, &mut buf)?;
+                }
+                None => write_long(&mut buf, 0),
+            }
+        }
+    }
+
+    Ok(buf)
+}
+
+/// Inverse of `encode_record`: decode `bytes` into a JSON object, restoring
+/// each field's original (unabbreviated) property name via `properties`
+pub(crate) fn decode_record(
+    bytes: &[u8],
+    fields: &[(String, FieldType)],
+    required: &HashSet<String>,
+    properties: &HashMap<String, String>,
+) -> Result<Value> {
+    let mut pos = 0;
+    let mut obj = serde_json::Map::new();
+
+    for (field_name, field_type) in fields {
+        let original_name = properties
+            .get(field_name)
+            .cloned()
+            .unwrap_or_else(|| field_name.clone());
+
+        if required.contains(field_name) {
+            let value = decode_value(field_type, bytes, &mut pos)?;
+            obj.insert(original_name, value);
+        } else {
+            let index = read_long(bytes, &mut pos)?;
+            if index == 1 {
+                let value = decode_value(field_type, bytes, &mut pos)?;
+                obj.insert(original_name, value);
+            }
+        }
+    }
+
+    Ok(Value::Object(obj))
+}
+
+/// `ResourceSchema::types`, sorted by field name so `encode_record`/
+/// `decode_record` agree on field order without persisting that order separately
+pub(crate) fn sorted_fields(types: &HashMap<String, FieldType>) -> Vec<(String, FieldType)> {
+    let mut fields: Vec<(String, FieldType)> = types.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    fields.sort_by(|a, b| a.0.cmp(&b.0));
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_long_round_trips_negative_and_positive() {
+        for n in [0_i64, 1, -1, 64, -64, i64::MAX, i64::MIN] {
+            let mut buf = Vec::new();
+            write_long(&mut buf, n);
+            let mut pos = 0;
+            assert_eq!(read_long(&buf, &mut pos).unwrap(), n);
+            assert_eq!(pos, buf.len());
+        }
+    }
+
+    #[test]
+    fn test_string_round_trips() {
+        let mut buf = Vec::new();
+        write_string(&mut buf, "hello avro");
+        let mut pos = 0;
+        assert_eq!(read_string(&buf, &mut pos).unwrap(), "hello avro");
+        assert_eq!(pos, buf.len());
+    }
+
+    #[test]
+    fn test_double_round_trips() {
+        let mut buf = Vec::new();
+        write_double(&mut buf, 3.14159);
+        let mut pos = 0;
+        assert_eq!(read_double(&buf, &mut pos).unwrap(), 3.14159);
+    }
+
+    #[test]
+    fn test_array_round_trips_including_empty() {
+        let mut buf = Vec::new();
+        encode_value(&json!([1, 2, 3]), &FieldType::Array(Box::new(FieldType::Int)), &mut buf).unwrap();
+        let mut pos = 0;
+        assert_eq!(
+            decode_value(&FieldType::Array(Box::new(FieldType::Int)), &buf, &mut pos).unwrap(),
+            json!([1, 2, 3])
+        );
+
+        let mut empty_buf = Vec::new();
+        encode_value(&json!([]), &FieldType::Array(Box::new(FieldType::Int)), &mut empty_buf).unwrap();
+        let mut pos = 0;
+        assert_eq!(
+            decode_value(&FieldType::Array(Box::new(FieldType::Int)), &empty_buf, &mut pos).unwrap(),
+            json!([])
+        );
+    }
+
+    #[test]
+    fn test_record_round_trips_with_optional_field_absent() {
+        let fields = vec![
+            ("cn".to_string(), FieldType::Str),
+            ("yi".to_string(), FieldType::Int),
+        ];
+        let required: HashSet<String> = ["cn".to_string()].into_iter().collect();
+        let properties: HashMap<String, String> = [("cn".to_string(), "company-name".to_string())]
+            .into_iter()
+            .collect();
+
+        let resource = json!({"cn": "Acme"});
+        let encoded = encode_record(&resource, &fields, &required).unwrap();
+        let decoded = decode_record(&encoded, &fields, &required, &properties).unwrap();
+
+        assert_eq!(decoded, json!({"company-name": "Acme"}));
+    }
+}
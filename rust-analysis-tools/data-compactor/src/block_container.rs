@@ -0,0 +1,311 @@
+use anyhow::{Context, Result};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use std::collections::HashMap;
+
+use crate::efficient_compactor::{
+    CompactionSchema, CompressionStats, Dictionaries, EfficientCompactedData, TypedResourceGroup,
+};
+
+/// Length in bytes of the sync marker written once in `ContainerHeader` and
+/// repeated after every `DataBlock`
+pub const SYNC_MARKER_LEN: usize = 16;
+
+/// A block container framing/integrity failure
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockContainerError {
+    /// A block's recomputed CRC32 (over its decompressed bytes) didn't
+    /// match the checksum stored alongside it
+    ChecksumMismatch {
+        block_index: usize,
+        expected: u32,
+        computed: u32,
+    },
+    /// A block's sync marker didn't match the container header's marker
+    SyncMarkerMismatch { block_index: usize },
+}
+
+impl fmt::Display for BlockContainerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlockContainerError::ChecksumMismatch {
+                block_index,
+                expected,
+                computed,
+            } => write!(
+                f,
+                "block {block_index} failed CRC32 verification (expected {expected:#x}, got {computed:#x})"
+            ),
+            BlockContainerError::SyncMarkerMismatch { block_index } => write!(
+                f,
+                "sync marker after block {block_index} did not match the container header's marker"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BlockContainerError {}
+
+/// Per-block compression codec, chosen independently for each data block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlockCodec {
+    Null,
+    Deflate,
+    Snappy,
+}
+
+/// Codec and size detail for a single block, surfaced via `CompressionStats::block_stats`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockStats {
+    pub codec: BlockCodec,
+    pub uncompressed_size: usize,
+    pub compressed_size: usize,
+}
+
+/// Header of a block container: the schema and dictionaries shared by every
+/// data block that follows, mirroring Avro's file-header-then-blocks layout.
+/// `sync_marker` is generated once per container and repeated on every
+/// `DataBlock`, so a reader streaming raw bytes could resynchronize after
+/// corruption instead of failing the whole container.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerHeader {
+    pub schema: CompactionSchema,
+    pub dictionaries: Dictionaries,
+    pub sync_marker: [u8; SYNC_MARKER_LEN],
+}
+
+/// A single self-describing data block: the resource type its payload
+/// decodes to, a record count, the (optionally compressed) payload, a
+/// trailing CRC32 computed over the *uncompressed* bytes, and a copy of the
+/// container's sync marker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataBlock {
+    pub resource_type: String,
+    pub record_count: usize,
+    pub codec: BlockCodec,
+    pub payload: Vec<u8>,
+    pub crc32: u32,
+    pub sync_marker: [u8; SYNC_MARKER_LEN],
+}
+
+/// A header plus one data block per resource type, so large compacted
+/// datasets can be streamed and partially recovered rather than requiring
+/// the whole JSON document to parse successfully
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockContainer {
+    pub header: ContainerHeader,
+    pub blocks: Vec<DataBlock>,
+}
+
+impl BlockContainer {
+    /// Split `compacted`'s columnar resource groups into one block per
+    /// resource type, compressing each with `codec`. Returns the container
+    /// alongside `compacted.stats` with `block_stats` filled in, so the
+    /// per-block codec/size detail isn't dropped on the floor by the caller.
+    pub fn write(compacted: &EfficientCompactedData, codec: BlockCodec) -> Result<(Self, CompressionStats)> {
+        let mut sync_marker = [0u8; SYNC_MARKER_LEN];
+        rand::thread_rng().fill_bytes(&mut sync_marker);
+
+        let header = ContainerHeader {
+            schema: compacted.schema.clone(),
+            dictionaries: compacted.dictionaries.clone(),
+            sync_marker,
+        };
+
+        let mut blocks = Vec::with_capacity(compacted.data.resources.len());
+        let mut block_stats = Vec::with_capacity(compacted.data.resources.len());
+
+        for (resource_type, group) in &compacted.data.resources {
+            let uncompressed = serde_json::to_vec(group).context("Failed to serialize resource group")?;
+            let crc32 = crc32fast::hash(&uncompressed);
+            let payload = Self::compress(&uncompressed, codec)?;
+
+            block_stats.push(BlockStats {
+                codec,
+                uncompressed_size: uncompressed.len(),
+                compressed_size: payload.len(),
+            });
+
+            blocks.push(DataBlock {
+                resource_type: resource_type.clone(),
+                record_count: group.count,
+                codec,
+                payload,
+                crc32,
+                sync_marker,
+            });
+        }
+
+        let stats = CompressionStats {
+            block_stats,
+            ..compacted.stats.clone()
+        };
+
+        Ok((Self { header, blocks }, stats))
+    }
+
+    /// Decompress and verify every block, returning the deserialized resource
+    /// groups keyed by resource type, in the shape `ColumnarData::resources`
+    /// expects so the caller can reassemble an `EfficientCompactedData` and
+    /// feed it to `EfficientCompactor::reconstruct_data`. Fails on the first
+    /// block whose sync marker doesn't match the header's, or whose
+    /// recomputed CRC32 doesn't match the stored checksum.
+    pub fn read(&self) -> Result<HashMap<String, TypedResourceGroup>> {
+        let mut groups = HashMap::with_capacity(self.blocks.len());
+
+        for (index, block) in self.blocks.iter().enumerate() {
+            if block.sync_marker != self.header.sync_marker {
+                return Err(BlockContainerError::SyncMarkerMismatch { block_index: index }.into());
+            }
+
+            let uncompressed = Self::decompress(&block.payload, block.codec)?;
+
+            let computed = crc32fast::hash(&uncompressed);
+            if computed != block.crc32 {
+                return Err(BlockContainerError::ChecksumMismatch {
+                    block_index: index,
+                    expected: block.crc32,
+                    computed,
+                }
+                .into());
+            }
+
+            let group: TypedResourceGroup = serde_json::from_slice(&uncompressed)
+                .with_context(|| format!("Failed to deserialize block {}", index))?;
+            groups.insert(block.resource_type.clone(), group);
+        }
+
+        Ok(groups)
+    }
+
+    fn compress(bytes: &[u8], codec: BlockCodec) -> Result<Vec<u8>> {
+        match codec {
+            BlockCodec::Null => Ok(bytes.to_vec()),
+            BlockCodec::Deflate => {
+                use flate2::write::DeflateEncoder;
+                use flate2::Compression;
+                use std::io::Write;
+
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(bytes).context("Failed to deflate block payload")?;
+                encoder.finish().context("Failed to finalize deflate stream")
+            },
+            BlockCodec::Snappy => {
+                let mut encoder = snap::raw::Encoder::new();
+                encoder.compress_vec(bytes).context("Failed to snappy-compress block payload")
+            },
+        }
+    }
+
+    fn decompress(bytes: &[u8], codec: BlockCodec) -> Result<Vec<u8>> {
+        match codec {
+            BlockCodec::Null => Ok(bytes.to_vec()),
+            BlockCodec::Deflate => {
+                use flate2::read::DeflateDecoder;
+                use std::io::Read;
+
+                let mut decoder = DeflateDecoder::new(bytes);
+                let mut out = Vec::new();
+                decoder.read_to_end(&mut out).context("Failed to inflate block payload")?;
+                Ok(out)
+            },
+            BlockCodec::Snappy => {
+                let mut decoder = snap::raw::Decoder::new();
+                decoder.decompress_vec(bytes).context("Failed to snappy-decompress block payload")
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::efficient_compactor::EfficientCompactor;
+    use serde_json::json;
+
+    fn sample_compacted() -> EfficientCompactedData {
+        let mut compactor = EfficientCompactor::new();
+        let data = json!({
+            "subresources": [
+                {
+                    "url": "https://example.com/1",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "Acme"
+                }
+            ]
+        });
+        compactor.compact_comprehensive_data(&data).unwrap()
+    }
+
+    #[test]
+    fn test_write_and_read_roundtrip_null_codec() {
+        let compacted = sample_compacted();
+        let (container, stats) = BlockContainer::write(&compacted, BlockCodec::Null).unwrap();
+
+        assert_eq!(stats.block_stats.len(), container.blocks.len());
+        assert!(stats.block_stats.iter().all(|s| matches!(s.codec, BlockCodec::Null)));
+
+        let groups = container.read().unwrap();
+        assert_eq!(groups.len(), container.blocks.len());
+
+        // read()'s keyed groups must be enough to feed straight into
+        // `EfficientCompactor::reconstruct_data` via a reassembled
+        // `EfficientCompactedData`.
+        let reassembled = EfficientCompactedData {
+            schema: container.header.schema.clone(),
+            data: crate::efficient_compactor::ColumnarData { resources: groups },
+            dictionaries: container.header.dictionaries.clone(),
+            stats,
+            raw_subresources: None,
+        };
+        let reconstructed = EfficientCompactor::reconstruct_data(&reassembled).unwrap();
+        assert_eq!(reconstructed["subresources"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_write_and_read_roundtrip_deflate_codec() {
+        let compacted = sample_compacted();
+        let (container, _stats) = BlockContainer::write(&compacted, BlockCodec::Deflate).unwrap();
+
+        let groups = container.read().unwrap();
+        assert_eq!(groups.len(), container.blocks.len());
+    }
+
+    #[test]
+    fn test_read_rejects_corrupted_block() {
+        let compacted = sample_compacted();
+        let (mut container, _stats) = BlockContainer::write(&compacted, BlockCodec::Null).unwrap();
+
+        if let Some(block) = container.blocks.first_mut() {
+            block.payload.push(0xFF);
+        }
+
+        let err = container.read().unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<BlockContainerError>(),
+            Some(&BlockContainerError::ChecksumMismatch {
+                block_index: 0,
+                expected: container.blocks[0].crc32,
+                computed: crc32fast::hash(&container.blocks[0].payload),
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_rejects_mismatched_sync_marker() {
+        let compacted = sample_compacted();
+        let (mut container, _stats) = BlockContainer::write(&compacted, BlockCodec::Null).unwrap();
+
+        if let Some(block) = container.blocks.first_mut() {
+            block.sync_marker[0] ^= 0xFF;
+        }
+
+        let err = container.read().unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<BlockContainerError>(),
+            Some(&BlockContainerError::SyncMarkerMismatch { block_index: 0 })
+        );
+    }
+}
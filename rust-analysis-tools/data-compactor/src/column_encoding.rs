@@ -0,0 +1,164 @@
+//! Bit-packed validity + run-length-encoded column storage.
+//!
+//! `TypedResourceGroup`'s columns used to be plain `Vec<Option<T>>`, which
+//! costs one slot per row even when a field is present everywhere or holds
+//! the same value across many rows (e.g. a `company-name` repeated across
+//! resources). `EncodedColumn` replaces that with a bit-packed presence
+//! mask plus a value stream that collapses consecutive equal values into
+//! `(value, run_length)` pairs, falling back to one-entry-per-value storage
+//! when runs wouldn't actually shrink the column.
+
+use serde::{Deserialize, Serialize};
+
+/// Bit-packed presence mask, one bit per row
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bitmap {
+    bits: Vec<u8>,
+    len: usize,
+}
+
+impl Bitmap {
+    fn new(len: usize) -> Self {
+        Self {
+            bits: vec![0u8; (len + 7) / 8],
+            len,
+        }
+    }
+
+    fn set(&mut self, index: usize, value: bool) {
+        if value {
+            self.bits[index / 8] |= 1 << (index % 8);
+        }
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        index < self.len && (self.bits[index / 8] >> (index % 8)) & 1 == 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// Which shape `EncodedColumn::values` is stored in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColumnEncoding {
+    Dense,
+    RunLength,
+}
+
+/// A column's present values, either stored individually (`Dense`) or
+/// collapsed into `(value, run_length)` pairs for consecutive repeats
+/// (`RunLength`). Absent (`None`) rows aren't represented here at all —
+/// see `EncodedColumn::validity`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ColumnValues<T> {
+    Dense(Vec<T>),
+    RunLength(Vec<(T, u32)>),
+}
+
+/// Replaces `Vec<Option<T>>`: a bit-packed validity mask plus a present-value
+/// stream, with the cheaper of `Dense`/`RunLength` chosen at encode time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncodedColumn<T> {
+    pub validity: Bitmap,
+    pub encoding: ColumnEncoding,
+    pub values: ColumnValues<T>,
+}
+
+impl<T: Clone + PartialEq> EncodedColumn<T> {
+    /// Bit-pack `cells`' presence, then run-length-encode the present
+    /// values, falling back to dense storage when RLE has no fewer entries
+    /// than the present values themselves (i.e. neighbors rarely repeat).
+    pub fn encode(cells: &[Option<T>]) -> Self {
+        let mut validity = Bitmap::new(cells.len());
+        let mut present: Vec<T> = Vec::new();
+        for (i, cell) in cells.iter().enumerate() {
+            if let Some(value) = cell {
+                validity.set(i, true);
+                present.push(value.clone());
+            }
+        }
+
+        let mut runs: Vec<(T, u32)> = Vec::new();
+        for value in &present {
+            match runs.last_mut() {
+                Some((last_value, count)) if last_value == value => *count += 1,
+                _ => runs.push((value.clone(), 1)),
+            }
+        }
+
+        let (encoding, values) = if runs.len() < present.len() {
+            (ColumnEncoding::RunLength, ColumnValues::RunLength(runs))
+        } else {
+            (ColumnEncoding::Dense, ColumnValues::Dense(present))
+        };
+
+        Self {
+            validity,
+            encoding,
+            values,
+        }
+    }
+
+    /// Inverse of `encode`: expand runs back to one `Option<T>` per row.
+    pub fn decode(&self) -> Vec<Option<T>> {
+        let present: Vec<T> = match &self.values {
+            ColumnValues::Dense(values) => values.clone(),
+            ColumnValues::RunLength(runs) => runs
+                .iter()
+                .flat_map(|(value, count)| std::iter::repeat(value.clone()).take(*count as usize))
+                .collect(),
+        };
+
+        let mut present = present.into_iter();
+        (0..self.validity.len())
+            .map(|i| if self.validity.get(i) { present.next() } else { None })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trips_with_gaps_and_repeats() {
+        let cells = vec![Some("a".to_string()), None, Some("a".to_string()), Some("b".to_string()), None];
+        let encoded = EncodedColumn::encode(&cells);
+        assert_eq!(encoded.decode(), cells);
+    }
+
+    #[test]
+    fn test_constant_column_chooses_run_length() {
+        let cells: Vec<Option<u16>> = vec![Some(7); 100];
+        let encoded = EncodedColumn::encode(&cells);
+        assert_eq!(encoded.encoding, ColumnEncoding::RunLength);
+        if let ColumnValues::RunLength(runs) = &encoded.values {
+            assert_eq!(runs.len(), 1);
+            assert_eq!(runs[0], (7, 100));
+        } else {
+            panic!("expected RunLength encoding");
+        }
+        assert_eq!(encoded.decode(), cells);
+    }
+
+    #[test]
+    fn test_alternating_column_falls_back_to_dense() {
+        let cells: Vec<Option<u16>> = (0..10).map(|i| Some(i % 2)).collect();
+        let encoded = EncodedColumn::encode(&cells);
+        assert_eq!(encoded.encoding, ColumnEncoding::Dense);
+        assert_eq!(encoded.decode(), cells);
+    }
+
+    #[test]
+    fn test_all_absent_column_round_trips() {
+        let cells: Vec<Option<u16>> = vec![None, None, None];
+        let encoded = EncodedColumn::encode(&cells);
+        assert_eq!(encoded.decode(), cells);
+    }
+}
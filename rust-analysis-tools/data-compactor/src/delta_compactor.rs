@@ -0,0 +1,210 @@
+//! Delta-compaction against a per-resource-type base record: once a type's
+//! instances are mostly near-identical to some canonical record (explicit
+//! or auto-derived as the most-common value per field), storing only the
+//! attributes that actually differ — plus the abbreviated names of those
+//! attributes — cuts size further than URL/string dedup alone can.
+//! `EfficientCompactor::compact_with_delta`/`reconstruct_from_delta` build
+//! on the pure functions here.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::efficient_compactor::{Dictionaries, CompressionStats};
+
+/// One override recorded for a field relative to `DeltaGroup::base`: either
+/// the resource's differing value for that field, or a marker that `base`'s
+/// key should be dropped entirely. Distinct from a bare `Value::Null` so a
+/// resource field whose genuine value is JSON `null` round-trips instead of
+/// being mistaken for a removal.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeltaOp {
+    Present(Value),
+    Removed,
+}
+
+/// One delta-compacted instance of a resource type: only the attributes
+/// that differ from its `DeltaGroup::base`, plus the abbreviated names of
+/// those attributes so `EfficientCompactor::reconstruct_from_delta` knows
+/// which overrides to overlay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeltaRecord {
+    pub relevant_attributes: Vec<String>,
+    pub overrides: HashMap<String, DeltaOp>,
+}
+
+/// A resource type's base record (explicit or auto-derived) plus one
+/// `DeltaRecord` per instance of that type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeltaGroup {
+    pub base: Value,
+    pub records: Vec<DeltaRecord>,
+}
+
+/// Delta-compacted resources, one `DeltaGroup` per resource type, produced
+/// by `EfficientCompactor::compact_with_delta`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeltaCompactedData {
+    pub groups: HashMap<String, DeltaGroup>,
+    pub dictionaries: Dictionaries,
+    pub stats: CompressionStats,
+}
+
+/// Fields present in `resource` whose value differs from `base`'s (recorded
+/// as `DeltaOp::Present`, even when that value is a genuine JSON `null`),
+/// plus any field `base` has that `resource` drops entirely (recorded as
+/// `DeltaOp::Removed`). Keyed by the *original* (unabbreviated) property name.
+pub(crate) fn diff_fields(resource: &Value, base: &Value) -> Vec<(String, DeltaOp)> {
+    let resource_obj = resource.as_object();
+    let base_obj = base.as_object();
+
+    let mut diffs = Vec::new();
+
+    if let Some(obj) = resource_obj {
+        for (key, value) in obj {
+            if base_obj.and_then(|b| b.get(key)) != Some(value) {
+                diffs.push((key.clone(), DeltaOp::Present(value.clone())));
+            }
+        }
+    }
+
+    if let (Some(base_obj), Some(resource_obj)) = (base_obj, resource_obj) {
+        for key in base_obj.keys() {
+            if !resource_obj.contains_key(key) {
+                diffs.push((key.clone(), DeltaOp::Removed));
+            }
+        }
+    }
+
+    diffs
+}
+
+/// Rebuild one resource by overlaying `overrides` (original, unabbreviated
+/// keys) onto `base`: `DeltaOp::Present` replaces the base's value (even
+/// with a genuine `null`), `DeltaOp::Removed` removes the base's key, and
+/// any key `overrides` doesn't mention is inherited verbatim from `base`.
+pub(crate) fn apply_delta(base: &Value, overrides: &HashMap<String, DeltaOp>) -> Value {
+    let mut merged = base.as_object().cloned().unwrap_or_default();
+
+    for (key, op) in overrides {
+        match op {
+            DeltaOp::Present(value) => {
+                merged.insert(key.clone(), value.clone());
+            }
+            DeltaOp::Removed => {
+                merged.remove(key);
+            }
+        }
+    }
+
+    Value::Object(merged)
+}
+
+/// Pick the most common value per field across `resources`, for use as a
+/// default base when the caller doesn't supply one explicitly. Ties are
+/// broken toward whichever value was seen first.
+pub(crate) fn auto_base(resources: &[&Value]) -> Value {
+    let mut field_order: Vec<String> = Vec::new();
+    let mut tallies: HashMap<String, Vec<(Value, usize)>> = HashMap::new();
+
+    for resource in resources {
+        let Some(obj) = resource.as_object() else { continue };
+        for (key, value) in obj {
+            let tally = tallies.entry(key.clone()).or_insert_with(|| {
+                field_order.push(key.clone());
+                Vec::new()
+            });
+            match tally.iter_mut().find(|(v, _)| v == value) {
+                Some(slot) => slot.1 += 1,
+                None => tally.push((value.clone(), 1)),
+            }
+        }
+    }
+
+    let mut base = serde_json::Map::new();
+    for key in field_order {
+        let Some(tally) = tallies.get(&key) else { continue };
+        let mut best: Option<&(Value, usize)> = None;
+        for entry in tally {
+            if best.map(|(_, count)| entry.1 > *count).unwrap_or(true) {
+                best = Some(entry);
+            }
+        }
+        if let Some((value, _)) = best {
+            base.insert(key, value.clone());
+        }
+    }
+
+    Value::Object(base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_fields_reports_changed_and_removed_keys() {
+        let base = json!({"a": 1, "b": "x", "c": true});
+        let resource = json!({"a": 1, "b": "y"});
+
+        let mut diffs = diff_fields(&resource, &base);
+        diffs.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(diffs, vec![
+            ("b".to_string(), DeltaOp::Present(json!("y"))),
+            ("c".to_string(), DeltaOp::Removed),
+        ]);
+    }
+
+    #[test]
+    fn test_apply_delta_inherits_base_and_applies_overrides() {
+        let base = json!({"a": 1, "b": "x", "c": true});
+        let mut overrides = HashMap::new();
+        overrides.insert("b".to_string(), DeltaOp::Present(json!("y")));
+        overrides.insert("c".to_string(), DeltaOp::Removed);
+
+        let rebuilt = apply_delta(&base, &overrides);
+
+        assert_eq!(rebuilt, json!({"a": 1, "b": "y"}));
+    }
+
+    #[test]
+    fn test_diff_fields_distinguishes_genuine_null_from_removal() {
+        let base = json!({"a": 1, "b": "x"});
+        let resource = json!({"a": 1, "b": null});
+
+        let diffs = diff_fields(&resource, &base);
+
+        assert_eq!(diffs, vec![("b".to_string(), DeltaOp::Present(Value::Null))]);
+    }
+
+    #[test]
+    fn test_apply_delta_restores_genuine_null_distinct_from_removal() {
+        let base = json!({"a": 1, "b": "x", "c": "y"});
+        let mut overrides = HashMap::new();
+        overrides.insert("b".to_string(), DeltaOp::Present(Value::Null));
+        overrides.insert("c".to_string(), DeltaOp::Removed);
+
+        let rebuilt = apply_delta(&base, &overrides);
+
+        assert_eq!(rebuilt, json!({"a": 1, "b": null}));
+    }
+
+    #[test]
+    fn test_auto_base_picks_most_common_value_per_field() {
+        let resources = vec![
+            json!({"name": "Acme", "country": "UG"}),
+            json!({"name": "Acme", "country": "UG"}),
+            json!({"name": "Acme", "country": "KE"}),
+        ];
+        let refs: Vec<&Value> = resources.iter().collect();
+
+        let base = auto_base(&refs);
+
+        assert_eq!(base["name"], "Acme");
+        assert_eq!(base["country"], "UG");
+    }
+}
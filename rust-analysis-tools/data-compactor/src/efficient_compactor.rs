@@ -1,7 +1,30 @@
 use anyhow::Result;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::avro_codec::{self, AvroCompactedData};
+use crate::column_encoding::EncodedColumn;
+use crate::delta_compactor::{self, DeltaCompactedData, DeltaGroup, DeltaOp, DeltaRecord};
+use crate::json_path::JsonPath;
+use crate::schema_validation::{SchemaValidator, ValidationIssue};
+use crate::validation::{FieldValidationRule, FieldValidator, MonotonicDirection, ValidationError, ValidationSpec};
+
+/// Declares how to pull one field out of an arbitrarily nested input
+/// document via a JSONPath expression, binding the result to `name` and
+/// overriding type inference with `field_type`. Used with
+/// `EfficientCompactor::compact_with_model` to compact documents whose
+/// shape doesn't match the flat top-level-keys layout `compact_comprehensive_data`
+/// expects.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldExtractionRule {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: FieldType,
+    pub path: String,
+}
 
 /// Efficient data compactor using structured format optimization
 #[derive(Debug, Clone)]
@@ -15,6 +38,12 @@ pub struct EfficientCompactor {
     /// Next available ID
     next_url_id: u16,
     next_string_id: u16,
+    /// When `true`, `compact_comprehensive_data` additionally stashes each
+    /// subresource's exact JSON text in `EfficientCompactedData::raw_subresources`,
+    /// so `reconstruct_data` returns it verbatim instead of rebuilding it
+    /// field-by-field from the (lossy, per-type-unified) columnar storage.
+    /// See `new_lossless` and `verify_roundtrip`.
+    lossless: bool,
 }
 
 /// Compacted data using structured format optimization
@@ -28,6 +57,36 @@ pub struct EfficientCompactedData {
     pub dictionaries: Dictionaries,
     /// Compression statistics
     pub stats: CompressionStats,
+    /// Each input subresource's exact JSON text, in original array order;
+    /// only populated by a compactor created via `EfficientCompactor::new_lossless`.
+    /// When present, `reconstruct_data` returns these rows verbatim instead
+    /// of rebuilding them from `data`, guaranteeing an exact round trip.
+    #[serde(default)]
+    pub raw_subresources: Option<Vec<String>>,
+}
+
+/// Result of a validating compaction: the compacted data plus every
+/// constraint violation found in the raw input, keyed by resource URL and field
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactResult {
+    pub data: EfficientCompactedData,
+    pub validation_errors: Vec<ValidationError>,
+}
+
+/// Whether `SchemaValidator` issues abort compaction or are merely collected
+/// alongside the compacted data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaValidationMode {
+    Strict,
+    Lenient,
+}
+
+/// Result of a schema-validating compaction: the compacted data plus every
+/// `ValidationIssue` found against its own inferred `CompactionSchema`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaValidatedResult {
+    pub data: EfficientCompactedData,
+    pub issues: Vec<ValidationIssue>,
 }
 
 /// Schema definition with type information
@@ -48,10 +107,38 @@ pub struct ResourceSchema {
     pub optional: Vec<String>,
     /// Field data types
     pub types: HashMap<String, FieldType>,
+    /// Validation constraints per field, keyed the same way as `types`.
+    /// `infer_schema` auto-populates `min`/`max` for numeric fields from the
+    /// observed range; `regex`/`monotonic` aren't inferred and are left
+    /// unset unless a caller augments the schema before `reconstruct_data`.
+    #[serde(default)]
+    pub constraints: HashMap<String, FieldValidationRule>,
+}
+
+/// A reconstructed value that violated its field's `FieldValidationRule`
+/// (`ResourceSchema::constraints`), raised by `reconstruct_data` instead of
+/// letting a corrupted or out-of-range value round-trip silently
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstraintViolation {
+    pub resource_index: usize,
+    pub property: String,
+    pub reason: String,
+}
+
+impl fmt::Display for ConstraintViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "resource {} property '{}' violated its schema constraint: {}",
+            self.resource_index, self.property, self.reason
+        )
+    }
 }
 
+impl std::error::Error for ConstraintViolation {}
+
 /// Field data types for efficient storage
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FieldType {
     Str,
     Int,
@@ -74,20 +161,46 @@ pub struct ColumnarData {
 pub struct TypedResourceGroup {
     /// Count of resources
     pub count: usize,
-    /// String columns
+    /// String columns: bit-packed validity + run-length-encoded values
+    pub str_cols: HashMap<String, EncodedColumn<u16>>,
+    /// Integer columns: bit-packed validity + run-length-encoded values
+    pub int_cols: HashMap<String, EncodedColumn<i64>>,
+    /// Float columns: bit-packed validity + run-length-encoded values
+    pub float_cols: HashMap<String, EncodedColumn<f64>>,
+    /// Boolean columns (one `bool` per row; absent values are stored as `false`)
+    pub bool_cols: HashMap<String, Vec<bool>>,
+    /// URL columns (references to dictionary): bit-packed validity + run-length-encoded values
+    pub url_cols: HashMap<String, EncodedColumn<u16>>,
+    /// JSON columns (for complex nested data): bit-packed validity + run-length-encoded values
+    pub json_cols: HashMap<String, EncodedColumn<u16>>,
+}
+
+/// `TypedResourceGroup`'s columns expanded back to one `Option<T>`/`bool`
+/// per row, for consumers (row-by-row reconstruction, Arrow/search export)
+/// that want to index by row rather than walk each `EncodedColumn`'s runs
+/// themselves. Built once per group rather than re-decoded per row.
+pub(crate) struct DecodedGroup {
     pub str_cols: HashMap<String, Vec<Option<u16>>>,
-    /// Integer columns
     pub int_cols: HashMap<String, Vec<Option<i64>>>,
-    /// Float columns
     pub float_cols: HashMap<String, Vec<Option<f64>>>,
-    /// Boolean columns (packed as bits)
     pub bool_cols: HashMap<String, Vec<bool>>,
-    /// URL columns (references to dictionary)
     pub url_cols: HashMap<String, Vec<Option<u16>>>,
-    /// JSON columns (for complex nested data)
     pub json_cols: HashMap<String, Vec<Option<u16>>>,
 }
 
+impl TypedResourceGroup {
+    pub(crate) fn decode(&self) -> DecodedGroup {
+        DecodedGroup {
+            str_cols: self.str_cols.iter().map(|(k, v)| (k.clone(), v.decode())).collect(),
+            int_cols: self.int_cols.iter().map(|(k, v)| (k.clone(), v.decode())).collect(),
+            float_cols: self.float_cols.iter().map(|(k, v)| (k.clone(), v.decode())).collect(),
+            bool_cols: self.bool_cols.clone(),
+            url_cols: self.url_cols.iter().map(|(k, v)| (k.clone(), v.decode())).collect(),
+            json_cols: self.json_cols.iter().map(|(k, v)| (k.clone(), v.decode())).collect(),
+        }
+    }
+}
+
 /// Lookup dictionaries
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dictionaries {
@@ -109,6 +222,77 @@ pub struct CompressionStats {
     pub strings_deduplicated: u32,
     pub properties_abbreviated: u32,
     pub resources_processed: u32,
+    /// Per-block codec and size when written via `BlockContainer::write`; empty otherwise
+    #[serde(default)]
+    pub block_stats: Vec<crate::block_container::BlockStats>,
+    /// Records merged away by `FuzzyDeduplicator` prior to compaction
+    #[serde(default)]
+    pub records_deduplicated_fuzzy: u32,
+    /// Clusters formed by `FuzzyDeduplicator::cluster_with_diffs` that had
+    /// more than one member (i.e. an actual near-duplicate merge happened)
+    #[serde(default)]
+    pub fuzzy_clusters_merged: u32,
+    /// Which serialization `compacted_size`/`compression_ratio` were
+    /// measured against. `Json` unless produced by `compact_with_binary_stats`.
+    #[serde(default)]
+    pub format: CompactionFormat,
+    /// Canonical, type-tagged, key-sorted digest of the original input
+    /// `data` passed to `compact_comprehensive_data`, used by
+    /// `EfficientCompactor::verify_roundtrip` to detect reconstruction drift
+    /// without depending on ordering compaction doesn't promise to preserve
+    #[serde(default)]
+    pub digest: String,
+    /// Attributes stored as an override by `EfficientCompactor::compact_with_delta`
+    /// rather than inherited verbatim from the resource type's base record
+    #[serde(default)]
+    pub attributes_deltaed: u32,
+}
+
+/// Serialization format a `CompressionStats` snapshot's size/ratio was
+/// measured against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CompactionFormat {
+    #[default]
+    Json,
+    Cbor,
+}
+
+/// Clean type name from a `resource_type` URL, used both to key
+/// `ColumnarData::resources`/`CompactionSchema::resource_types` during
+/// compaction and to look a resource's schema back up during
+/// `SchemaValidator::validate`.
+pub(crate) fn extract_type_name(resource_type: &str) -> String {
+    resource_type
+        .split('/')
+        .last()
+        .unwrap_or("unknown")
+        .replace("-step", "")
+        .replace('-', "_")
+}
+
+/// Infer a `Value`'s `FieldType`, used both by schema inference during
+/// compaction and by `SchemaValidator::validate` to detect type mismatches
+/// against an already-inferred schema.
+pub(crate) fn infer_field_type(value: &Value) -> FieldType {
+    match value {
+        Value::String(s) => {
+            if s.starts_with("https://") || s.starts_with("http://") {
+                FieldType::Url
+            } else {
+                FieldType::Str
+            }
+        },
+        Value::Number(n) => {
+            if n.is_i64() {
+                FieldType::Int
+            } else {
+                FieldType::Float
+            }
+        },
+        Value::Bool(_) => FieldType::Bool,
+        Value::Array(_) | Value::Object(_) => FieldType::Json,
+        Value::Null => FieldType::Str, // Default to string for nulls
+    }
 }
 
 impl EfficientCompactor {
@@ -120,6 +304,20 @@ impl EfficientCompactor {
             property_abbrevs: Self::init_property_abbreviations(),
             next_url_id: 1,
             next_string_id: 1,
+            lossless: false,
+        }
+    }
+
+    /// Create a compactor in lossless mode: `compact_comprehensive_data`
+    /// additionally stashes each subresource's exact JSON text, so
+    /// `reconstruct_data` is guaranteed to reproduce the original
+    /// `subresources` array exactly (key order, int/float distinctions, and
+    /// any property colliding with a reserved abbreviation included)
+    /// instead of rebuilding it from the lossy columnar representation.
+    pub fn new_lossless() -> Self {
+        Self {
+            lossless: true,
+            ..Self::new()
         }
     }
 
@@ -145,9 +343,325 @@ impl EfficientCompactor {
 
     /// Compact comprehensive subresource data efficiently
     pub fn compact_comprehensive_data(&mut self, data: &Value) -> Result<EfficientCompactedData> {
+        self.compact_comprehensive_data_with_forced_types(data, &HashMap::new())
+    }
+
+    /// Compact `data` as usual, then recompute `compacted_size`/
+    /// `compression_ratio` against the CBOR-encoded byte length instead of
+    /// the JSON string length `compact_comprehensive_data` measures by
+    /// default, so the reported ratio reflects the actual on-wire size
+    /// when callers ship `serialize_binary()` rather than `serde_json::to_string`.
+    pub fn compact_with_binary_stats(&mut self, data: &Value) -> Result<EfficientCompactedData> {
+        let mut compacted = self.compact_comprehensive_data(data)?;
+
+        let binary_size = compacted.serialize_binary()?.len();
+        compacted.stats.compacted_size = binary_size;
+        compacted.stats.compression_ratio = if compacted.stats.original_size > 0 {
+            (compacted.stats.original_size as f32 - binary_size as f32) / compacted.stats.original_size as f32
+        } else {
+            0.0
+        };
+        compacted.stats.format = CompactionFormat::Cbor;
+
+        Ok(compacted)
+    }
+
+    /// Compact a document whose fields aren't pre-flattened onto each
+    /// subresource, by first extracting each declared field via its
+    /// JSONPath and binding it to the rule's name, then compacting as usual
+    /// with the rule's declared type overriding inference for that field.
+    pub fn compact_with_model(
+        &mut self,
+        data: &Value,
+        model: &[FieldExtractionRule],
+    ) -> Result<EfficientCompactedData> {
+        let empty_vec = vec![];
+        let subresources = data
+            .get("subresources")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+
+        let flattened_subresources: Vec<Value> = subresources
+            .iter()
+            .map(|resource| self.apply_model(resource, model))
+            .collect();
+
+        let forced_types: HashMap<String, FieldType> = model
+            .iter()
+            .map(|rule| (rule.name.clone(), rule.field_type.clone()))
+            .collect();
+
+        let flattened_data = serde_json::json!({ "subresources": flattened_subresources });
+        self.compact_comprehensive_data_with_forced_types(&flattened_data, &forced_types)
+    }
+
+    /// Compact `data` as usual, but first check every raw subresource
+    /// against `spec` and surface the violations found instead of silently
+    /// encoding bad data
+    pub fn compact_with_validation(
+        &mut self,
+        data: &Value,
+        spec: &ValidationSpec,
+    ) -> Result<CompactResult> {
+        let empty_vec = vec![];
+        let subresources: Vec<&Value> = data
+            .get("subresources")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec)
+            .iter()
+            .collect();
+
+        let validation_errors = FieldValidator::validate(&subresources, spec);
+        let compacted = self.compact_comprehensive_data(data)?;
+
+        Ok(CompactResult {
+            data: compacted,
+            validation_errors,
+        })
+    }
+
+    /// Compact `data`, then check every raw subresource against the
+    /// resulting `CompactionSchema` via `SchemaValidator::validate`. In
+    /// `SchemaValidationMode::Strict`, any issue aborts with an error
+    /// instead of returning the compacted data; in `Lenient`, issues are
+    /// returned alongside the compacted data so compaction still succeeds.
+    pub fn compact_with_schema_validation(
+        &mut self,
+        data: &Value,
+        mode: SchemaValidationMode,
+    ) -> Result<SchemaValidatedResult> {
+        let compacted = self.compact_comprehensive_data(data)?;
+        let issues = SchemaValidator::validate(data, &compacted.schema);
+
+        if mode == SchemaValidationMode::Strict && !issues.is_empty() {
+            anyhow::bail!(
+                "schema validation failed with {} issue(s): {:?}",
+                issues.len(),
+                issues
+            );
+        }
+
+        Ok(SchemaValidatedResult {
+            data: compacted,
+            issues,
+        })
+    }
+
+    /// Compact `data` as usual for its schema/dictionaries, then separately
+    /// Avro-encode each raw subresource per its resource type's inferred
+    /// schema (see `avro_codec`) — dramatically smaller than the JSON
+    /// dictionaries `compact_comprehensive_data` stores for homogeneous
+    /// resource arrays. `reconstruct_from_avro` reverses this.
+    pub fn compact_to_avro(&mut self, data: &Value) -> Result<AvroCompactedData> {
+        let compacted = self.compact_comprehensive_data(data)?;
+
+        let empty_vec = vec![];
+        let subresources = data
+            .get("subresources")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+
+        let mut grouped: HashMap<String, Vec<&Value>> = HashMap::new();
+        for subresource in subresources {
+            if let Some(resource_type) = subresource.get("resource_type").and_then(|v| v.as_str()) {
+                grouped.entry(extract_type_name(resource_type)).or_default().push(subresource);
+            }
+        }
+
+        let mut records: HashMap<String, Vec<Vec<u8>>> = HashMap::new();
+        for (type_name, resources) in grouped {
+            let Some(resource_schema) = compacted.schema.resource_types.get(&type_name) else {
+                continue;
+            };
+            let fields = avro_codec::sorted_fields(&resource_schema.types);
+            let required: HashSet<String> = resource_schema.required.iter().cloned().collect();
+
+            let encoded_rows = resources
+                .iter()
+                .map(|resource| avro_codec::encode_record(resource, &fields, &required))
+                .collect::<Result<Vec<_>>>()?;
+            records.insert(type_name, encoded_rows);
+        }
+
+        Ok(AvroCompactedData {
+            schema: compacted.schema,
+            dictionaries: compacted.dictionaries,
+            stats: compacted.stats,
+            records,
+        })
+    }
+
+    /// Inverse of `compact_to_avro`: decode every Avro record back into a
+    /// JSON resource, restoring original (unabbreviated) property names via
+    /// `avro_data.dictionaries.properties`, and return `{"subresources": [...]}`
+    pub fn reconstruct_from_avro(avro_data: &AvroCompactedData) -> Result<Value> {
+        let mut reconstructed = Vec::new();
+
+        for (type_name, rows) in &avro_data.records {
+            let Some(resource_schema) = avro_data.schema.resource_types.get(type_name) else {
+                continue;
+            };
+            let fields = avro_codec::sorted_fields(&resource_schema.types);
+            let required: HashSet<String> = resource_schema.required.iter().cloned().collect();
+
+            for row in rows {
+                reconstructed.push(avro_codec::decode_record(
+                    row,
+                    &fields,
+                    &required,
+                    &avro_data.dictionaries.properties,
+                )?);
+            }
+        }
+
+        Ok(serde_json::json!({ "subresources": reconstructed }))
+    }
+
+    /// Compact `data` as usual for its schema/dictionaries, then separately
+    /// delta-encode each raw subresource against its resource type's base
+    /// record: only attributes differing from the base are stored, plus
+    /// their abbreviated names in `relevant_attributes`. `bases` supplies an
+    /// explicit base per type name (as returned by `extract_type_name`); any
+    /// type missing from `bases` falls back to the most-common value per
+    /// field across that type's instances (see `delta_compactor::auto_base`).
+    /// Cuts size further than URL/string dedup alone when a resource type's
+    /// instances are mostly near-identical to a canonical record.
+    /// `reconstruct_from_delta` reverses this.
+    pub fn compact_with_delta(
+        &mut self,
+        data: &Value,
+        bases: &HashMap<String, Value>,
+    ) -> Result<DeltaCompactedData> {
+        let compacted = self.compact_comprehensive_data(data)?;
+        let mut stats = compacted.stats.clone();
+
+        let empty_vec = vec![];
+        let subresources = data
+            .get("subresources")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+
+        let mut resource_groups: HashMap<String, Vec<&Value>> = HashMap::new();
+        for subresource in subresources {
+            if let Some(resource_type) = subresource.get("resource_type").and_then(|v| v.as_str()) {
+                resource_groups
+                    .entry(self.extract_type_name(resource_type))
+                    .or_default()
+                    .push(subresource);
+            }
+        }
+
+        let mut groups = HashMap::new();
+        for (type_name, resources) in resource_groups {
+            let base = bases
+                .get(&type_name)
+                .cloned()
+                .unwrap_or_else(|| delta_compactor::auto_base(&resources));
+
+            let records = resources
+                .iter()
+                .map(|resource| {
+                    let mut overrides = HashMap::new();
+                    let mut relevant_attributes = Vec::new();
+
+                    for (key, op) in delta_compactor::diff_fields(resource, &base) {
+                        let abbreviated = self.abbreviate_property(&key, &mut stats);
+                        relevant_attributes.push(abbreviated.clone());
+                        overrides.insert(abbreviated, op);
+                    }
+
+                    stats.attributes_deltaed += relevant_attributes.len() as u32;
+                    DeltaRecord {
+                        relevant_attributes,
+                        overrides,
+                    }
+                })
+                .collect();
+
+            groups.insert(type_name, DeltaGroup { base, records });
+        }
+
+        let delta_data = DeltaCompactedData {
+            groups,
+            dictionaries: compacted.dictionaries,
+            stats,
+        };
+
+        let compacted_json = serde_json::to_string(&delta_data)?;
+        let compacted_size = compacted_json.len();
+        let original_size = delta_data.stats.original_size;
+        let compression_ratio = if original_size > 0 {
+            (original_size as f32 - compacted_size as f32) / original_size as f32
+        } else {
+            0.0
+        };
+
+        Ok(DeltaCompactedData {
+            stats: CompressionStats {
+                compacted_size,
+                compression_ratio,
+                ..delta_data.stats
+            },
+            ..delta_data
+        })
+    }
+
+    /// Inverse of `compact_with_delta`: rehydrate each instance by starting
+    /// from its resource type's base record and overlaying `overrides`
+    /// (restored to their original, unabbreviated property names via
+    /// `delta_data.dictionaries.properties`)
+    pub fn reconstruct_from_delta(delta_data: &DeltaCompactedData) -> Result<Value> {
+        let mut reconstructed = Vec::new();
+
+        for group in delta_data.groups.values() {
+            for record in &group.records {
+                let mut overrides: HashMap<String, DeltaOp> = HashMap::new();
+                for (abbrev_key, op) in &record.overrides {
+                    let original_key = delta_data
+                        .dictionaries
+                        .properties
+                        .get(abbrev_key)
+                        .cloned()
+                        .unwrap_or_else(|| abbrev_key.clone());
+                    overrides.insert(original_key, op.clone());
+                }
+                reconstructed.push(delta_compactor::apply_delta(&group.base, &overrides));
+            }
+        }
+
+        Ok(serde_json::json!({ "subresources": reconstructed }))
+    }
+
+    /// Apply a field-extraction model to one raw resource, passing
+    /// `resource_type` through unchanged and binding each rule's
+    /// JSONPath-evaluated result under its declared field name
+    fn apply_model(&self, resource: &Value, model: &[FieldExtractionRule]) -> Value {
+        let mut flattened = serde_json::Map::new();
+
+        if let Some(resource_type) = resource.get("resource_type") {
+            flattened.insert("resource_type".to_string(), resource_type.clone());
+        }
+
+        for rule in model {
+            if let Some(value) = JsonPath::evaluate(resource, &rule.path) {
+                flattened.insert(rule.name.clone(), value);
+            }
+        }
+
+        Value::Object(flattened)
+    }
+
+    /// Shared implementation behind `compact_comprehensive_data` and
+    /// `compact_with_model`: `forced_types` overrides `infer_field_type`
+    /// for any field name present in the map
+    fn compact_comprehensive_data_with_forced_types(
+        &mut self,
+        data: &Value,
+        forced_types: &HashMap<String, FieldType>,
+    ) -> Result<EfficientCompactedData> {
         let original_json = serde_json::to_string(data)?;
         let original_size = original_json.len();
-        
+
         let mut stats = CompressionStats {
             original_size,
             compacted_size: 0,
@@ -156,6 +670,12 @@ impl EfficientCompactor {
             strings_deduplicated: 0,
             properties_abbreviated: 0,
             resources_processed: 0,
+            block_stats: Vec::new(),
+            records_deduplicated_fuzzy: 0,
+            fuzzy_clusters_merged: 0,
+            format: CompactionFormat::Json,
+            digest: Self::canonical_digest(data),
+            attributes_deltaed: 0,
         };
 
         // Extract and analyze subresources
@@ -185,13 +705,23 @@ impl EfficientCompactor {
         };
 
         for (type_name, resources) in resource_groups {
-            let typed_group = self.convert_to_columnar(&type_name, &resources, &mut stats)?;
+            let typed_group = self.convert_to_columnar(&type_name, &resources, &mut stats, forced_types)?;
             columnar_data.resources.insert(type_name.clone(), typed_group);
-            
+
             // Update schema
-            schema.resource_types.insert(type_name, self.infer_schema(&resources)?);
+            schema.resource_types.insert(type_name, self.infer_schema(&resources, forced_types)?);
         }
 
+        let raw_subresources = if self.lossless {
+            let mut rows = Vec::with_capacity(subresources.len());
+            for resource in subresources {
+                rows.push(serde_json::to_string(resource)?);
+            }
+            Some(rows)
+        } else {
+            None
+        };
+
         // Calculate final compression stats
         let compacted_data = EfficientCompactedData {
             schema,
@@ -202,6 +732,7 @@ impl EfficientCompactor {
                 properties: self.property_abbrevs.iter().map(|(k, &v)| (v.to_string(), k.clone())).collect(),
             },
             stats: stats.clone(),
+            raw_subresources,
         };
 
         let compacted_json = serde_json::to_string(&compacted_data)?;
@@ -226,12 +757,7 @@ impl EfficientCompactor {
 
     /// Extract clean type name from resource URL
     fn extract_type_name(&self, resource_type: &str) -> String {
-        resource_type
-            .split('/')
-            .last()
-            .unwrap_or("unknown")
-            .replace("-step", "")
-            .replace('-', "_")
+        extract_type_name(resource_type)
     }
 
     /// Convert resources to columnar format
@@ -240,17 +766,17 @@ impl EfficientCompactor {
         _type_name: &str,
         resources: &[&Value],
         stats: &mut CompressionStats,
+        forced_types: &HashMap<String, FieldType>,
     ) -> Result<TypedResourceGroup> {
         let count = resources.len();
-        let mut group = TypedResourceGroup {
-            count,
-            str_cols: HashMap::new(),
-            int_cols: HashMap::new(),
-            float_cols: HashMap::new(),
-            bool_cols: HashMap::new(),
-            url_cols: HashMap::new(),
-            json_cols: HashMap::new(),
-        };
+
+        // Raw, per-row columns before validity-bitmap + run-length encoding
+        let mut raw_str_cols: HashMap<String, Vec<Option<u16>>> = HashMap::new();
+        let mut raw_int_cols: HashMap<String, Vec<Option<i64>>> = HashMap::new();
+        let mut raw_float_cols: HashMap<String, Vec<Option<f64>>> = HashMap::new();
+        let mut bool_cols: HashMap<String, Vec<bool>> = HashMap::new();
+        let mut raw_url_cols: HashMap<String, Vec<Option<u16>>> = HashMap::new();
+        let mut raw_json_cols: HashMap<String, Vec<Option<u16>>> = HashMap::new();
 
         // Analyze all fields across resources
         let mut all_fields: HashMap<String, FieldType> = HashMap::new();
@@ -258,7 +784,10 @@ impl EfficientCompactor {
             if let Some(obj) = resource.as_object() {
                 for (key, value) in obj {
                     let field_name = self.abbreviate_property(key, stats);
-                    let field_type = self.infer_field_type(value);
+                    let field_type = forced_types
+                        .get(key)
+                        .cloned()
+                        .unwrap_or_else(|| self.infer_field_type(value));
                     all_fields.insert(field_name, field_type);
                 }
             }
@@ -267,13 +796,13 @@ impl EfficientCompactor {
         // Initialize columns
         for (field_name, field_type) in &all_fields {
             match field_type {
-                FieldType::Str => { group.str_cols.insert(field_name.clone(), Vec::with_capacity(count)); },
-                FieldType::Int => { group.int_cols.insert(field_name.clone(), Vec::with_capacity(count)); },
-                FieldType::Float => { group.float_cols.insert(field_name.clone(), Vec::with_capacity(count)); },
-                FieldType::Bool => { group.bool_cols.insert(field_name.clone(), Vec::with_capacity(count)); },
-                FieldType::Url => { group.url_cols.insert(field_name.clone(), Vec::with_capacity(count)); },
-                FieldType::Json => { group.json_cols.insert(field_name.clone(), Vec::with_capacity(count)); },
-                _ => { group.json_cols.insert(field_name.clone(), Vec::with_capacity(count)); },
+                FieldType::Str => { raw_str_cols.insert(field_name.clone(), Vec::with_capacity(count)); },
+                FieldType::Int => { raw_int_cols.insert(field_name.clone(), Vec::with_capacity(count)); },
+                FieldType::Float => { raw_float_cols.insert(field_name.clone(), Vec::with_capacity(count)); },
+                FieldType::Bool => { bool_cols.insert(field_name.clone(), Vec::with_capacity(count)); },
+                FieldType::Url => { raw_url_cols.insert(field_name.clone(), Vec::with_capacity(count)); },
+                FieldType::Json => { raw_json_cols.insert(field_name.clone(), Vec::with_capacity(count)); },
+                _ => { raw_json_cols.insert(field_name.clone(), Vec::with_capacity(count)); },
             }
         }
 
@@ -287,30 +816,30 @@ impl EfficientCompactor {
                     match field_type {
                         FieldType::Str => {
                             let str_val = value.and_then(|v| v.as_str()).map(|s| self.intern_string(s, stats));
-                            group.str_cols.get_mut(field_name).unwrap().push(str_val);
+                            raw_str_cols.get_mut(field_name).unwrap().push(str_val);
                         },
                         FieldType::Int => {
                             let int_val = value.and_then(|v| v.as_i64());
-                            group.int_cols.get_mut(field_name).unwrap().push(int_val);
+                            raw_int_cols.get_mut(field_name).unwrap().push(int_val);
                         },
                         FieldType::Float => {
                             let float_val = value.and_then(|v| v.as_f64());
-                            group.float_cols.get_mut(field_name).unwrap().push(float_val);
+                            raw_float_cols.get_mut(field_name).unwrap().push(float_val);
                         },
                         FieldType::Bool => {
                             let bool_val = value.and_then(|v| v.as_bool()).unwrap_or(false);
-                            group.bool_cols.get_mut(field_name).unwrap().push(bool_val);
+                            bool_cols.get_mut(field_name).unwrap().push(bool_val);
                         },
                         FieldType::Url => {
                             let url_val = value.and_then(|v| v.as_str()).map(|s| self.intern_url(s, stats));
-                            group.url_cols.get_mut(field_name).unwrap().push(url_val);
+                            raw_url_cols.get_mut(field_name).unwrap().push(url_val);
                         },
                         FieldType::Json => {
                             let json_val = value.map(|v| {
                                 let json_str = serde_json::to_string(v).unwrap_or_default();
                                 self.intern_string(&json_str, stats)
                             });
-                            group.json_cols.get_mut(field_name).unwrap().push(json_val);
+                            raw_json_cols.get_mut(field_name).unwrap().push(json_val);
                         },
                         _ => {},
                     }
@@ -318,7 +847,15 @@ impl EfficientCompactor {
             }
         }
 
-        Ok(group)
+        Ok(TypedResourceGroup {
+            count,
+            str_cols: raw_str_cols.into_iter().map(|(k, v)| (k, EncodedColumn::encode(&v))).collect(),
+            int_cols: raw_int_cols.into_iter().map(|(k, v)| (k, EncodedColumn::encode(&v))).collect(),
+            float_cols: raw_float_cols.into_iter().map(|(k, v)| (k, EncodedColumn::encode(&v))).collect(),
+            bool_cols,
+            url_cols: raw_url_cols.into_iter().map(|(k, v)| (k, EncodedColumn::encode(&v))).collect(),
+            json_cols: raw_json_cols.into_iter().map(|(k, v)| (k, EncodedColumn::encode(&v))).collect(),
+        })
     }
 
     /// Abbreviate property name using dictionary
@@ -359,25 +896,7 @@ impl EfficientCompactor {
 
     /// Infer field type from JSON value
     fn infer_field_type(&self, value: &Value) -> FieldType {
-        match value {
-            Value::String(s) => {
-                if s.starts_with("https://") || s.starts_with("http://") {
-                    FieldType::Url
-                } else {
-                    FieldType::Str
-                }
-            },
-            Value::Number(n) => {
-                if n.is_i64() {
-                    FieldType::Int
-                } else {
-                    FieldType::Float
-                }
-            },
-            Value::Bool(_) => FieldType::Bool,
-            Value::Array(_) | Value::Object(_) => FieldType::Json,
-            Value::Null => FieldType::Str, // Default to string for nulls
-        }
+        infer_field_type(value)
     }
 
     /// Find original property key given abbreviated name
@@ -398,16 +917,38 @@ impl EfficientCompactor {
     }
 
     /// Infer schema from resources
-    fn infer_schema(&self, resources: &[&Value]) -> Result<ResourceSchema> {
+    fn infer_schema(
+        &self,
+        resources: &[&Value],
+        forced_types: &HashMap<String, FieldType>,
+    ) -> Result<ResourceSchema> {
         let mut all_fields = HashMap::new();
         let mut field_counts = HashMap::new();
+        let mut numeric_ranges: HashMap<String, (f64, f64)> = HashMap::new();
 
         for resource in resources {
             if let Some(obj) = resource.as_object() {
                 for (key, value) in obj {
-                    let field_type = self.infer_field_type(value);
+                    let field_type = forced_types
+                        .get(key)
+                        .cloned()
+                        .unwrap_or_else(|| self.infer_field_type(value));
                     all_fields.insert(key.clone(), field_type);
                     *field_counts.entry(key.clone()).or_insert(0) += 1;
+
+                    if let Some(n) = value.as_f64() {
+                        numeric_ranges
+                            .entry(key.clone())
+                            .and_modify(|(min, max)| {
+                                if n < *min {
+                                    *min = n;
+                                }
+                                if n > *max {
+                                    *max = n;
+                                }
+                            })
+                            .or_insert((n, n));
+                    }
                 }
             }
         }
@@ -424,10 +965,25 @@ impl EfficientCompactor {
             }
         }
 
+        let constraints = numeric_ranges
+            .into_iter()
+            .map(|(field, (min, max))| {
+                (
+                    field,
+                    FieldValidationRule {
+                        min: Some(min),
+                        max: Some(max),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect();
+
         Ok(ResourceSchema {
             required,
             optional,
             types: all_fields,
+            constraints,
         })
     }
 
@@ -449,107 +1005,437 @@ impl Default for EfficientCompactor {
 }
 
 impl EfficientCompactor {
-    /// Reconstruct original data from compacted format
+    /// Reconstruct original data from compacted format. When `compacted`
+    /// carries `raw_subresources` (produced by a lossless compactor), those
+    /// rows are parsed back verbatim and returned in their original order;
+    /// otherwise each resource is rebuilt field-by-field from the columnar
+    /// storage, which only guarantees that *some* representation of each
+    /// value survives, not byte-for-byte fidelity. Either way, every
+    /// reconstructed value is then checked against its field's
+    /// `ResourceSchema::constraints`; the first violation found aborts with
+    /// a `ConstraintViolation` naming the offending resource index,
+    /// property, and rule.
     pub fn reconstruct_data(compacted: &EfficientCompactedData) -> Result<Value> {
-        let mut reconstructed_subresources = Vec::new();
-
-        for (type_name, group) in &compacted.data.resources {
-            // Reconstruct resources of this type
-            for i in 0..group.count {
-                let mut resource = serde_json::Map::new();
-
-                // Add resource type
-                let full_type_url = format!(
-                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/{}-step",
-                    type_name.replace('_', "-")
-                );
-                resource.insert("resource_type".to_string(), Value::String(full_type_url));
-
-                // Reconstruct string fields
-                for (field_name, values) in &group.str_cols {
-                    if let Some(Some(string_id)) = values.get(i) {
-                        if let Some(string_value) = compacted.dictionaries.strings.get(string_id) {
-                            let original_key = compacted.dictionaries.properties
-                                .get(field_name)
-                                .map(|s| s.as_str())
-                                .unwrap_or(field_name);
-                            resource.insert(original_key.to_string(), Value::String(string_value.clone()));
+        let subresources = if let Some(raw_rows) = &compacted.raw_subresources {
+            let mut subresources = Vec::with_capacity(raw_rows.len());
+            for row in raw_rows {
+                subresources.push(serde_json::from_str(row)?);
+            }
+            subresources
+        } else {
+            let mut reconstructed_subresources = Vec::new();
+
+            for (type_name, group) in &compacted.data.resources {
+                let decoded = group.decode();
+                for i in 0..group.count {
+                    reconstructed_subresources.push(Self::reconstruct_resource(compacted, type_name, &decoded, i));
+                }
+            }
+
+            reconstructed_subresources
+        };
+
+        Self::validate_constraints(&compacted.schema, &subresources)?;
+
+        Ok(serde_json::json!({ "subresources": subresources }))
+    }
+
+    /// Check every reconstructed resource's fields against its type's
+    /// `ResourceSchema::constraints`, in `subresources`' order (the order
+    /// `monotonic` is checked against). Returns the first `ConstraintViolation`
+    /// encountered, if any.
+    fn validate_constraints(schema: &CompactionSchema, subresources: &[Value]) -> Result<()> {
+        let mut by_type: HashMap<String, Vec<usize>> = HashMap::new();
+        for (index, resource) in subresources.iter().enumerate() {
+            if let Some(raw_type) = resource.get("resource_type").and_then(|v| v.as_str()) {
+                by_type.entry(extract_type_name(raw_type)).or_default().push(index);
+            }
+        }
+
+        for (type_name, indices) in &by_type {
+            let Some(resource_schema) = schema.resource_types.get(type_name) else {
+                continue;
+            };
+
+            for (field, rule) in &resource_schema.constraints {
+                let regex = rule.regex.as_ref().and_then(|pattern| Regex::new(pattern).ok());
+                let mut sequence: Vec<(usize, f64)> = Vec::new();
+
+                for &index in indices {
+                    let Some(value) = subresources[index].get(field) else {
+                        continue;
+                    };
+
+                    if let (Some(re), Some(s)) = (&regex, value.as_str()) {
+                        if !re.is_match(s) {
+                            return Err(ConstraintViolation {
+                                resource_index: index,
+                                property: field.clone(),
+                                reason: format!("value '{s}' does not match pattern /{}/", re.as_str()),
+                            }
+                            .into());
                         }
                     }
-                }
 
-                // Reconstruct URL fields  
-                for (field_name, values) in &group.url_cols {
-                    if let Some(Some(url_id)) = values.get(i) {
-                        if let Some(url_value) = compacted.dictionaries.urls.get(url_id) {
-                            let original_key = compacted.dictionaries.properties
-                                .get(field_name)
-                                .map(|s| s.as_str())
-                                .unwrap_or(field_name);
-                            resource.insert(original_key.to_string(), Value::String(url_value.clone()));
+                    let Some(n) = value.as_f64() else { continue };
+
+                    if !rule.min_disabled {
+                        if let Some(min) = rule.min {
+                            if n < min {
+                                return Err(ConstraintViolation {
+                                    resource_index: index,
+                                    property: field.clone(),
+                                    reason: format!("value {n} is below minimum {min}"),
+                                }
+                                .into());
+                            }
                         }
                     }
-                }
 
-                // Reconstruct int fields
-                for (field_name, values) in &group.int_cols {
-                    if let Some(Some(int_value)) = values.get(i) {
-                        let original_key = compacted.dictionaries.properties
-                            .get(field_name)
-                            .map(|s| s.as_str())
-                            .unwrap_or(field_name);
-                        resource.insert(original_key.to_string(), Value::Number((*int_value).into()));
+                    if !rule.max_disabled {
+                        if let Some(max) = rule.max {
+                            if n > max {
+                                return Err(ConstraintViolation {
+                                    resource_index: index,
+                                    property: field.clone(),
+                                    reason: format!("value {n} is above maximum {max}"),
+                                }
+                                .into());
+                            }
+                        }
+                    }
+
+                    if rule.monotonic.is_some() {
+                        sequence.push((index, n));
                     }
                 }
 
-                // Reconstruct float fields
-                for (field_name, values) in &group.float_cols {
-                    if let Some(Some(float_value)) = values.get(i) {
-                        let original_key = compacted.dictionaries.properties
-                            .get(field_name)
-                            .map(|s| s.as_str())
-                            .unwrap_or(field_name);
-                        if let Some(num) = serde_json::Number::from_f64(*float_value) {
-                            resource.insert(original_key.to_string(), Value::Number(num));
+                if let Some(direction) = &rule.monotonic {
+                    for window in sequence.windows(2) {
+                        let (_, prev) = window[0];
+                        let (index, curr) = window[1];
+                        let violated = match direction {
+                            MonotonicDirection::Increasing => curr < prev,
+                            MonotonicDirection::Decreasing => curr > prev,
+                        };
+                        if violated {
+                            return Err(ConstraintViolation {
+                                resource_index: index,
+                                property: field.clone(),
+                                reason: format!("value {curr} breaks expected {direction:?} order after {prev}"),
+                            }
+                            .into());
                         }
                     }
                 }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reconstruct a single resource (row `i` of `decoded`, whose type is
+    /// `type_name`) back into JSON. Factored out of `reconstruct_data` so
+    /// `SearchIndex::query` can reconstruct only the rows a search actually
+    /// matched, without rebuilding every resource in the dataset. Takes an
+    /// already-`TypedResourceGroup::decode`d group so repeated calls for
+    /// different rows of the same group don't each re-expand every column's
+    /// run-length encoding from scratch.
+    pub(crate) fn reconstruct_resource(
+        compacted: &EfficientCompactedData,
+        type_name: &str,
+        decoded: &DecodedGroup,
+        i: usize,
+    ) -> Value {
+        let mut resource = serde_json::Map::new();
+
+        // Add resource type
+        let full_type_url = format!(
+            "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/{}-step",
+            type_name.replace('_', "-")
+        );
+        resource.insert("resource_type".to_string(), Value::String(full_type_url));
+
+        // Reconstruct string fields
+        for (field_name, values) in &decoded.str_cols {
+            if let Some(Some(string_id)) = values.get(i) {
+                if let Some(string_value) = compacted.dictionaries.strings.get(string_id) {
+                    let original_key = compacted.dictionaries.properties
+                        .get(field_name)
+                        .map(|s| s.as_str())
+                        .unwrap_or(field_name);
+                    resource.insert(original_key.to_string(), Value::String(string_value.clone()));
+                }
+            }
+        }
+
+        // Reconstruct URL fields
+        for (field_name, values) in &decoded.url_cols {
+            if let Some(Some(url_id)) = values.get(i) {
+                if let Some(url_value) = compacted.dictionaries.urls.get(url_id) {
+                    let original_key = compacted.dictionaries.properties
+                        .get(field_name)
+                        .map(|s| s.as_str())
+                        .unwrap_or(field_name);
+                    resource.insert(original_key.to_string(), Value::String(url_value.clone()));
+                }
+            }
+        }
+
+        // Reconstruct int fields
+        for (field_name, values) in &decoded.int_cols {
+            if let Some(Some(int_value)) = values.get(i) {
+                let original_key = compacted.dictionaries.properties
+                    .get(field_name)
+                    .map(|s| s.as_str())
+                    .unwrap_or(field_name);
+                resource.insert(original_key.to_string(), Value::Number((*int_value).into()));
+            }
+        }
+
+        // Reconstruct float fields
+        for (field_name, values) in &decoded.float_cols {
+            if let Some(Some(float_value)) = values.get(i) {
+                let original_key = compacted.dictionaries.properties
+                    .get(field_name)
+                    .map(|s| s.as_str())
+                    .unwrap_or(field_name);
+                if let Some(num) = serde_json::Number::from_f64(*float_value) {
+                    resource.insert(original_key.to_string(), Value::Number(num));
+                }
+            }
+        }
 
-                // Reconstruct bool fields
-                for (field_name, values) in &group.bool_cols {
-                    if let Some(bool_value) = values.get(i) {
-                        let original_key = compacted.dictionaries.properties
-                            .get(field_name)
-                            .map(|s| s.as_str())
-                            .unwrap_or(field_name);
-                        resource.insert(original_key.to_string(), Value::Bool(*bool_value));
+        // Reconstruct bool fields
+        for (field_name, values) in &decoded.bool_cols {
+            if let Some(bool_value) = values.get(i) {
+                let original_key = compacted.dictionaries.properties
+                    .get(field_name)
+                    .map(|s| s.as_str())
+                    .unwrap_or(field_name);
+                resource.insert(original_key.to_string(), Value::Bool(*bool_value));
+            }
+        }
+
+        // Reconstruct JSON fields
+        for (field_name, values) in &decoded.json_cols {
+            if let Some(Some(json_id)) = values.get(i) {
+                if let Some(json_string) = compacted.dictionaries.strings.get(json_id) {
+                    let original_key = compacted.dictionaries.properties
+                        .get(field_name)
+                        .map(|s| s.as_str())
+                        .unwrap_or(field_name);
+                    if let Ok(json_value) = serde_json::from_str::<Value>(json_string) {
+                        resource.insert(original_key.to_string(), json_value);
+                    } else {
+                        resource.insert(original_key.to_string(), Value::String(json_string.clone()));
                     }
                 }
+            }
+        }
+
+        Value::Object(resource)
+    }
 
-                // Reconstruct JSON fields
-                for (field_name, values) in &group.json_cols {
-                    if let Some(Some(json_id)) = values.get(i) {
-                        if let Some(json_string) = compacted.dictionaries.strings.get(json_id) {
-                            let original_key = compacted.dictionaries.properties
-                                .get(field_name)
-                                .map(|s| s.as_str())
-                                .unwrap_or(field_name);
-                            if let Ok(json_value) = serde_json::from_str::<Value>(json_string) {
-                                resource.insert(original_key.to_string(), json_value);
-                            } else {
-                                resource.insert(original_key.to_string(), Value::String(json_string.clone()));
+    /// Reconstruct `compacted` and recompact that reconstruction, then
+    /// compare the recompaction's canonical digest against `compacted.stats.digest`
+    /// (both computed over the same kind of input, so they're directly
+    /// comparable regardless of whether `compacted` came from a lossless
+    /// compactor). Digests agreeing means the round trip was exact; on
+    /// disagreement, returns the first JSON Pointer (RFC 6901) at which
+    /// `original` and the reconstruction actually diverge.
+    pub fn verify_roundtrip(original: &Value, compacted: &EfficientCompactedData) -> Result<Option<String>> {
+        let reconstructed = Self::reconstruct_data(compacted)?;
+
+        let mut recompactor = EfficientCompactor::new();
+        let recompacted = recompactor.compact_comprehensive_data(&reconstructed)?;
+
+        if recompacted.stats.digest == compacted.stats.digest {
+            Ok(None)
+        } else {
+            Ok(Some(Self::first_diverging_pointer(original, &reconstructed)))
+        }
+    }
+
+    /// Canonical, type-tagged, key-sorted serialization of `value`, hashed
+    /// with CRC32 so `verify_roundtrip` can compare two documents without
+    /// depending on `Value`'s own map ordering or on equality distinguishing
+    /// e.g. `2020` from `2020.0` (it does, but the digest makes that explicit)
+    fn canonical_digest(value: &Value) -> String {
+        let mut canonical = String::new();
+        Self::write_canonical(value, &mut canonical);
+        format!("{:08x}", crc32fast::hash(canonical.as_bytes()))
+    }
+
+    fn write_canonical(value: &Value, out: &mut String) {
+        match value {
+            Value::Null => out.push_str("n:"),
+            Value::Bool(b) => out.push_str(if *b { "b:1" } else { "b:0" }),
+            Value::Number(n) => {
+                if n.is_f64() {
+                    out.push_str(&format!("f:{n}"));
+                } else {
+                    out.push_str(&format!("i:{n}"));
+                }
+            }
+            Value::String(s) => out.push_str(&format!("s:{}:{s}", s.len())),
+            Value::Array(items) => {
+                out.push('[');
+                for item in items {
+                    Self::write_canonical(item, out);
+                    out.push(',');
+                }
+                out.push(']');
+            }
+            Value::Object(map) => {
+                let mut keys: Vec<&String> = map.keys().collect();
+                keys.sort();
+                out.push('{');
+                for key in keys {
+                    out.push_str(&format!("{}:{key}=", key.len()));
+                    Self::write_canonical(&map[key], out);
+                    out.push(',');
+                }
+                out.push('}');
+            }
+        }
+    }
+
+    /// Find the first JSON Pointer (RFC 6901) at which `a` and `b` diverge,
+    /// comparing object keys in sorted order so the result doesn't depend
+    /// on `Value`'s internal map ordering. Returns the document root `"/"`
+    /// if `a == b` (only reachable via a digest collision in `verify_roundtrip`).
+    fn first_diverging_pointer(a: &Value, b: &Value) -> String {
+        Self::diverge_at(a, b, "").unwrap_or_else(|| "/".to_string())
+    }
+
+    fn diverge_at(a: &Value, b: &Value, pointer: &str) -> Option<String> {
+        match (a, b) {
+            (Value::Object(obj_a), Value::Object(obj_b)) => {
+                let mut keys: Vec<&String> = obj_a.keys().chain(obj_b.keys()).collect();
+                keys.sort();
+                keys.dedup();
+                for key in keys {
+                    let child_pointer = format!("{pointer}/{}", Self::escape_pointer_segment(key));
+                    match (obj_a.get(key), obj_b.get(key)) {
+                        (Some(va), Some(vb)) => {
+                            if let Some(p) = Self::diverge_at(va, vb, &child_pointer) {
+                                return Some(p);
                             }
                         }
+                        _ => return Some(child_pointer),
                     }
                 }
-
-                reconstructed_subresources.push(Value::Object(resource));
+                None
+            }
+            (Value::Array(arr_a), Value::Array(arr_b)) => {
+                if arr_a.len() != arr_b.len() {
+                    return Some(pointer.to_string());
+                }
+                arr_a.iter().zip(arr_b.iter()).enumerate().find_map(|(i, (va, vb))| {
+                    Self::diverge_at(va, vb, &format!("{pointer}/{i}"))
+                })
             }
+            _ if a == b => None,
+            _ => Some(pointer.to_string()),
+        }
+    }
+
+    /// Escape a JSON Pointer reference token per RFC 6901 (`~` -> `~0`, `/` -> `~1`)
+    fn escape_pointer_segment(segment: &str) -> String {
+        segment.replace('~', "~0").replace('/', "~1")
+    }
+}
+
+impl EfficientCompactedData {
+    /// Serialize to CBOR instead of JSON. The columnar dictionary ids and
+    /// array lengths this format is dense with encode as compact varints
+    /// in CBOR rather than re-paying JSON's quoting/stringification
+    /// overhead — see `EfficientCompactor::compact_with_binary_stats`,
+    /// which measures `compacted_size` against this instead.
+    pub fn serialize_binary(&self) -> Result<Vec<u8>> {
+        use anyhow::Context as _;
+        serde_cbor::to_vec(self).context("failed to serialize EfficientCompactedData to CBOR")
+    }
+
+    /// Inverse of `serialize_binary`
+    pub fn deserialize_binary(bytes: &[u8]) -> Result<Self> {
+        use anyhow::Context as _;
+        serde_cbor::from_slice(bytes).context("failed to deserialize EfficientCompactedData from CBOR")
+    }
+
+    /// Translate the inferred schema into standard Avro record schemas, one
+    /// per resource type, so compacted output can be read by any Avro tool
+    /// without custom parsing. Abbreviated property keys become Avro field
+    /// names, with the full property URL preserved in each field's `doc` so
+    /// the abbreviation is reversible.
+    pub fn to_avro_schema(&self) -> Value {
+        let records: Vec<Value> = self.schema.resource_types
+            .iter()
+            .map(|(type_name, resource_schema)| self.avro_record_for(type_name, resource_schema))
+            .collect();
+
+        Value::Array(records)
+    }
+
+    fn avro_record_for(&self, type_name: &str, resource_schema: &ResourceSchema) -> Value {
+        let fields: Vec<Value> = resource_schema.types
+            .iter()
+            .map(|(field_name, field_type)| self.avro_field(field_name, field_type))
+            .collect();
+
+        serde_json::json!({
+            "type": "record",
+            "name": Self::avro_safe_name(type_name),
+            "fields": fields,
+        })
+    }
+
+    fn avro_field(&self, field_name: &str, field_type: &FieldType) -> Value {
+        // `ResourceSchema::types` is keyed by full property names; map each
+        // back to its abbreviation for the Avro name and keep the full name in `doc`
+        let abbrev = self
+            .dictionaries
+            .properties
+            .iter()
+            .find(|(_, full_url)| full_url.as_str() == field_name)
+            .map(|(abbrev, _)| abbrev.as_str());
+        let mut field = serde_json::json!({
+            "name": Self::avro_safe_name(abbrev.unwrap_or(field_name)),
+            "type": Self::avro_type(field_type),
+        });
+        if abbrev.is_some() {
+            field["doc"] = Value::String(field_name.to_string());
+        }
+        field
+    }
+
+    /// Map an inferred `FieldType` to its Avro primitive (or nested) type
+    fn avro_type(field_type: &FieldType) -> Value {
+        match field_type {
+            FieldType::Int => Value::String("long".to_string()),
+            FieldType::Float => Value::String("double".to_string()),
+            FieldType::Bool => Value::String("boolean".to_string()),
+            FieldType::Str | FieldType::Url => Value::String("string".to_string()),
+            FieldType::Json => Value::String("string".to_string()),
+            FieldType::Array(inner) => serde_json::json!({
+                "type": "array",
+                "items": Self::avro_type(inner),
+            }),
         }
+    }
 
-        Ok(serde_json::json!({
-            "subresources": reconstructed_subresources
-        }))
+    /// Avro record/field names must match `[A-Za-z_][A-Za-z0-9_]*`; replace
+    /// any other character and prefix with `_` if the name would start with a digit
+    fn avro_safe_name(name: &str) -> String {
+        let mut safe: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+            .collect();
+        if safe.chars().next().map(|c| c.is_ascii_digit()).unwrap_or(true) {
+            safe.insert(0, '_');
+        }
+        safe
     }
 }
 
@@ -665,6 +1551,12 @@ mod tests {
             strings_deduplicated: 0,
             properties_abbreviated: 0,
             resources_processed: 0,
+            block_stats: Vec::new(),
+            records_deduplicated_fuzzy: 0,
+            fuzzy_clusters_merged: 0,
+            format: CompactionFormat::Json,
+            digest: String::new(),
+            attributes_deltaed: 0,
         };
 
         // Test abbreviation
@@ -689,4 +1581,421 @@ mod tests {
         assert!(matches!(compactor.infer_field_type(&json!({"key": "value"})), FieldType::Json));
         assert!(matches!(compactor.infer_field_type(&json!([1, 2, 3])), FieldType::Json));
     }
+
+    #[test]
+    fn test_to_avro_schema_maps_field_types_and_preserves_doc() {
+        let mut compactor = EfficientCompactor::new();
+        let data = json!({
+            "subresources": [
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://atomicdata.dev/properties/isA": "not-an-array-here",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation": 2020
+                }
+            ]
+        });
+
+        let compacted = compactor.compact_comprehensive_data(&data).unwrap();
+        let avro_schema = compacted.to_avro_schema();
+        let records = avro_schema.as_array().unwrap();
+
+        assert_eq!(records.len(), 1);
+        let fields = records[0]["fields"].as_array().unwrap();
+
+        let is_a_field = fields.iter().find(|f| f["name"] == "t").unwrap();
+        assert_eq!(is_a_field["type"], "string");
+        assert_eq!(is_a_field["doc"], "https://atomicdata.dev/properties/isA");
+
+        let year_field = fields.iter().find(|f| f["name"] == "yi").unwrap();
+        assert_eq!(year_field["type"], "long");
+    }
+
+    #[test]
+    fn test_serialize_binary_round_trips() {
+        let mut compactor = EfficientCompactor::new();
+        let data = json!({
+            "subresources": [{
+                "url": "https://example.com/1",
+                "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/foo-step",
+                "https://atomicdata.dev/properties/isA": "bar"
+            }]
+        });
+
+        let compacted = compactor.compact_comprehensive_data(&data).unwrap();
+        let bytes = compacted.serialize_binary().unwrap();
+        let from_binary = EfficientCompactedData::deserialize_binary(&bytes).unwrap();
+
+        let reconstructed = EfficientCompactor::reconstruct_data(&from_binary).unwrap();
+        let original = EfficientCompactor::reconstruct_data(&compacted).unwrap();
+        assert_eq!(reconstructed, original);
+    }
+
+    #[test]
+    fn test_compact_with_binary_stats_measures_cbor_byte_length() {
+        let mut compactor = EfficientCompactor::new();
+        let data = json!({
+            "subresources": (0..10).map(|i| json!({
+                "url": format!("https://example.com/{}", i),
+                "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/foo-step",
+                "https://atomicdata.dev/properties/isA": "bar",
+                "year": 2000 + i
+            })).collect::<Vec<_>>()
+        });
+
+        let compacted = compactor.compact_with_binary_stats(&data).unwrap();
+        let binary_len = compacted.serialize_binary().unwrap().len();
+
+        assert_eq!(compacted.stats.compacted_size, binary_len);
+        assert!(matches!(compacted.stats.format, CompactionFormat::Cbor));
+        assert!(binary_len < compacted.stats.original_size);
+    }
+
+    #[test]
+    fn test_default_format_is_json() {
+        let mut compactor = EfficientCompactor::new();
+        let data = json!({ "subresources": [] });
+        let compacted = compactor.compact_comprehensive_data(&data).unwrap();
+        assert!(matches!(compacted.stats.format, CompactionFormat::Json));
+    }
+
+    #[test]
+    fn test_avro_safe_name_handles_leading_digit_and_special_chars() {
+        assert_eq!(EfficientCompactedData::avro_safe_name("2fast"), "_2fast");
+        assert_eq!(EfficientCompactedData::avro_safe_name("co-name"), "co_name");
+        assert_eq!(EfficientCompactedData::avro_safe_name("ok_name"), "ok_name");
+    }
+
+    #[test]
+    fn test_compact_with_model_extracts_nested_fields_via_jsonpath() {
+        let mut compactor = EfficientCompactor::new();
+
+        let data = json!({
+            "subresources": [
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/funder-step",
+                    "pid": [
+                        {"qualifier": {"classid": "other"}, "value": "irrelevant"},
+                        {"qualifier": {"classid": "grid"}, "value": "grid.123.456"}
+                    ]
+                }
+            ]
+        });
+
+        let model = vec![FieldExtractionRule {
+            name: "gridid".to_string(),
+            field_type: FieldType::Url,
+            path: "$.pid[?(@.qualifier.classid=='grid')].value".to_string(),
+        }];
+
+        let compacted = compactor.compact_with_model(&data, &model).unwrap();
+        let reconstructed = EfficientCompactor::reconstruct_data(&compacted).unwrap();
+
+        let resources = reconstructed["subresources"].as_array().unwrap();
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0]["gridid"], "grid.123.456");
+
+        // The declared type should have won over inference: field should be
+        // stored as a URL column in the schema, not a plain string
+        let funder_schema = compacted.schema.resource_types.get("funder").unwrap();
+        assert!(matches!(funder_schema.types.get("gridid"), Some(FieldType::Url)));
+    }
+
+    #[test]
+    fn test_compact_with_validation_reports_out_of_range_year() {
+        let mut compactor = EfficientCompactor::new();
+        let data = json!({
+            "subresources": [
+                {
+                    "url": "https://example.com/1",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation": 1850
+                }
+            ]
+        });
+
+        let mut spec = crate::validation::ValidationSpec::new();
+        spec.insert(
+            "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation".to_string(),
+            crate::validation::FieldValidationRule {
+                min: Some(1900.0),
+                ..Default::default()
+            },
+        );
+
+        let result = compactor.compact_with_validation(&data, &spec).unwrap();
+        assert_eq!(result.validation_errors.len(), 1);
+        assert_eq!(result.validation_errors[0].resource_url, "https://example.com/1");
+    }
+
+    #[test]
+    fn test_lossless_mode_reconstructs_data_exactly() {
+        let mut compactor = EfficientCompactor::new_lossless();
+
+        // "cn" collides with the company-name abbreviation, "yi" holds a
+        // whole-number float that must not collapse into an int
+        let data = json!({
+            "subresources": [
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "Acme",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation": 2020.0,
+                    "cn": "literal, not an abbreviation"
+                }
+            ]
+        });
+
+        let compacted = compactor.compact_comprehensive_data(&data).unwrap();
+        let reconstructed = EfficientCompactor::reconstruct_data(&compacted).unwrap();
+
+        assert_eq!(reconstructed, data);
+        assert!(compacted.raw_subresources.is_some());
+    }
+
+    #[test]
+    fn test_verify_roundtrip_passes_for_lossless_data() {
+        let data = json!({
+            "subresources": [
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation": 2020
+                }
+            ]
+        });
+
+        let mut compactor = EfficientCompactor::new_lossless();
+        let compacted = compactor.compact_comprehensive_data(&data).unwrap();
+
+        assert_eq!(EfficientCompactor::verify_roundtrip(&data, &compacted).unwrap(), None);
+    }
+
+    #[test]
+    fn test_verify_roundtrip_reports_diverging_pointer_for_lossy_data() {
+        // The default (non-lossless) path unifies a field's column type
+        // across the whole group, so a field that's an int in one row and a
+        // float in another loses one of the two on reconstruction.
+        let data = json!({
+            "subresources": [
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation": 2020
+                },
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation": 2021.5
+                }
+            ]
+        });
+
+        let mut compactor = EfficientCompactor::new();
+        let compacted = compactor.compact_comprehensive_data(&data).unwrap();
+
+        let pointer = EfficientCompactor::verify_roundtrip(&data, &compacted).unwrap();
+        assert!(pointer.is_some());
+    }
+
+    #[test]
+    fn test_compact_with_delta_stores_only_changed_attributes() {
+        let data = json!({
+            "subresources": [
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "Acme",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/country-of-registration": "UG"
+                },
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "Acme",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/country-of-registration": "KE"
+                }
+            ]
+        });
+
+        let mut compactor = EfficientCompactor::new();
+        let delta_data = compactor.compact_with_delta(&data, &HashMap::new()).unwrap();
+
+        let group = delta_data.groups.get("company_information_and_history").unwrap();
+        assert_eq!(group.records.len(), 2);
+        // The auto-derived base already matches the first instance on every field
+        assert!(group.records[0].relevant_attributes.is_empty());
+        // The second instance only diverges on country-of-registration
+        assert_eq!(group.records[1].relevant_attributes, vec!["cr".to_string()]);
+        assert_eq!(delta_data.stats.attributes_deltaed, 1);
+    }
+
+    #[test]
+    fn test_reconstruct_from_delta_round_trips() {
+        let data = json!({
+            "subresources": [
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "Acme",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation": 2020
+                },
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "Globex",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation": 2020
+                }
+            ]
+        });
+
+        let mut compactor = EfficientCompactor::new();
+        let delta_data = compactor.compact_with_delta(&data, &HashMap::new()).unwrap();
+        let reconstructed = EfficientCompactor::reconstruct_from_delta(&delta_data).unwrap();
+
+        let mut original_resources = data["subresources"].as_array().unwrap().clone();
+        let mut reconstructed_resources = reconstructed["subresources"].as_array().unwrap().clone();
+        original_resources.sort_by_key(|r| r.to_string());
+        reconstructed_resources.sort_by_key(|r| r.to_string());
+
+        assert_eq!(original_resources, reconstructed_resources);
+    }
+
+    #[test]
+    fn test_reconstruct_from_delta_preserves_genuine_null_field() {
+        let data = json!({
+            "subresources": [
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "Acme"
+                },
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": Value::Null
+                }
+            ]
+        });
+
+        let mut compactor = EfficientCompactor::new();
+        let delta_data = compactor.compact_with_delta(&data, &HashMap::new()).unwrap();
+        let reconstructed = EfficientCompactor::reconstruct_from_delta(&delta_data).unwrap();
+
+        let mut original_resources = data["subresources"].as_array().unwrap().clone();
+        let mut reconstructed_resources = reconstructed["subresources"].as_array().unwrap().clone();
+        original_resources.sort_by_key(|r| r.to_string());
+        reconstructed_resources.sort_by_key(|r| r.to_string());
+
+        // A genuine `null` field must round-trip as `null`, not be dropped
+        // as if it had been removed relative to the base.
+        assert_eq!(original_resources, reconstructed_resources);
+    }
+
+    #[test]
+    fn test_compact_with_delta_uses_explicit_base_when_supplied() {
+        let data = json!({
+            "subresources": [
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "Acme"
+                }
+            ]
+        });
+
+        let explicit_base = json!({
+            "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+            "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "Acme"
+        });
+        let mut bases = HashMap::new();
+        bases.insert("company_information_and_history".to_string(), explicit_base.clone());
+
+        let mut compactor = EfficientCompactor::new();
+        let delta_data = compactor.compact_with_delta(&data, &bases).unwrap();
+
+        let group = delta_data.groups.get("company_information_and_history").unwrap();
+        assert_eq!(group.base, explicit_base);
+        assert!(group.records[0].relevant_attributes.is_empty());
+    }
+
+    #[test]
+    fn test_infer_schema_captures_observed_numeric_range() {
+        let data = json!({
+            "subresources": [
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation": 1990
+                },
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation": 2020
+                }
+            ]
+        });
+
+        let mut compactor = EfficientCompactor::new();
+        let compacted = compactor.compact_comprehensive_data(&data).unwrap();
+
+        let resource_schema = compacted
+            .schema
+            .resource_types
+            .get("company_information_and_history")
+            .unwrap();
+        let rule = resource_schema
+            .constraints
+            .get("https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation")
+            .unwrap();
+
+        assert_eq!(rule.min, Some(1990.0));
+        assert_eq!(rule.max, Some(2020.0));
+        assert!(!rule.min_disabled);
+        assert!(!rule.max_disabled);
+    }
+
+    #[test]
+    fn test_reconstruct_data_rejects_value_outside_inferred_range() {
+        let data = json!({
+            "subresources": [
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation": 1990
+                }
+            ]
+        });
+
+        let mut compactor = EfficientCompactor::new();
+        let mut compacted = compactor.compact_comprehensive_data(&data).unwrap();
+
+        // Corrupt the inferred range so the (unchanged) reconstructed value now violates it
+        let resource_schema = compacted
+            .schema
+            .resource_types
+            .get_mut("company_information_and_history")
+            .unwrap();
+        resource_schema.constraints.insert(
+            "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation".to_string(),
+            FieldValidationRule {
+                max: Some(1900.0),
+                ..Default::default()
+            },
+        );
+
+        let err = EfficientCompactor::reconstruct_data(&compacted).unwrap_err();
+        let violation = err.downcast_ref::<ConstraintViolation>().unwrap();
+        assert_eq!(violation.resource_index, 0);
+        assert_eq!(
+            violation.property,
+            "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation"
+        );
+        assert!(violation.reason.contains("above maximum"));
+    }
+
+    #[test]
+    fn test_reconstruct_data_passes_when_within_constraints() {
+        let data = json!({
+            "subresources": [
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation": 1990
+                },
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation": 2020
+                }
+            ]
+        });
+
+        let mut compactor = EfficientCompactor::new();
+        let compacted = compactor.compact_comprehensive_data(&data).unwrap();
+
+        assert!(EfficientCompactor::reconstruct_data(&compacted).is_ok());
+    }
 }
\ No newline at end of file
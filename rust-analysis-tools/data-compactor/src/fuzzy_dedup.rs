@@ -0,0 +1,715 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::efficient_compactor::CompressionStats;
+
+/// How two field values are scored for similarity, producing a value in `[0, 1]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum FieldComparator {
+    /// 1.0 if the values are identical, 0.0 otherwise
+    Exact,
+    /// Jaro-Winkler string similarity, well suited to short name-like fields
+    JaroWinkler,
+    /// Compares only the host component of a URL, ignoring scheme/path/query
+    UrlHost,
+}
+
+/// How per-field similarity scores combine into a single aggregate match score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ScoreAggregation {
+    /// Unweighted mean of all scored fields
+    Avg,
+    /// Weighted mean; a field missing from the map defaults to a weight of 1.0
+    WMean(HashMap<String, f64>),
+    /// The single highest-scoring field wins
+    Max,
+}
+
+/// Declares how one field contributes to the match score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldMatchRule {
+    pub field: String,
+    pub comparator: FieldComparator,
+    /// Values to ignore entirely when scoring this field (e.g. placeholder junk)
+    #[serde(default)]
+    pub blacklist: Vec<String>,
+}
+
+/// A full fuzzy-dedup model: which fields to compare, how to aggregate their
+/// scores, and the threshold an aggregate score must cross to call it a match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuzzyDedupConfig {
+    pub fields: Vec<FieldMatchRule>,
+    pub aggregation: ScoreAggregation,
+    pub threshold: f64,
+    /// Field whose value seeds blocking keys (trigram pairs plus a
+    /// prefix/suffix key), so `cluster_with_diffs` only compares records
+    /// that are already plausibly similar instead of every pair in the
+    /// input. `None` falls back to comparing every pair.
+    #[serde(default)]
+    pub blocking_field: Option<String>,
+    /// Field records are sorted by within a candidate block before the
+    /// sliding comparison window is applied. Defaults to `blocking_field`.
+    #[serde(default)]
+    pub order_field: Option<String>,
+    /// How many records on either side of a given record, once a block is
+    /// sorted by `order_field`, are still compared against it
+    #[serde(default = "FuzzyDedupConfig::default_window_size")]
+    pub window_size: usize,
+    /// Hard cap on members considered from a single candidate block, so one
+    /// very common blocking key can't blow up comparison cost
+    #[serde(default = "FuzzyDedupConfig::default_group_max_size")]
+    pub group_max_size: usize,
+}
+
+impl FuzzyDedupConfig {
+    fn default_window_size() -> usize {
+        200
+    }
+
+    fn default_group_max_size() -> usize {
+        200
+    }
+}
+
+/// One cluster of near-duplicate records: `canonical` is the first member
+/// encountered, `diffs` are the field-level changes needed to turn
+/// `canonical` back into each additional member, in encounter order.
+/// `FuzzyDeduplicator::reconstruct_clusters` inverts this.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DedupCluster {
+    pub canonical: Value,
+    pub diffs: Vec<Value>,
+}
+
+/// Clusters near-duplicate JSON records using a field-weighted similarity
+/// model, either merging each cluster into one canonical record that keeps
+/// the longest/most-complete value for every field (`deduplicate`), or
+/// storing one canonical record per cluster plus field-level diffs for the
+/// rest (`cluster_with_diffs`)
+pub struct FuzzyDeduplicator;
+
+impl FuzzyDeduplicator {
+    /// Deduplicate `records` per `config`, recording the number of records
+    /// removed (i.e. merged away) in `stats.records_deduplicated_fuzzy`.
+    pub fn deduplicate_with_stats(
+        records: &[Value],
+        config: &FuzzyDedupConfig,
+        stats: &mut CompressionStats,
+    ) -> Vec<Value> {
+        let merged = Self::deduplicate(records, config);
+        let removed = records.len().saturating_sub(merged.len());
+        stats.records_deduplicated_fuzzy += removed as u32;
+        merged
+    }
+
+    /// Deduplicate `records` per `config`, returning the canonical, merged records
+    pub fn deduplicate(records: &[Value], config: &FuzzyDedupConfig) -> Vec<Value> {
+        let n = records.len();
+        let mut parent: Vec<usize> = (0..n).collect();
+
+        for i in 0..n {
+            for j in (i + 1)..n {
+                if Self::is_match(&records[i], &records[j], config) {
+                    Self::union(&mut parent, i, j);
+                }
+            }
+        }
+
+        let mut clusters: HashMap<usize, Vec<usize>> = HashMap::new();
+        for i in 0..n {
+            let root = Self::find(&mut parent, i);
+            clusters.entry(root).or_default().push(i);
+        }
+
+        let mut cluster_roots: Vec<usize> = clusters.keys().copied().collect();
+        cluster_roots.sort_unstable();
+
+        cluster_roots
+            .into_iter()
+            .map(|root| {
+                let members = &clusters[&root];
+                members.iter().skip(1).fold(records[members[0]].clone(), |acc, &idx| {
+                    Self::merge_records(&acc, &records[idx])
+                })
+            })
+            .collect()
+    }
+
+    /// Cluster `records` per `config`, recording the number of clusters
+    /// that actually merged more than one record in
+    /// `stats.fuzzy_clusters_merged`.
+    pub fn cluster_with_diffs_and_stats(
+        records: &[Value],
+        config: &FuzzyDedupConfig,
+        stats: &mut CompressionStats,
+    ) -> Vec<DedupCluster> {
+        let clusters = Self::cluster_with_diffs(records, config);
+        stats.fuzzy_clusters_merged += clusters.iter().filter(|c| !c.diffs.is_empty()).count() as u32;
+        clusters
+    }
+
+    /// Cluster `records` per `config` via a blocking-then-compare pipeline:
+    /// when `config.blocking_field` is set, records are only compared
+    /// against others sharing a blocking key and within `window_size` of
+    /// each other once the block is sorted by `order_field`; otherwise
+    /// every pair is compared (fine for small inputs). Each resulting
+    /// cluster keeps one canonical record plus a field-level diff per
+    /// additional member; `reconstruct_clusters` inverts this.
+    pub fn cluster_with_diffs(records: &[Value], config: &FuzzyDedupConfig) -> Vec<DedupCluster> {
+        let n = records.len();
+        let mut parent: Vec<usize> = (0..n).collect();
+
+        let candidate_pairs: Vec<(usize, usize)> = match &config.blocking_field {
+            Some(field) => Self::blocked_candidate_pairs(records, field, config),
+            None => (0..n).flat_map(|i| ((i + 1)..n).map(move |j| (i, j))).collect(),
+        };
+
+        for (i, j) in candidate_pairs {
+            if Self::is_match(&records[i], &records[j], config) {
+                Self::union(&mut parent, i, j);
+            }
+        }
+
+        let mut clusters: HashMap<usize, Vec<usize>> = HashMap::new();
+        for i in 0..n {
+            let root = Self::find(&mut parent, i);
+            clusters.entry(root).or_default().push(i);
+        }
+
+        let mut cluster_roots: Vec<usize> = clusters.keys().copied().collect();
+        cluster_roots.sort_unstable();
+
+        cluster_roots
+            .into_iter()
+            .map(|root| {
+                let members = &clusters[&root];
+                let canonical = records[members[0]].clone();
+                let diffs = members[1..]
+                    .iter()
+                    .map(|&idx| Self::diff_against(&canonical, &records[idx]))
+                    .collect();
+                DedupCluster { canonical, diffs }
+            })
+            .collect()
+    }
+
+    /// Inverse of `cluster_with_diffs`: re-expand every cluster's canonical
+    /// record plus its stored diffs back into a flat record list
+    pub fn reconstruct_clusters(clusters: &[DedupCluster]) -> Vec<Value> {
+        clusters
+            .iter()
+            .flat_map(|cluster| {
+                let canonical = cluster.canonical.clone();
+                std::iter::once(cluster.canonical.clone())
+                    .chain(cluster.diffs.iter().map(move |diff| Self::apply_diff(&canonical, diff)))
+            })
+            .collect()
+    }
+
+    /// Blocking keys from `config.blocking_field`'s value, grouped into
+    /// candidate blocks; each block is sorted by `config.order_field` (or
+    /// `field` if unset), capped at `config.group_max_size`, then every
+    /// record is paired with the `config.window_size` records after it
+    fn blocked_candidate_pairs(records: &[Value], field: &str, config: &FuzzyDedupConfig) -> Vec<(usize, usize)> {
+        let order_field = config.order_field.as_deref().unwrap_or(field);
+
+        let mut blocks: HashMap<String, Vec<usize>> = HashMap::new();
+        for (idx, record) in records.iter().enumerate() {
+            if let Some(value) = record.get(field).and_then(|v| v.as_str()) {
+                for key in Self::blocking_keys(value) {
+                    blocks.entry(key).or_default().push(idx);
+                }
+            }
+        }
+
+        let mut pairs = std::collections::HashSet::new();
+        for members in blocks.values() {
+            let mut sorted_members = members.clone();
+            sorted_members.sort_by(|&a, &b| {
+                let a_key = records[a].get(order_field).and_then(|v| v.as_str()).unwrap_or("");
+                let b_key = records[b].get(order_field).and_then(|v| v.as_str()).unwrap_or("");
+                a_key.cmp(b_key)
+            });
+            sorted_members.truncate(config.group_max_size);
+
+            for (pos, &i) in sorted_members.iter().enumerate() {
+                let window_end = (pos + config.window_size + 1).min(sorted_members.len());
+                for &j in &sorted_members[(pos + 1)..window_end] {
+                    pairs.insert(if i < j { (i, j) } else { (j, i) });
+                }
+            }
+        }
+
+        pairs.into_iter().collect()
+    }
+
+    /// Candidate blocking keys for `value`: up to two sorted-trigram-pair
+    /// keys plus a prefix/suffix key, so records whose chosen field
+    /// differs by only a few characters still land in a shared block
+    fn blocking_keys(value: &str) -> Vec<String> {
+        let mut keys = Self::ngram_pair_keys(value, 2);
+        keys.push(Self::prefix_suffix_key(value));
+        keys
+    }
+
+    /// All character 3-grams of `value`, sorted, paired up adjacently and
+    /// concatenated into a key, keeping up to `max` such pair-keys
+    fn ngram_pair_keys(value: &str, max: usize) -> Vec<String> {
+        let lower = value.to_lowercase();
+        let chars: Vec<char> = lower.chars().collect();
+        if chars.len() < 3 {
+            return Vec::new();
+        }
+
+        let mut ngrams: Vec<String> = chars.windows(3).map(|w| w.iter().collect()).collect();
+        ngrams.sort();
+
+        ngrams.chunks(2).take(max).map(|pair| pair.concat()).collect()
+    }
+
+    /// First and last 3 characters of `value`, so short prefix/suffix
+    /// variations of the same string still share a blocking key
+    fn prefix_suffix_key(value: &str) -> String {
+        let lower = value.to_lowercase();
+        let chars: Vec<char> = lower.chars().collect();
+        if chars.is_empty() {
+            return String::new();
+        }
+
+        let prefix: String = chars.iter().take(3).collect();
+        let suffix: String = chars.iter().rev().take(3).collect::<Vec<_>>().into_iter().rev().collect();
+        format!("{}#{}", prefix, suffix)
+    }
+
+    /// The field-level delta needed to turn `canonical` into `member`:
+    /// every key in `member` whose value differs from (or is absent on)
+    /// `canonical`. `apply_diff` re-applies this on top of `canonical`.
+    fn diff_against(canonical: &Value, member: &Value) -> Value {
+        let (Some(canonical_obj), Some(member_obj)) = (canonical.as_object(), member.as_object()) else {
+            return member.clone();
+        };
+
+        let mut diff = serde_json::Map::new();
+        for (key, value) in member_obj {
+            if canonical_obj.get(key) != Some(value) {
+                diff.insert(key.clone(), value.clone());
+            }
+        }
+
+        Value::Object(diff)
+    }
+
+    /// Inverse of `diff_against`: overlay `diff`'s keys onto `canonical`
+    fn apply_diff(canonical: &Value, diff: &Value) -> Value {
+        let (Some(canonical_obj), Some(diff_obj)) = (canonical.as_object(), diff.as_object()) else {
+            return diff.clone();
+        };
+
+        let mut merged = canonical_obj.clone();
+        for (key, value) in diff_obj {
+            merged.insert(key.clone(), value.clone());
+        }
+
+        Value::Object(merged)
+    }
+
+    /// Score `a` vs `b` across every configured field and check the aggregate
+    /// against `config.threshold`
+    fn is_match(a: &Value, b: &Value, config: &FuzzyDedupConfig) -> bool {
+        let scores: Vec<(String, f64)> = config.fields
+            .iter()
+            .filter_map(|rule| Self::field_similarity(a, b, rule).map(|score| (rule.field.clone(), score)))
+            .collect();
+
+        if scores.is_empty() {
+            return false;
+        }
+
+        Self::aggregate(&scores, &config.aggregation) >= config.threshold
+    }
+
+    /// Score a single field, skipping it (returning `None`) when either side
+    /// is missing or blacklisted
+    fn field_similarity(a: &Value, b: &Value, rule: &FieldMatchRule) -> Option<f64> {
+        let a_val = a.get(&rule.field).and_then(|v| v.as_str())?;
+        let b_val = b.get(&rule.field).and_then(|v| v.as_str())?;
+
+        if rule.blacklist.iter().any(|junk| junk == a_val || junk == b_val) {
+            return None;
+        }
+
+        Some(match rule.comparator {
+            FieldComparator::Exact => Self::exact_score(a_val, b_val),
+            FieldComparator::JaroWinkler => Self::jaro_winkler_score(a_val, b_val),
+            FieldComparator::UrlHost => Self::url_host_score(a_val, b_val),
+        })
+    }
+
+    fn aggregate(scores: &[(String, f64)], aggregation: &ScoreAggregation) -> f64 {
+        match aggregation {
+            ScoreAggregation::Avg => scores.iter().map(|(_, s)| s).sum::<f64>() / scores.len() as f64,
+            ScoreAggregation::Max => scores.iter().map(|(_, s)| *s).fold(0.0, f64::max),
+            ScoreAggregation::WMean(weights) => {
+                let (weighted_sum, weight_total) = scores.iter().fold((0.0, 0.0), |(sum, total), (field, score)| {
+                    let weight = weights.get(field).copied().unwrap_or(1.0);
+                    (sum + weight * score, total + weight)
+                });
+                if weight_total <= 0.0 {
+                    0.0
+                } else {
+                    weighted_sum / weight_total
+                }
+            },
+        }
+    }
+
+    fn exact_score(a: &str, b: &str) -> f64 {
+        if a == b { 1.0 } else { 0.0 }
+    }
+
+    fn url_host_score(a: &str, b: &str) -> f64 {
+        Self::exact_score(&Self::url_host(a), &Self::url_host(b))
+    }
+
+    fn url_host(url: &str) -> String {
+        let without_scheme = url.splitn(2, "://").nth(1).unwrap_or(url);
+        without_scheme
+            .split(['/', '?', '#'])
+            .next()
+            .unwrap_or(without_scheme)
+            .trim_start_matches("www.")
+            .to_lowercase()
+    }
+
+    /// Classic Jaro-Winkler string similarity, in `[0, 1]`
+    fn jaro_winkler_score(a: &str, b: &str) -> f64 {
+        let jaro = Self::jaro_score(a, b);
+        if jaro <= 0.0 {
+            return jaro;
+        }
+
+        let a_chars: Vec<char> = a.chars().collect();
+        let b_chars: Vec<char> = b.chars().collect();
+        let prefix_len = a_chars.iter()
+            .zip(b_chars.iter())
+            .take(4)
+            .take_while(|(x, y)| x == y)
+            .count() as f64;
+
+        jaro + prefix_len * 0.1 * (1.0 - jaro)
+    }
+
+    fn jaro_score(a: &str, b: &str) -> f64 {
+        let a_chars: Vec<char> = a.chars().collect();
+        let b_chars: Vec<char> = b.chars().collect();
+        let (a_len, b_len) = (a_chars.len(), b_chars.len());
+
+        if a_len == 0 && b_len == 0 {
+            return 1.0;
+        }
+        if a_len == 0 || b_len == 0 {
+            return 0.0;
+        }
+
+        let match_distance = (a_len.max(b_len) / 2).saturating_sub(1);
+        let mut a_matches = vec![false; a_len];
+        let mut b_matches = vec![false; b_len];
+        let mut matches = 0usize;
+
+        for i in 0..a_len {
+            let lo = i.saturating_sub(match_distance);
+            let hi = (i + match_distance + 1).min(b_len);
+            for j in lo..hi {
+                if b_matches[j] || a_chars[i] != b_chars[j] {
+                    continue;
+                }
+                a_matches[i] = true;
+                b_matches[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+
+        if matches == 0 {
+            return 0.0;
+        }
+
+        let mut transpositions = 0usize;
+        let mut b_index = 0usize;
+        for i in 0..a_len {
+            if !a_matches[i] {
+                continue;
+            }
+            while !b_matches[b_index] {
+                b_index += 1;
+            }
+            if a_chars[i] != b_chars[b_index] {
+                transpositions += 1;
+            }
+            b_index += 1;
+        }
+        let transpositions = transpositions / 2;
+
+        let m = matches as f64;
+        (m / a_len as f64 + m / b_len as f64 + (m - transpositions as f64) / m) / 3.0
+    }
+
+    /// Merge two records believed to be the same entity, keeping the
+    /// longest/most-complete value for every field present on either side
+    fn merge_records(a: &Value, b: &Value) -> Value {
+        let (Some(a_obj), Some(b_obj)) = (a.as_object(), b.as_object()) else {
+            return a.clone();
+        };
+
+        let mut merged = a_obj.clone();
+        for (key, b_value) in b_obj {
+            match merged.get(key) {
+                Some(a_value) if Self::completeness(a_value) >= Self::completeness(b_value) => {},
+                _ => {
+                    merged.insert(key.clone(), b_value.clone());
+                },
+            }
+        }
+
+        Value::Object(merged)
+    }
+
+    /// Rough completeness measure used to pick the "better" of two field values
+    fn completeness(value: &Value) -> usize {
+        match value {
+            Value::Null => 0,
+            Value::String(s) => s.len(),
+            Value::Array(items) => items.len(),
+            Value::Object(obj) => obj.len(),
+            Value::Bool(_) | Value::Number(_) => 1,
+        }
+    }
+
+    fn find(parent: &mut [usize], i: usize) -> usize {
+        if parent[i] != i {
+            parent[i] = Self::find(parent, parent[i]);
+        }
+        parent[i]
+    }
+
+    fn union(parent: &mut [usize], a: usize, b: usize) {
+        let root_a = Self::find(parent, a);
+        let root_b = Self::find(parent, b);
+        if root_a != root_b {
+            parent[root_b] = root_a;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample_config() -> FuzzyDedupConfig {
+        FuzzyDedupConfig {
+            fields: vec![
+                FieldMatchRule { field: "name".to_string(), comparator: FieldComparator::JaroWinkler, blacklist: vec![] },
+                FieldMatchRule { field: "country".to_string(), comparator: FieldComparator::Exact, blacklist: vec!["unknown".to_string()] },
+            ],
+            aggregation: ScoreAggregation::Avg,
+            threshold: 0.85,
+            blocking_field: None,
+            order_field: None,
+            window_size: FuzzyDedupConfig::default_window_size(),
+            group_max_size: FuzzyDedupConfig::default_group_max_size(),
+        }
+    }
+
+    #[test]
+    fn test_jaro_winkler_identical_strings() {
+        assert_eq!(FuzzyDeduplicator::jaro_winkler_score("acme", "acme"), 1.0);
+    }
+
+    #[test]
+    fn test_jaro_winkler_near_match_scores_high() {
+        let score = FuzzyDeduplicator::jaro_winkler_score("EcoBright Solutions", "EcoBright Solutions Ltd");
+        assert!(score > 0.85, "expected high similarity, got {}", score);
+    }
+
+    #[test]
+    fn test_url_host_ignores_scheme_and_path() {
+        let score = FuzzyDeduplicator::url_host_score("https://www.acme.com/about", "http://acme.com/contact");
+        assert_eq!(score, 1.0);
+    }
+
+    #[test]
+    fn test_deduplicate_merges_near_duplicate_records() {
+        let records = vec![
+            json!({"name": "EcoBright Solutions", "country": "UG"}),
+            json!({"name": "EcoBright Solutions Ltd", "country": "UG"}),
+            json!({"name": "Totally Different Co", "country": "KE"}),
+        ];
+
+        let merged = FuzzyDeduplicator::deduplicate(&records, &sample_config());
+
+        assert_eq!(merged.len(), 2);
+    }
+
+    #[test]
+    fn test_deduplicate_with_stats_records_count() {
+        let records = vec![
+            json!({"name": "EcoBright Solutions", "country": "UG"}),
+            json!({"name": "EcoBright Solutions Ltd", "country": "UG"}),
+        ];
+        let mut stats = CompressionStats {
+            original_size: 0,
+            compacted_size: 0,
+            compression_ratio: 0.0,
+            urls_deduplicated: 0,
+            strings_deduplicated: 0,
+            properties_abbreviated: 0,
+            resources_processed: 0,
+            block_stats: Vec::new(),
+            records_deduplicated_fuzzy: 0,
+            fuzzy_clusters_merged: 0,
+            format: Default::default(),
+            digest: String::new(),
+            attributes_deltaed: 0,
+        };
+
+        let merged = FuzzyDeduplicator::deduplicate_with_stats(&records, &sample_config(), &mut stats);
+
+        assert_eq!(merged.len(), 1);
+        assert_eq!(stats.records_deduplicated_fuzzy, 1);
+    }
+
+    #[test]
+    fn test_merge_records_keeps_most_complete_value() {
+        let a = json!({"name": "Acme", "description": "short"});
+        let b = json!({"name": "Acme", "description": "a much longer and more complete description"});
+
+        let merged = FuzzyDeduplicator::merge_records(&a, &b);
+
+        assert_eq!(merged["description"], "a much longer and more complete description");
+    }
+
+    #[test]
+    fn test_blacklisted_values_do_not_contribute_to_score() {
+        let rule = FieldMatchRule {
+            field: "country".to_string(),
+            comparator: FieldComparator::Exact,
+            blacklist: vec!["unknown".to_string()],
+        };
+        let a = json!({"country": "unknown"});
+        let b = json!({"country": "unknown"});
+
+        assert!(FuzzyDeduplicator::field_similarity(&a, &b, &rule).is_none());
+    }
+
+    #[test]
+    fn test_cluster_with_diffs_groups_near_duplicates_and_stores_diffs() {
+        let records = vec![
+            json!({"name": "EcoBright Solutions", "country": "UG"}),
+            json!({"name": "EcoBright Solutions Ltd", "country": "UG"}),
+            json!({"name": "Totally Different Co", "country": "KE"}),
+        ];
+
+        let clusters = FuzzyDeduplicator::cluster_with_diffs(&records, &sample_config());
+
+        assert_eq!(clusters.len(), 2);
+        let merged = clusters.iter().find(|c| !c.diffs.is_empty()).unwrap();
+        assert_eq!(merged.canonical["name"], "EcoBright Solutions");
+        assert_eq!(merged.diffs.len(), 1);
+        assert_eq!(merged.diffs[0]["name"], "EcoBright Solutions Ltd");
+        assert!(merged.diffs[0].get("country").is_none(), "unchanged fields should be omitted from the diff");
+    }
+
+    #[test]
+    fn test_reconstruct_clusters_round_trips_cluster_with_diffs() {
+        let records = vec![
+            json!({"name": "EcoBright Solutions", "country": "UG"}),
+            json!({"name": "EcoBright Solutions Ltd", "country": "UG"}),
+            json!({"name": "Totally Different Co", "country": "KE"}),
+        ];
+
+        let clusters = FuzzyDeduplicator::cluster_with_diffs(&records, &sample_config());
+        let mut reconstructed = FuzzyDeduplicator::reconstruct_clusters(&clusters);
+        let mut expected = records.clone();
+
+        reconstructed.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+        expected.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+        assert_eq!(reconstructed, expected);
+    }
+
+    #[test]
+    fn test_cluster_with_diffs_and_stats_records_clusters_merged() {
+        let records = vec![
+            json!({"name": "EcoBright Solutions", "country": "UG"}),
+            json!({"name": "EcoBright Solutions Ltd", "country": "UG"}),
+            json!({"name": "Totally Different Co", "country": "KE"}),
+        ];
+        let mut stats = CompressionStats {
+            original_size: 0,
+            compacted_size: 0,
+            compression_ratio: 0.0,
+            urls_deduplicated: 0,
+            strings_deduplicated: 0,
+            properties_abbreviated: 0,
+            resources_processed: 0,
+            block_stats: Vec::new(),
+            records_deduplicated_fuzzy: 0,
+            fuzzy_clusters_merged: 0,
+            format: Default::default(),
+            digest: String::new(),
+            attributes_deltaed: 0,
+        };
+
+        let clusters = FuzzyDeduplicator::cluster_with_diffs_and_stats(&records, &sample_config(), &mut stats);
+
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(stats.fuzzy_clusters_merged, 1);
+    }
+
+    #[test]
+    fn test_blocking_field_still_finds_near_duplicates() {
+        let records = vec![
+            json!({"name": "Acme Corporation", "country": "UG"}),
+            json!({"name": "Acme Corporaiton", "country": "UG"}),
+            json!({"name": "Totally Different Co", "country": "KE"}),
+        ];
+        let mut config = sample_config();
+        config.blocking_field = Some("name".to_string());
+
+        let clusters = FuzzyDeduplicator::cluster_with_diffs(&records, &config);
+
+        assert_eq!(clusters.len(), 2);
+        assert!(clusters.iter().any(|c| !c.diffs.is_empty()));
+    }
+
+    #[test]
+    fn test_blocking_keys_share_a_key_for_near_identical_strings() {
+        // Same length and prefix, but the transposition changes the last 3
+        // chars; the sorted-trigram keys should still land both strings in
+        // a shared block.
+        let a = FuzzyDeduplicator::blocking_keys("Acme Corporation");
+        let b = FuzzyDeduplicator::blocking_keys("Acme Corporaiton");
+
+        assert!(a.iter().any(|key| b.contains(key)), "expected at least one shared blocking key, got {:?} vs {:?}", a, b);
+    }
+
+    #[test]
+    fn test_group_max_size_caps_block_membership() {
+        let records: Vec<Value> = (0..10)
+            .map(|i| json!({"name": format!("Acme Corp {}", i), "country": "UG"}))
+            .collect();
+        let mut config = sample_config();
+        config.blocking_field = Some("name".to_string());
+        config.group_max_size = 1;
+
+        let clusters = FuzzyDeduplicator::cluster_with_diffs(&records, &config);
+
+        // With group_max_size capped at 1, no block ever has more than one
+        // member to compare, so nothing can merge even though every name
+        // is a near-duplicate of the others.
+        assert_eq!(clusters.len(), records.len());
+    }
+}
@@ -0,0 +1,495 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Fields whose value (or array of values) is itself a resource URL: either
+/// the resource's own subject id, or a reference pointing at another
+/// resource in the same document. Atomic Data documents repeat the same
+/// 40+ character `https://` ids across every one of these, so interning
+/// them into small integers is where the savings come from.
+const LINK_FIELDS: &[&str] = &[
+    "url",
+    "@id",
+    "https://atomicdata.dev/properties/parent",
+    "https://atomicdata.dev/properties/lastCommit",
+    "https://atomicdata.dev/properties/isA",
+];
+
+/// `(full IRI, short edge label)` for the `LINK_FIELDS` that represent an
+/// actual graph edge out of a resource (as opposed to `url`/`@id`, which
+/// name the resource itself). Mirrors `Context::atomic_data_defaults`'s
+/// `p`/`lc`/`t` terms so path hops read the same abbreviated way the rest
+/// of the crate already abbreviates these three properties.
+const EDGE_FIELDS: &[(&str, &str)] = &[
+    ("https://atomicdata.dev/properties/parent", "p"),
+    ("https://atomicdata.dev/properties/lastCommit", "lc"),
+    ("https://atomicdata.dev/properties/isA", "t"),
+];
+
+/// Graph-aware compactor for link-heavy Atomic Data: instead of transposing
+/// fields into columns like `EfficientCompactor`/`TrulyEfficientCompactor`,
+/// this builds a reference graph by interning every distinct resource URL
+/// seen in [`LINK_FIELDS`] into a small integer id, shared by a single
+/// id -> URL table. Resources that mostly consist of repeated cross-references
+/// (`parent`, `lastCommit`, `isA`) compact far better this way than columnar
+/// encoding, since every occurrence of a repeated URL drops to 1-2 bytes.
+#[derive(Debug, Clone, Default)]
+pub struct GraphCompactor {
+    url_ids: HashMap<String, u32>,
+    next_id: u32,
+}
+
+/// Graph-compacted data: resources with their link fields rewritten to
+/// interned ids, plus the id -> URL table `reconstruct` needs to undo it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphCompactedData {
+    /// Each resource, with every [`LINK_FIELDS`] occurrence replaced by an
+    /// interned id; all other fields untouched
+    pub resources: Vec<Value>,
+    /// id -> URL table, shared across every resource and every link field
+    pub urls: HashMap<u32, String>,
+    pub stats: GraphCompactionStats,
+}
+
+/// One hop in a [`GraphCompactedData::shortest_path`] result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphPathHop {
+    pub url: String,
+    /// Edge field traversed to reach this hop from the previous one
+    /// (`"p"`/`"lc"`/`"t"`, per [`EDGE_FIELDS`]); `None` for the starting url
+    pub via: Option<String>,
+}
+
+impl GraphCompactedData {
+    /// Directed edges `(subject url, edge label, target url)` implied by the
+    /// `parent`/`lastCommit`/`isA` fields already present on each resource,
+    /// resolved back from interned ids through `self.urls`
+    fn edges(&self) -> Vec<(String, &'static str, String)> {
+        let mut edges = Vec::new();
+        for resource in &self.resources {
+            let Some(obj) = resource.as_object() else {
+                continue;
+            };
+            let Some(subject) = obj
+                .get("url")
+                .and_then(|v| v.as_u64())
+                .and_then(|id| self.urls.get(&(id as u32)))
+            else {
+                continue;
+            };
+            for (field, label) in EDGE_FIELDS {
+                let Some(value) = obj.get(*field) else {
+                    continue;
+                };
+                for id in Self::interned_ids(value) {
+                    if let Some(target) = self.urls.get(&id) {
+                        edges.push((subject.clone(), *label, target.clone()));
+                    }
+                }
+            }
+        }
+        edges
+    }
+
+    fn interned_ids(value: &Value) -> Vec<u32> {
+        match value {
+            Value::Number(n) => n.as_u64().map(|n| vec![n as u32]).unwrap_or_default(),
+            Value::Array(items) => items.iter().flat_map(Self::interned_ids).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// BFS over the directed edge set for the shortest (fewest-hop) path
+    /// from `from_url` to `to_url`, returning the ordered URLs plus the
+    /// edge field traversed at each hop. `None` if they're disconnected.
+    pub fn shortest_path(&self, from_url: &str, to_url: &str) -> Option<Vec<GraphPathHop>> {
+        if from_url == to_url {
+            return Some(vec![GraphPathHop {
+                url: from_url.to_string(),
+                via: None,
+            }]);
+        }
+
+        let edges = self.edges();
+        let mut adjacency: HashMap<&str, Vec<(&str, &'static str)>> = HashMap::new();
+        for (from, label, to) in &edges {
+            adjacency.entry(from.as_str()).or_default().push((to.as_str(), *label));
+        }
+
+        let mut came_from: HashMap<&str, (&str, &'static str)> = HashMap::new();
+        let mut visited: HashSet<&str> = HashSet::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(from_url);
+        visited.insert(from_url);
+
+        while let Some(current) = queue.pop_front() {
+            if current == to_url {
+                let mut hops = Vec::new();
+                let mut node = current;
+                loop {
+                    let via = came_from.get(node).map(|(_, label)| label.to_string());
+                    let prev = came_from.get(node).map(|(prev, _)| *prev);
+                    hops.push(GraphPathHop {
+                        url: node.to_string(),
+                        via,
+                    });
+                    match prev {
+                        Some(prev) => node = prev,
+                        None => break,
+                    }
+                }
+                hops.reverse();
+                return Some(hops);
+            }
+
+            if let Some(neighbors) = adjacency.get(current) {
+                for &(next, label) in neighbors {
+                    if visited.insert(next) {
+                        came_from.insert(next, (current, label));
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Follow `parent` edges from `url` up to the root, returning the URLs
+    /// in order from the immediate parent to the ultimate root. Empty if
+    /// `url` has no `parent` edge; stops (without looping) on a cycle.
+    pub fn ancestors(&self, url: &str) -> Vec<String> {
+        let edges = self.edges();
+        let mut parent_of: HashMap<&str, &str> = HashMap::new();
+        for (from, label, to) in &edges {
+            if *label == "p" {
+                parent_of.entry(from.as_str()).or_insert(to.as_str());
+            }
+        }
+
+        let mut result = Vec::new();
+        let mut visited: HashSet<String> = HashSet::new();
+        visited.insert(url.to_string());
+        let mut current = url.to_string();
+        while let Some(&parent) = parent_of.get(current.as_str()) {
+            if !visited.insert(parent.to_string()) {
+                break;
+            }
+            result.push(parent.to_string());
+            current = parent.to_string();
+        }
+        result
+    }
+}
+
+/// Compression statistics for [`GraphCompactor`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphCompactionStats {
+    pub original_size: usize,
+    pub compacted_size: usize,
+    pub compression_ratio: f32,
+    /// Distinct resource URLs interned into the id table
+    pub urls_interned: u32,
+    pub resources_processed: u32,
+}
+
+impl GraphCompactor {
+    pub fn new() -> Self {
+        Self {
+            url_ids: HashMap::new(),
+            next_id: 1,
+        }
+    }
+
+    /// Compact data by interning every [`LINK_FIELDS`] URL into an integer id
+    pub fn compact(&mut self, data: &Value) -> Result<GraphCompactedData> {
+        let original_json = serde_json::to_string(data)?;
+        let original_size = original_json.len();
+
+        let empty_vec = vec![];
+        let subresources = data
+            .get("subresources")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+
+        let resources: Vec<Value> = subresources.iter().map(|r| self.intern_resource(r)).collect();
+        let resources_processed = resources.len() as u32;
+
+        let urls = self.url_ids.iter().map(|(url, &id)| (id, url.clone())).collect();
+
+        let mut compacted = GraphCompactedData {
+            resources,
+            urls,
+            stats: GraphCompactionStats {
+                original_size,
+                compacted_size: 0,
+                compression_ratio: 0.0,
+                urls_interned: self.url_ids.len() as u32,
+                resources_processed,
+            },
+        };
+
+        let compacted_json = serde_json::to_string(&compacted)?;
+        compacted.stats.compacted_size = compacted_json.len();
+        compacted.stats.compression_ratio = if original_size > 0 {
+            (original_size as f32 - compacted.stats.compacted_size as f32) / original_size as f32
+        } else {
+            0.0
+        };
+
+        Ok(compacted)
+    }
+
+    fn intern_resource(&mut self, resource: &Value) -> Value {
+        let Some(obj) = resource.as_object() else {
+            return resource.clone();
+        };
+
+        let mut out = serde_json::Map::new();
+        for (key, value) in obj {
+            if LINK_FIELDS.contains(&key.as_str()) {
+                out.insert(key.clone(), self.intern_value(value));
+            } else {
+                out.insert(key.clone(), value.clone());
+            }
+        }
+        Value::Object(out)
+    }
+
+    fn intern_value(&mut self, value: &Value) -> Value {
+        match value {
+            Value::String(url) => Value::Number(self.intern_url(url).into()),
+            Value::Array(items) => Value::Array(items.iter().map(|v| self.intern_value(v)).collect()),
+            other => other.clone(),
+        }
+    }
+
+    fn intern_url(&mut self, url: &str) -> u32 {
+        if let Some(&id) = self.url_ids.get(url) {
+            return id;
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.url_ids.insert(url.to_string(), id);
+        id
+    }
+
+    /// Inverse of `compact`: walks the id table to restore every interned
+    /// [`LINK_FIELDS`] occurrence back to its original URL string
+    pub fn reconstruct(compacted: &GraphCompactedData) -> Result<Value> {
+        let subresources: Vec<Value> = compacted
+            .resources
+            .iter()
+            .map(|resource| Self::expand_resource(resource, &compacted.urls))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(serde_json::json!({ "subresources": subresources }))
+    }
+
+    fn expand_resource(resource: &Value, urls: &HashMap<u32, String>) -> Result<Value> {
+        let Some(obj) = resource.as_object() else {
+            return Ok(resource.clone());
+        };
+
+        let mut out = serde_json::Map::new();
+        for (key, value) in obj {
+            if LINK_FIELDS.contains(&key.as_str()) {
+                out.insert(key.clone(), Self::expand_value(value, urls)?);
+            } else {
+                out.insert(key.clone(), value.clone());
+            }
+        }
+        Ok(Value::Object(out))
+    }
+
+    fn expand_value(value: &Value, urls: &HashMap<u32, String>) -> Result<Value> {
+        match value {
+            Value::Number(n) => {
+                let id = n
+                    .as_u64()
+                    .context("interned reference id must be a non-negative integer")? as u32;
+                let url = urls
+                    .get(&id)
+                    .with_context(|| format!("no URL interned for id {id}"))?;
+                Ok(Value::String(url.clone()))
+            }
+            Value::Array(items) => {
+                let expanded = items
+                    .iter()
+                    .map(|v| Self::expand_value(v, urls))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Value::Array(expanded))
+            }
+            other => Ok(other.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample_data() -> Value {
+        json!({
+            "subresources": [
+                {
+                    "url": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/resource/a",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/step",
+                    "https://atomicdata.dev/properties/isA": [
+                        "https://atomicdata.dev/classes/Step"
+                    ],
+                    "https://atomicdata.dev/properties/parent": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/resource/root",
+                    "https://atomicdata.dev/properties/lastCommit": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/commit/1",
+                },
+                {
+                    "url": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/resource/b",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/step",
+                    "https://atomicdata.dev/properties/isA": [
+                        "https://atomicdata.dev/classes/Step"
+                    ],
+                    "https://atomicdata.dev/properties/parent": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/resource/root",
+                    "https://atomicdata.dev/properties/lastCommit": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/commit/2",
+                },
+            ]
+        })
+    }
+
+    #[test]
+    fn test_repeated_reference_urls_share_one_interned_id() {
+        let mut compactor = GraphCompactor::new();
+        let compacted = compactor.compact(&sample_data()).unwrap();
+
+        // "root" and the isA class are referenced by both resources; each
+        // distinct URL should only have been interned once
+        assert_eq!(compacted.stats.urls_interned, compacted.urls.len() as u32);
+        assert!(compacted.stats.urls_interned < 8, "shared references should dedupe below 8 distinct ids");
+    }
+
+    #[test]
+    fn test_reconstruct_restores_original_data() {
+        let original = sample_data();
+        let mut compactor = GraphCompactor::new();
+        let compacted = compactor.compact(&original).unwrap();
+        let reconstructed = GraphCompactor::reconstruct(&compacted).unwrap();
+
+        assert_eq!(reconstructed, original);
+    }
+
+    #[test]
+    fn test_link_fields_are_rewritten_to_integers() {
+        let mut compactor = GraphCompactor::new();
+        let compacted = compactor.compact(&sample_data()).unwrap();
+
+        let first = &compacted.resources[0];
+        assert!(first["url"].is_number());
+        assert!(first["https://atomicdata.dev/properties/parent"].is_number());
+        assert!(first["https://atomicdata.dev/properties/isA"][0].is_number());
+        // resource_type is not a link field the request calls out, so it's left as-is
+        assert!(first["resource_type"].is_string());
+    }
+
+    #[test]
+    fn test_compaction_beats_original_on_link_heavy_data() {
+        let mut compactor = GraphCompactor::new();
+        let compacted = compactor.compact(&sample_data()).unwrap();
+
+        assert!(compacted.stats.compression_ratio > 0.0, "shared references should compress");
+    }
+
+    #[test]
+    fn test_empty_subresources_round_trips() {
+        let original = json!({ "subresources": [] });
+        let mut compactor = GraphCompactor::new();
+        let compacted = compactor.compact(&original).unwrap();
+
+        assert_eq!(compacted.stats.urls_interned, 0);
+        assert_eq!(GraphCompactor::reconstruct(&compacted).unwrap(), original);
+    }
+
+    fn chain_data() -> Value {
+        // root <- a <- b <- c, via "parent"; "a" and "c" also share an isA target
+        json!({
+            "subresources": [
+                {
+                    "url": "root_url",
+                    "resource_type": "type_str",
+                },
+                {
+                    "url": "a_url",
+                    "resource_type": "type_str",
+                    "https://atomicdata.dev/properties/parent": "root_url",
+                    "https://atomicdata.dev/properties/isA": ["class_step"],
+                },
+                {
+                    "url": "b_url",
+                    "resource_type": "type_str",
+                    "https://atomicdata.dev/properties/parent": "a_url",
+                },
+                {
+                    "url": "c_url",
+                    "resource_type": "type_str",
+                    "https://atomicdata.dev/properties/parent": "b_url",
+                    "https://atomicdata.dev/properties/isA": ["class_step"],
+                },
+            ]
+        })
+    }
+
+    #[test]
+    fn test_shortest_path_follows_parent_chain() {
+        let mut compactor = GraphCompactor::new();
+        let compacted = compactor.compact(&chain_data()).unwrap();
+
+        let path = compacted.shortest_path("c_url", "root_url").unwrap();
+        let urls: Vec<&str> = path.iter().map(|hop| hop.url.as_str()).collect();
+        assert_eq!(urls, vec!["c_url", "b_url", "a_url", "root_url"]);
+        assert_eq!(path[0].via, None);
+        assert_eq!(path[1].via.as_deref(), Some("p"));
+        assert_eq!(path[3].via.as_deref(), Some("p"));
+    }
+
+    #[test]
+    fn test_shortest_path_prefers_the_shorter_edge_not_just_parent() {
+        let mut compactor = GraphCompactor::new();
+        let compacted = compactor.compact(&chain_data()).unwrap();
+
+        // "a_url" points at "class_step" directly via isA, so the path is a
+        // single "t" hop; a_url's parent edge leads to "root_url" instead
+        let path = compacted.shortest_path("a_url", "class_step").unwrap();
+        let urls: Vec<&str> = path.iter().map(|hop| hop.url.as_str()).collect();
+        assert_eq!(urls, vec!["a_url", "class_step"]);
+        assert_eq!(path[1].via.as_deref(), Some("t"));
+    }
+
+    #[test]
+    fn test_shortest_path_returns_none_when_disconnected() {
+        let mut compactor = GraphCompactor::new();
+        let compacted = compactor.compact(&chain_data()).unwrap();
+
+        assert_eq!(compacted.shortest_path("root_url", "c_url"), None);
+    }
+
+    #[test]
+    fn test_shortest_path_same_url_is_a_single_hop() {
+        let mut compactor = GraphCompactor::new();
+        let compacted = compactor.compact(&chain_data()).unwrap();
+
+        let path = compacted.shortest_path("a_url", "a_url").unwrap();
+        assert_eq!(path.len(), 1);
+        assert_eq!(path[0].via, None);
+    }
+
+    #[test]
+    fn test_ancestors_walks_parent_chain_to_root() {
+        let mut compactor = GraphCompactor::new();
+        let compacted = compactor.compact(&chain_data()).unwrap();
+
+        assert_eq!(
+            compacted.ancestors("c_url"),
+            vec!["b_url".to_string(), "a_url".to_string(), "root_url".to_string()]
+        );
+        assert_eq!(compacted.ancestors("root_url"), Vec::<String>::new());
+    }
+}
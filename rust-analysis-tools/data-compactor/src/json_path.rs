@@ -0,0 +1,215 @@
+use serde_json::Value;
+
+/// One step of a parsed JSONPath expression
+enum Segment {
+    Key(String),
+    Index(usize),
+    Wildcard,
+    /// Raw filter source, e.g. `@.qualifier.classid=='grid'`
+    Filter(String),
+}
+
+/// A minimal JSONPath evaluator supporting the subset the compactor's field
+/// extraction model needs: dotted member access, array indices, `[*]`
+/// wildcards, and `[?(@.path==literal)]` equality filters. Not a general
+/// JSONPath implementation — unsupported syntax simply fails to match.
+pub struct JsonPath;
+
+impl JsonPath {
+    /// Evaluate `path` against `root`, returning the first matching value.
+    /// When a step yields multiple candidates (a wildcard or filter), the
+    /// rest of the path is evaluated against all of them and the first
+    /// overall match wins.
+    pub fn evaluate(root: &Value, path: &str) -> Option<Value> {
+        let segments = Self::parse(path)?;
+
+        let mut candidates: Vec<&Value> = vec![root];
+        for segment in &segments {
+            let mut next = Vec::new();
+            for candidate in candidates {
+                match segment {
+                    Segment::Key(key) => {
+                        if let Some(v) = candidate.get(key) {
+                            next.push(v);
+                        }
+                    },
+                    Segment::Index(i) => {
+                        if let Some(v) = candidate.as_array().and_then(|arr| arr.get(*i)) {
+                            next.push(v);
+                        }
+                    },
+                    Segment::Wildcard => {
+                        if let Some(arr) = candidate.as_array() {
+                            next.extend(arr.iter());
+                        } else if let Some(obj) = candidate.as_object() {
+                            next.extend(obj.values());
+                        }
+                    },
+                    Segment::Filter(expr) => {
+                        if let Some(arr) = candidate.as_array() {
+                            next.extend(arr.iter().filter(|item| Self::filter_matches(item, expr)));
+                        }
+                    },
+                }
+            }
+            if next.is_empty() {
+                return None;
+            }
+            candidates = next;
+        }
+
+        candidates.into_iter().next().cloned()
+    }
+
+    fn parse(path: &str) -> Option<Vec<Segment>> {
+        let path = path.strip_prefix('$')?;
+        let chars: Vec<char> = path.chars().collect();
+        let mut segments = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            match chars[i] {
+                '.' => {
+                    i += 1;
+                    let start = i;
+                    while i < chars.len() && chars[i] != '.' && chars[i] != '[' {
+                        i += 1;
+                    }
+                    if start < i {
+                        segments.push(Segment::Key(chars[start..i].iter().collect()));
+                    }
+                },
+                '[' => {
+                    let start = i + 1;
+                    let mut depth = 1;
+                    let mut j = start;
+                    while j < chars.len() && depth > 0 {
+                        match chars[j] {
+                            '[' => depth += 1,
+                            ']' => {
+                                depth -= 1;
+                                if depth == 0 {
+                                    break;
+                                }
+                            },
+                            _ => {},
+                        }
+                        j += 1;
+                    }
+                    let content: String = chars[start..j].iter().collect();
+                    i = j + 1;
+
+                    if content == "*" {
+                        segments.push(Segment::Wildcard);
+                    } else if let Some(filter_expr) = content.strip_prefix("?(").and_then(|s| s.strip_suffix(')')) {
+                        segments.push(Segment::Filter(filter_expr.to_string()));
+                    } else if let Ok(idx) = content.parse::<usize>() {
+                        segments.push(Segment::Index(idx));
+                    } else {
+                        let key = content.trim_matches(|c| c == '\'' || c == '"');
+                        segments.push(Segment::Key(key.to_string()));
+                    }
+                },
+                _ => i += 1,
+            }
+        }
+
+        Some(segments)
+    }
+
+    /// Evaluate a `@.a.b==literal` / `@.a.b!=literal` filter against one array element
+    fn filter_matches(item: &Value, expr: &str) -> bool {
+        let (op, op_index) = match (expr.find("=="), expr.find("!=")) {
+            (Some(eq), _) => ("==", eq),
+            (None, Some(ne)) => ("!=", ne),
+            (None, None) => return false,
+        };
+
+        let left = expr[..op_index].trim().strip_prefix('@').unwrap_or("").to_string();
+        let right = expr[op_index + op.len()..].trim();
+
+        let actual = Self::resolve_relative(item, &left);
+        let expected = Self::parse_literal(right);
+
+        match op {
+            "==" => actual.as_ref() == Some(&expected),
+            "!=" => actual.as_ref() != Some(&expected),
+            _ => false,
+        }
+    }
+
+    /// Resolve a `.a.b.c`-style path relative to `item` (the `@` in a filter expression)
+    fn resolve_relative(item: &Value, path: &str) -> Option<Value> {
+        let mut current = item;
+        for key in path.split('.').filter(|s| !s.is_empty()) {
+            current = current.get(key)?;
+        }
+        Some(current.clone())
+    }
+
+    fn parse_literal(raw: &str) -> Value {
+        let trimmed = raw.trim();
+        if let Some(inner) = trimmed.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+            return Value::String(inner.to_string());
+        }
+        if let Some(inner) = trimmed.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            return Value::String(inner.to_string());
+        }
+        if let Ok(n) = trimmed.parse::<i64>() {
+            return Value::Number(n.into());
+        }
+        if let Ok(f) = trimmed.parse::<f64>() {
+            if let Some(num) = serde_json::Number::from_f64(f) {
+                return Value::Number(num);
+            }
+        }
+        match trimmed {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            other => Value::String(other.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_simple_member_access() {
+        let root = json!({"a": {"b": "c"}});
+        assert_eq!(JsonPath::evaluate(&root, "$.a.b"), Some(json!("c")));
+    }
+
+    #[test]
+    fn test_array_index() {
+        let root = json!({"items": ["x", "y", "z"]});
+        assert_eq!(JsonPath::evaluate(&root, "$.items[1]"), Some(json!("y")));
+    }
+
+    #[test]
+    fn test_filter_then_member_access() {
+        let root = json!({
+            "pid": [
+                {"qualifier": {"classid": "other"}, "value": "ignored"},
+                {"qualifier": {"classid": "grid"}, "value": "grid.123.456"}
+            ]
+        });
+
+        let result = JsonPath::evaluate(&root, "$.pid[?(@.qualifier.classid=='grid')].value");
+        assert_eq!(result, Some(json!("grid.123.456")));
+    }
+
+    #[test]
+    fn test_missing_path_returns_none() {
+        let root = json!({"a": 1});
+        assert_eq!(JsonPath::evaluate(&root, "$.b.c"), None);
+    }
+
+    #[test]
+    fn test_wildcard_collects_array_elements() {
+        let root = json!({"items": [{"v": 1}, {"v": 2}]});
+        assert_eq!(JsonPath::evaluate(&root, "$.items[*].v"), Some(json!(1)));
+    }
+}
@@ -1,14 +1,48 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 
+pub mod column_encoding;
+pub use column_encoding::{Bitmap, ColumnEncoding, ColumnValues, EncodedColumn};
+
 pub mod efficient_compactor;
 pub use efficient_compactor::{EfficientCompactor, EfficientCompactedData};
 
+pub mod schema_validation;
+pub use schema_validation::{SchemaValidator, ValidationIssue, ValidationIssueKind};
+
+pub mod avro_codec;
+pub use avro_codec::AvroCompactedData;
+
+pub mod delta_compactor;
+pub use delta_compactor::{DeltaCompactedData, DeltaGroup, DeltaRecord};
+
+pub mod block_container;
+pub use block_container::{BlockCodec, BlockContainer, BlockContainerError};
+
+pub mod fuzzy_dedup;
+pub use fuzzy_dedup::{DedupCluster, FieldComparator, FieldMatchRule, FuzzyDedupConfig, FuzzyDeduplicator, ScoreAggregation};
+
+pub mod json_path;
+pub use json_path::JsonPath;
+
+pub mod validation;
+pub use validation::{FieldValidationRule, FieldValidator, MonotonicDirection, ValidationError, ValidationSpec};
+
 pub mod truly_efficient_compactor;
 pub use truly_efficient_compactor::{TrulyEfficientCompactor, CompactFormat};
 
+pub mod lzss;
+
+pub mod graph_compactor;
+pub use graph_compactor::{GraphCompactedData, GraphCompactionStats, GraphCompactor, GraphPathHop};
+
+pub mod arrow_export;
+
+pub mod search_index;
+pub use search_index::SearchIndex;
+
 pub mod compactor_comparison_test;
 
 /// Data compactor for atomic server data optimization
@@ -22,8 +56,34 @@ pub struct DataCompactor {
     property_mappings: HashMap<String, String>,
     /// Next available ID for mappings
     next_id: u32,
+    /// When `true`, `compact_value` skips `compress_number`'s rounding and
+    /// tags every substituted URL/string reference with a typed envelope
+    /// (`{"$u": id}` / `{"$s": id}`) instead of a bare number, so
+    /// `decompress_value` never has to guess whether a number is a genuine
+    /// value or a compressed reference. See `new_lossless`.
+    lossless: bool,
+}
+
+/// Byte-level entropy codec applied to the compacted JSON as a second,
+/// optional stage after categorical/structural compaction. The categorical
+/// pass shrinks the document's *shape*; this stage shrinks the resulting
+/// bytes, the same two-layer split `BlockContainer`'s `BlockCodec` uses for
+/// columnar blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Codec {
+    /// No byte-level codec; `CompactedData::encoded` is left unset and callers
+    /// keep using `data` directly, exactly as before this stage existed.
+    None,
+    Zstd { level: i32 },
+    Brotli { quality: u32 },
+    Deflate,
 }
 
+/// Distinct/count ratio below which `columnarize_grouped_subresources`
+/// stores a column as a dictionary (index array into a per-column value
+/// table) rather than inline
+const COLUMN_DICTIONARY_THRESHOLD: f64 = 0.5;
+
 /// Compacted data structure with lookup tables
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompactedData {
@@ -37,6 +97,13 @@ pub struct CompactedData {
     pub property_lookup: HashMap<String, String>,
     /// Compression statistics
     pub stats: CompressionStats,
+    /// Codec applied to `encoded`, `Codec::None` when this stage wasn't used
+    pub codec: Codec,
+    /// `data` re-serialized to JSON and run through `codec`, when `codec` is
+    /// not `Codec::None`. Callers after true on-disk savings should persist
+    /// this instead of `data`, and read it back with `decompress_encoded`
+    /// rather than `decompress`.
+    pub encoded: Option<Vec<u8>>,
 }
 
 /// Compression statistics
@@ -48,6 +115,9 @@ pub struct CompressionStats {
     pub urls_compressed: u32,
     pub strings_compressed: u32,
     pub properties_abbreviated: u32,
+    /// Size in bytes of `CompactedData::encoded`, after the byte-level codec
+    /// stage; `0` when no codec was applied.
+    pub post_codec_size: usize,
 }
 
 impl DataCompactor {
@@ -58,6 +128,20 @@ impl DataCompactor {
             string_mappings: HashMap::new(),
             property_mappings: Self::initialize_property_mappings(),
             next_id: 1,
+            lossless: false,
+        }
+    }
+
+    /// Create a compactor in lossless mode: numbers are never rounded and
+    /// substituted URL/string references are tagged (`{"$u": id}` / `{"$s": id}`)
+    /// rather than emitted as bare numbers, so `decompress` is guaranteed
+    /// to reproduce the original input exactly. Trades some of the
+    /// compression the default lossy mode gets from collapsing references
+    /// to bare numbers.
+    pub fn new_lossless() -> Self {
+        Self {
+            lossless: true,
+            ..Self::new()
         }
     }
 
@@ -164,6 +248,7 @@ impl DataCompactor {
             urls_compressed: 0,
             strings_compressed: 0,
             properties_abbreviated: 0,
+            post_codec_size: 0,
         };
 
         // Apply structural compaction first (before URLs are compressed)
@@ -172,6 +257,9 @@ impl DataCompactor {
         // Then process the data with compression
         let compacted_data = self.compact_value(structurally_compacted, &mut stats)?;
 
+        // Finally transpose each subresources_grouped bucket into columns
+        let compacted_data = self.columnarize_grouped_subresources(compacted_data);
+
         let compacted_json = serde_json::to_string(&compacted_data)?;
         stats.compacted_size = compacted_json.len();
         stats.compression_ratio =
@@ -183,9 +271,34 @@ impl DataCompactor {
             string_lookup: self.create_reverse_string_lookup(),
             property_lookup: self.property_mappings.clone(),
             stats,
+            codec: Codec::None,
+            encoded: None,
         })
     }
 
+    /// `compact_comprehensive_data`, then additionally run the compacted JSON
+    /// through `codec` and store the resulting binary blob in
+    /// `CompactedData::encoded` for true on-disk savings. `Codec::None`
+    /// behaves identically to `compact_comprehensive_data`.
+    pub fn compact_comprehensive_data_with_codec(
+        &mut self,
+        data: &Value,
+        codec: Codec,
+    ) -> Result<CompactedData> {
+        let mut compacted = self.compact_comprehensive_data(data)?;
+
+        if codec != Codec::None {
+            let compacted_json =
+                serde_json::to_vec(&compacted.data).context("Failed to serialize compacted data")?;
+            let encoded = encode_bytes(&compacted_json, codec)?;
+            compacted.stats.post_codec_size = encoded.len();
+            compacted.codec = codec;
+            compacted.encoded = Some(encoded);
+        }
+
+        Ok(compacted)
+    }
+
     /// Compact a JSON value recursively
     fn compact_value(&mut self, value: Value, stats: &mut CompressionStats) -> Result<Value> {
         match value {
@@ -219,13 +332,13 @@ impl DataCompactor {
                 // Check if it's a URL
                 if self.is_url(&s) {
                     if let Some(&id) = self.url_mappings.get(&s) {
-                        Ok(Value::Number(serde_json::Number::from(id)))
+                        Ok(Self::tag_reference("$u", id, self.lossless))
                     } else {
                         let id = self.next_id;
                         self.url_mappings.insert(s, id);
                         self.next_id += 1;
                         stats.urls_compressed += 1;
-                        Ok(Value::Number(serde_json::Number::from(id)))
+                        Ok(Self::tag_reference("$u", id, self.lossless))
                     }
                 } else {
                     // Apply categorical encoding for repeated strings
@@ -233,16 +346,20 @@ impl DataCompactor {
                 }
             }
             Value::Number(n) => {
-                // Apply numerical compression
+                // Apply numerical compression (lossless mode passes numbers through untouched)
                 self.compress_number(n)
             }
             Value::Bool(b) => {
-                // Convert booleans to compact form
-                Ok(Value::String(if b {
-                    "T".to_string()
+                if self.lossless {
+                    Ok(Value::Bool(b))
                 } else {
-                    "F".to_string()
-                }))
+                    // Convert booleans to compact form
+                    Ok(Value::String(if b {
+                        "T".to_string()
+                    } else {
+                        "F".to_string()
+                    }))
+                }
             }
             _ => Ok(value),
         }
@@ -253,18 +370,30 @@ impl DataCompactor {
         s.starts_with("http://") || s.starts_with("https://")
     }
 
+    /// In lossless mode, wrap a substituted id in a typed envelope
+    /// (`{"$u": id}` for URLs, `{"$s": id}` for string-table entries) so
+    /// `decompress_value` can tell a reference apart from a genuine numeric
+    /// value; in the default lossy mode, keep emitting a bare number.
+    fn tag_reference(tag: &str, id: u32, lossless: bool) -> Value {
+        if lossless {
+            serde_json::json!({ tag: id })
+        } else {
+            Value::Number(serde_json::Number::from(id))
+        }
+    }
+
     /// Compress repeated strings with categorical encoding
     fn compress_string(&mut self, s: String, stats: &mut CompressionStats) -> Result<Value> {
         // Only compress strings that appear multiple times or are very long
         if s.len() > 50 || self.string_mappings.contains_key(&s) {
             if let Some(&id) = self.string_mappings.get(&s) {
-                Ok(Value::Number(serde_json::Number::from(id)))
+                Ok(Self::tag_reference("$s", id, self.lossless))
             } else {
                 let id = self.next_id;
                 self.string_mappings.insert(s, id);
                 self.next_id += 1;
                 stats.strings_compressed += 1;
-                Ok(Value::Number(serde_json::Number::from(id)))
+                Ok(Self::tag_reference("$s", id, self.lossless))
             }
         } else {
             Ok(Value::String(s))
@@ -273,6 +402,10 @@ impl DataCompactor {
 
     /// Compress numerical values
     fn compress_number(&self, n: serde_json::Number) -> Result<Value> {
+        if self.lossless {
+            return Ok(Value::Number(n));
+        }
+
         if let Some(f) = n.as_f64() {
             // Round to reasonable precision
             let rounded = if f.abs() > 1000.0 {
@@ -335,6 +468,165 @@ impl DataCompactor {
         Ok(data)
     }
 
+    /// Transpose each `subresources_grouped` bucket into struct-of-arrays:
+    /// one column per property, replaced by a dictionary + index array when
+    /// the column's cardinality is low relative to its row count. Runs after
+    /// `compact_value` so columns hold already-compacted values (URL/string
+    /// ids, `T`/`F` booleans), the same layering `BlockContainer` uses to
+    /// stack a codec on top of categorical compaction.
+    fn columnarize_grouped_subresources(&self, mut data: Value) -> Value {
+        if let Value::Object(ref mut obj) = data {
+            if let Some(Value::Object(groups)) = obj.get("subresources_grouped").cloned() {
+                let columnar_groups: serde_json::Map<String, Value> = groups
+                    .into_iter()
+                    .map(|(type_name, resources)| {
+                        let columnar = match resources {
+                            Value::Array(rows) => Self::columnarize_group(&rows),
+                            other => other,
+                        };
+                        (type_name, columnar)
+                    })
+                    .collect();
+                obj.insert("subresources_grouped".to_string(), Value::Object(columnar_groups));
+            }
+        }
+
+        data
+    }
+
+    /// Transpose one homogeneous group's array-of-objects into
+    /// `{__columnar__, count, keys, manifest, columns}`. `keys` fixes the
+    /// original column order so `decolumnarize_group` can rebuild each row;
+    /// a row missing a key is stored as `null` in that column, so a field
+    /// whose value is `null` comes back absent. `manifest` records, per
+    /// column, whether it was dictionary-encoded.
+    fn columnarize_group(rows: &[Value]) -> Value {
+        let count = rows.len();
+
+        // Column order is the union of keys across every row, in first-seen order
+        let mut keys: Vec<String> = Vec::new();
+        for row in rows {
+            if let Some(obj) = row.as_object() {
+                for key in obj.keys() {
+                    if !keys.contains(key) {
+                        keys.push(key.clone());
+                    }
+                }
+            }
+        }
+
+        let mut manifest = serde_json::Map::new();
+        let mut columns = serde_json::Map::new();
+
+        for key in &keys {
+            let column: Vec<Value> = rows
+                .iter()
+                .map(|row| {
+                    row.as_object()
+                        .and_then(|obj| obj.get(key))
+                        .cloned()
+                        .unwrap_or(Value::Null)
+                })
+                .collect();
+
+            let mut distinct: Vec<Value> = Vec::new();
+            for value in &column {
+                if !distinct.contains(value) {
+                    distinct.push(value.clone());
+                }
+            }
+
+            let use_dictionary =
+                count > 0 && (distinct.len() as f64 / count as f64) < COLUMN_DICTIONARY_THRESHOLD;
+            manifest.insert(key.clone(), Value::Bool(use_dictionary));
+
+            if use_dictionary {
+                let indices: Vec<Value> = column
+                    .iter()
+                    .map(|value| {
+                        let index = distinct.iter().position(|d| d == value).unwrap_or(0);
+                        Value::Number(serde_json::Number::from(index as u64))
+                    })
+                    .collect();
+                columns.insert(
+                    key.clone(),
+                    serde_json::json!({ "dict": distinct, "indices": indices }),
+                );
+            } else {
+                columns.insert(key.clone(), serde_json::json!({ "values": column }));
+            }
+        }
+
+        serde_json::json!({
+            "__columnar__": true,
+            "count": count,
+            "keys": keys,
+            "manifest": Value::Object(manifest),
+            "columns": Value::Object(columns),
+        })
+    }
+
+    /// Invert `columnarize_grouped_subresources`
+    fn decolumnarize_grouped_subresources(&self, mut data: Value) -> Value {
+        if let Value::Object(ref mut obj) = data {
+            if let Some(Value::Object(groups)) = obj.get("subresources_grouped").cloned() {
+                let restored_groups: serde_json::Map<String, Value> = groups
+                    .into_iter()
+                    .map(|(type_name, group)| {
+                        let restored = Self::decolumnarize_group(&group).unwrap_or(group);
+                        (type_name, restored)
+                    })
+                    .collect();
+                obj.insert("subresources_grouped".to_string(), Value::Object(restored_groups));
+            }
+        }
+
+        data
+    }
+
+    /// Invert `columnarize_group`, rebuilding the original array-of-objects
+    /// order exactly from `keys` plus each column's dictionary/index pair or
+    /// inline values. Returns `None` (leaving the value untouched) if
+    /// `columnar` isn't a well-formed columnar group.
+    fn decolumnarize_group(columnar: &Value) -> Option<Value> {
+        let obj = columnar.as_object()?;
+        if obj.get("__columnar__").and_then(Value::as_bool) != Some(true) {
+            return None;
+        }
+
+        let count = obj.get("count")?.as_u64()? as usize;
+        let keys = obj.get("keys")?.as_array()?;
+        let columns = obj.get("columns")?.as_object()?;
+
+        let mut rows: Vec<serde_json::Map<String, Value>> = vec![serde_json::Map::new(); count];
+
+        for key_value in keys {
+            let key = key_value.as_str()?;
+            let column = columns.get(key)?.as_object()?;
+
+            let values: Vec<Value> = if let Some(dict) = column.get("dict").and_then(|v| v.as_array()) {
+                let indices = column.get("indices")?.as_array()?;
+                indices
+                    .iter()
+                    .map(|index| {
+                        let index = index.as_u64().unwrap_or(0) as usize;
+                        dict.get(index).cloned().unwrap_or(Value::Null)
+                    })
+                    .collect()
+            } else {
+                column.get("values")?.as_array()?.clone()
+            };
+
+            for (row, value) in rows.iter_mut().zip(values) {
+                if !value.is_null() {
+                    row.insert(key.to_string(), value);
+                }
+            }
+        }
+
+        Some(Value::Array(rows.into_iter().map(Value::Object).collect()))
+    }
+
     /// Create reverse lookup for URLs
     fn create_reverse_url_lookup(&self) -> HashMap<u32, String> {
         self.url_mappings
@@ -353,11 +645,49 @@ impl DataCompactor {
 
     /// Decompress data back to original form (for debugging)
     pub fn decompress(&self, compacted: &CompactedData) -> Result<Value> {
-        self.decompress_value(
-            &compacted.data,
-            &compacted.url_lookup,
-            &compacted.string_lookup,
-        )
+        let data = self.decolumnarize_grouped_subresources(compacted.data.clone());
+        self.decompress_value(&data, &compacted.url_lookup, &compacted.string_lookup)
+    }
+
+    /// `decompress`, but for a `CompactedData` produced by
+    /// `compact_comprehensive_data_with_codec`: inverts `codec` over
+    /// `encoded` to recover the compacted JSON before running the usual
+    /// URL/string/property decompression. Falls back to `decompress` when
+    /// `encoded` is unset.
+    pub fn decompress_encoded(&self, compacted: &CompactedData) -> Result<Value> {
+        let Some(encoded) = compacted.encoded.as_ref() else {
+            return self.decompress(compacted);
+        };
+
+        let compacted_json = decode_bytes(encoded, compacted.codec)?;
+        let data: Value =
+            serde_json::from_slice(&compacted_json).context("Failed to parse decoded compacted data")?;
+        let data = self.decolumnarize_grouped_subresources(data);
+
+        self.decompress_value(&data, &compacted.url_lookup, &compacted.string_lookup)
+    }
+
+    /// Compact `data` in lossless mode, decompress the result, and fail if
+    /// it doesn't reproduce `data` exactly. Intended as a test invariant for
+    /// callers that need a guaranteed-reversible compaction. Note that
+    /// `apply_structural_compaction`'s `subresources` -> `subresources_grouped`
+    /// rename is itself a one-way transform (`decompress` leaves the
+    /// `subresources_grouped` key in place) independent of `lossless`, so this is only a true round trip for
+    /// documents without a top-level `subresources` array.
+    pub fn verify_roundtrip(data: &Value) -> Result<()> {
+        let mut compactor = Self::new_lossless();
+        let compacted = compactor.compact_comprehensive_data(data)?;
+        let decompressed = compactor.decompress(&compacted)?;
+
+        if &decompressed != data {
+            bail!(
+                "Lossless round-trip mismatch: expected {}, got {}",
+                data,
+                decompressed
+            );
+        }
+
+        Ok(())
     }
 
     /// Recursively decompress a value
@@ -369,6 +699,21 @@ impl DataCompactor {
     ) -> Result<Value> {
         match value {
             Value::Object(obj) => {
+                // Typed envelope from lossless mode (`{"$u": id}` / `{"$s": id}`):
+                // unambiguous reference, not a nested object to recurse into
+                if obj.len() == 1 {
+                    if let Some(id) = obj.get("$u").and_then(Value::as_u64) {
+                        if let Some(url) = url_lookup.get(&(id as u32)) {
+                            return Ok(Value::String(url.clone()));
+                        }
+                    }
+                    if let Some(id) = obj.get("$s").and_then(Value::as_u64) {
+                        if let Some(s) = string_lookup.get(&(id as u32)) {
+                            return Ok(Value::String(s.clone()));
+                        }
+                    }
+                }
+
                 let mut decompressed_obj = serde_json::Map::new();
 
                 for (key, val) in obj {
@@ -433,6 +778,77 @@ impl Default for DataCompactor {
     }
 }
 
+/// Default Brotli window size (lg base 2), matching the crate's own default
+const BROTLI_LG_WINDOW_SIZE: u32 = 22;
+const BROTLI_BUFFER_SIZE: usize = 4096;
+
+fn encode_bytes(bytes: &[u8], codec: Codec) -> Result<Vec<u8>> {
+    match codec {
+        Codec::None => Ok(bytes.to_vec()),
+        Codec::Zstd { level } => {
+            zstd::bulk::compress(bytes, level).context("Failed to zstd-compress codec payload")
+        }
+        Codec::Brotli { quality } => {
+            use std::io::Write;
+
+            let mut out = Vec::new();
+            {
+                let mut writer = brotli::CompressorWriter::new(
+                    &mut out,
+                    BROTLI_BUFFER_SIZE,
+                    quality,
+                    BROTLI_LG_WINDOW_SIZE,
+                );
+                writer
+                    .write_all(bytes)
+                    .context("Failed to brotli-compress codec payload")?;
+            }
+            Ok(out)
+        }
+        Codec::Deflate => {
+            use flate2::write::DeflateEncoder;
+            use flate2::Compression;
+            use std::io::Write;
+
+            let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+            encoder
+                .write_all(bytes)
+                .context("Failed to deflate codec payload")?;
+            encoder.finish().context("Failed to finalize deflate stream")
+        }
+    }
+}
+
+fn decode_bytes(bytes: &[u8], codec: Codec) -> Result<Vec<u8>> {
+    match codec {
+        Codec::None => Ok(bytes.to_vec()),
+        Codec::Zstd { .. } => {
+            zstd::stream::decode_all(bytes).context("Failed to zstd-decompress codec payload")
+        }
+        Codec::Brotli { .. } => {
+            use std::io::Read;
+
+            let mut decoder = brotli::Decompressor::new(bytes, BROTLI_BUFFER_SIZE);
+            let mut out = Vec::new();
+            decoder
+                .read_to_end(&mut out)
+                .context("Failed to brotli-decompress codec payload")?;
+            Ok(out)
+        }
+        Codec::Deflate => {
+            use flate2::read::DeflateDecoder;
+            use std::io::Read;
+
+            let mut decoder = DeflateDecoder::new(bytes);
+            let mut out = Vec::new();
+            decoder
+                .read_to_end(&mut out)
+                .context("Failed to inflate codec payload")?;
+            Ok(out)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -477,4 +893,143 @@ mod tests {
             assert_eq!(obj.get("verified"), Some(&Value::String("F".to_string())));
         }
     }
+
+    #[test]
+    fn test_codec_none_leaves_encoded_unset() {
+        let mut compactor = DataCompactor::new();
+        let data = json!({ "active": true });
+
+        let compacted = compactor
+            .compact_comprehensive_data_with_codec(&data, Codec::None)
+            .unwrap();
+
+        assert_eq!(compacted.codec, Codec::None);
+        assert!(compacted.encoded.is_none());
+        assert_eq!(compacted.stats.post_codec_size, 0);
+    }
+
+    #[test]
+    fn test_codec_zstd_roundtrips_through_decompress_encoded() {
+        let mut compactor = DataCompactor::new();
+        let data = json!({
+            "id": "https://common.terraphim.io/01k2cxga1cqmqsgvqk0enxq8a5",
+            "active": true
+        });
+
+        let compacted = compactor
+            .compact_comprehensive_data_with_codec(&data, Codec::Zstd { level: 3 })
+            .unwrap();
+
+        assert!(compacted.encoded.is_some());
+        assert!(compacted.stats.post_codec_size > 0);
+
+        let decompressed = compactor.decompress_encoded(&compacted).unwrap();
+        assert_eq!(decompressed, data);
+    }
+
+    #[test]
+    fn test_codec_deflate_roundtrips_through_decompress_encoded() {
+        let mut compactor = DataCompactor::new();
+        let data = json!({ "verified": false });
+
+        let compacted = compactor
+            .compact_comprehensive_data_with_codec(&data, Codec::Deflate)
+            .unwrap();
+
+        let decompressed = compactor.decompress_encoded(&compacted).unwrap();
+        assert_eq!(decompressed, data);
+    }
+
+    #[test]
+    fn test_grouped_subresources_columnarize_low_cardinality_field() {
+        let mut compactor = DataCompactor::new();
+        // "active" has 2 distinct values over 5 rows (ratio 0.4 < 0.5): dictionary-encoded.
+        // "id" is all-distinct (ratio 1.0): stored inline.
+        let data = json!({
+            "subresources": [
+                {"resource_type": "https://example.com/class/widget-step", "active": true, "id": "a"},
+                {"resource_type": "https://example.com/class/widget-step", "active": true, "id": "b"},
+                {"resource_type": "https://example.com/class/widget-step", "active": true, "id": "c"},
+                {"resource_type": "https://example.com/class/widget-step", "active": false, "id": "d"},
+                {"resource_type": "https://example.com/class/widget-step", "active": true, "id": "e"}
+            ]
+        });
+
+        let compacted = compactor.compact_comprehensive_data(&data).unwrap();
+        let group = &compacted.data["subresources_grouped"]["widget"];
+        assert_eq!(group["__columnar__"], true);
+        assert_eq!(group["count"], 5);
+        assert_eq!(group["manifest"]["active"], true);
+        assert_eq!(group["manifest"]["id"], false);
+
+        let decompressed = compactor.decompress(&compacted).unwrap();
+        let rows = decompressed["subresources_grouped"]["widget"].as_array().unwrap();
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[0]["active"], true);
+        assert_eq!(rows[3]["active"], false);
+        assert_eq!(rows[4]["id"], "e");
+    }
+
+    #[test]
+    fn test_grouped_subresources_roundtrips_through_codec() {
+        let mut compactor = DataCompactor::new();
+        let data = json!({
+            "subresources": [
+                {"resource_type": "https://example.com/class/widget-step", "active": true, "id": "a"},
+                {"resource_type": "https://example.com/class/widget-step", "active": false, "id": "b"}
+            ]
+        });
+
+        let compacted = compactor
+            .compact_comprehensive_data_with_codec(&data, Codec::Zstd { level: 3 })
+            .unwrap();
+        let decompressed = compactor.decompress_encoded(&compacted).unwrap();
+
+        let rows = decompressed["subresources_grouped"]["widget"].as_array().unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["active"], true);
+        assert_eq!(rows[1]["active"], false);
+    }
+
+    #[test]
+    fn test_lossless_mode_preserves_exact_floats_and_bools() {
+        let mut compactor = DataCompactor::new_lossless();
+        let data = json!({ "score": 3.14159, "active": true });
+
+        let compacted = compactor.compact_comprehensive_data(&data).unwrap();
+        assert_eq!(compacted.data["score"], 3.14159);
+        assert_eq!(compacted.data["active"], true);
+
+        let decompressed = compactor.decompress(&compacted).unwrap();
+        assert_eq!(decompressed, data);
+    }
+
+    #[test]
+    fn test_lossless_mode_tags_references_instead_of_bare_numbers() {
+        let mut compactor = DataCompactor::new_lossless();
+        let data = json!({
+            "id": "https://common.terraphim.io/01k2cxga1cqmqsgvqk0enxq8a5",
+            "literal_number": 1
+        });
+
+        let compacted = compactor.compact_comprehensive_data(&data).unwrap();
+        assert!(compacted.data["id"].is_object());
+        assert!(compacted.data["id"]["$u"].is_number());
+        // A genuine number is never mistaken for a reference envelope
+        assert_eq!(compacted.data["literal_number"], 1);
+    }
+
+    #[test]
+    fn test_verify_roundtrip_passes_for_lossless_data() {
+        let data = json!({
+            "id": "https://common.terraphim.io/01k2cxga1cqmqsgvqk0enxq8a5",
+            "parent": "https://common.terraphim.io/01k2cxga1cqmqsgvqk0enxq8a5",
+            "literal_number": 1,
+            "price": 1234.5678,
+            "active": true,
+            "note": "a repeated note that appears twice: a repeated note that appears twice"
+        });
+
+        DataCompactor::verify_roundtrip(&data).unwrap();
+    }
 }
\ No newline at end of file
@@ -0,0 +1,233 @@
+//! Self-contained LZSS sliding-window codec (Yaz0-style), offered as a
+//! lightweight alternative to zstd/CBOR for callers that can't pull in a
+//! heavy codec dependency.
+//!
+//! Format: a 4-byte magic tag, then the 4-byte big-endian decompressed
+//! length, then a sequence of groups. Each group starts with one "code"
+//! byte whose 8 bits (MSB first) flag the next 8 tokens: a set bit means
+//! "copy one literal byte verbatim," a clear bit means "back-reference."
+//! A back-reference is 2 bytes: the high nibble of the first byte is
+//! `length - 2` and the remaining 12 bits form `distance - 1` into the
+//! already-output window; if that high nibble is zero, a third byte gives
+//! `length - 0x12`, extending matches up to `0xFF + 0x12` bytes. The final
+//! group of a stream may flag fewer than 8 tokens.
+
+use anyhow::{bail, Result};
+
+const MAGIC: &[u8; 4] = b"LZS0";
+const HEADER_LEN: usize = MAGIC.len() + 4;
+const WINDOW_SIZE: usize = 4096;
+const MIN_MATCH_LEN: usize = 3;
+const MAX_SHORT_MATCH_LEN: usize = 17; // nibble 1..=15 -> length 3..=17
+const MAX_LONG_MATCH_LEN: usize = 0xFF + 0x12; // nibble 0, third byte 0..=255 -> length 18..=273
+
+/// Compress `input` into the LZS0 format described in the module docs
+pub fn compress(input: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(input.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&(input.len() as u32).to_be_bytes());
+
+    let mut pos = 0;
+    while pos < input.len() {
+        let mut code_byte = 0u8;
+        let mut tokens = Vec::with_capacity(16);
+
+        for bit in 0..8 {
+            if pos >= input.len() {
+                break;
+            }
+
+            match find_longest_match(input, pos) {
+                Some((distance, length)) => {
+                    let distance_minus_1 = (distance - 1) as u16;
+                    if length <= MAX_SHORT_MATCH_LEN {
+                        let nibble = (length - 2) as u8;
+                        tokens.push((nibble << 4) | ((distance_minus_1 >> 8) as u8));
+                        tokens.push((distance_minus_1 & 0xFF) as u8);
+                    } else {
+                        tokens.push((distance_minus_1 >> 8) as u8);
+                        tokens.push((distance_minus_1 & 0xFF) as u8);
+                        tokens.push((length - 0x12) as u8);
+                    }
+                    pos += length;
+                    // bit stays clear: back-reference
+                }
+                None => {
+                    code_byte |= 1 << (7 - bit);
+                    tokens.push(input[pos]);
+                    pos += 1;
+                }
+            }
+        }
+
+        out.push(code_byte);
+        out.extend_from_slice(&tokens);
+    }
+
+    out
+}
+
+/// Decompress an LZS0 stream produced by `compress`
+pub fn decompress(input: &[u8]) -> Result<Vec<u8>> {
+    if input.len() < HEADER_LEN || &input[0..4] != MAGIC {
+        bail!("invalid LZS0 stream: missing or corrupt magic header");
+    }
+
+    let decompressed_len = u32::from_be_bytes([input[4], input[5], input[6], input[7]]) as usize;
+    let mut out = Vec::with_capacity(decompressed_len);
+    let mut pos = HEADER_LEN;
+
+    while out.len() < decompressed_len {
+        let code_byte = *input.get(pos).ok_or_else(|| {
+            anyhow::anyhow!("truncated LZS0 stream: missing code byte")
+        })?;
+        pos += 1;
+
+        for bit in 0..8 {
+            if out.len() >= decompressed_len {
+                break;
+            }
+
+            let is_literal = code_byte & (1 << (7 - bit)) != 0;
+            if is_literal {
+                let byte = *input.get(pos).ok_or_else(|| {
+                    anyhow::anyhow!("truncated LZS0 stream: missing literal byte")
+                })?;
+                pos += 1;
+                out.push(byte);
+            } else {
+                if pos + 1 >= input.len() {
+                    bail!("truncated LZS0 stream: missing back-reference bytes");
+                }
+                let (b0, b1) = (input[pos], input[pos + 1]);
+                pos += 2;
+
+                let nibble = b0 >> 4;
+                let distance = (((b0 & 0x0F) as usize) << 8 | b1 as usize) + 1;
+                let length = if nibble == 0 {
+                    let b2 = *input.get(pos).ok_or_else(|| {
+                        anyhow::anyhow!("truncated LZS0 stream: missing extended-length byte")
+                    })?;
+                    pos += 1;
+                    b2 as usize + 0x12
+                } else {
+                    nibble as usize + 2
+                };
+
+                if distance > out.len() {
+                    bail!(
+                        "corrupt LZS0 stream: back-reference distance {} exceeds {} bytes already emitted",
+                        distance,
+                        out.len()
+                    );
+                }
+
+                let start = out.len() - distance;
+                for i in 0..length {
+                    out.push(out[start + i]);
+                }
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+/// Find the longest run of `>= MIN_MATCH_LEN` bytes starting at `pos` that
+/// also appears within `WINDOW_SIZE` bytes behind it. Searches the
+/// original input rather than decoded output, so a match whose length
+/// exceeds its distance (an overlapping run) is still found correctly —
+/// the decoder reproduces it since it copies byte-by-byte from output it
+/// has already written earlier in the same back-reference.
+fn find_longest_match(input: &[u8], pos: usize) -> Option<(usize, usize)> {
+    let window_start = pos.saturating_sub(WINDOW_SIZE);
+    let max_len = (input.len() - pos).min(MAX_LONG_MATCH_LEN);
+    if max_len < MIN_MATCH_LEN {
+        return None;
+    }
+
+    let mut best_len = 0;
+    let mut best_distance = 0;
+
+    for start in window_start..pos {
+        let mut len = 0;
+        while len < max_len && input[start + len] == input[pos + len] {
+            len += 1;
+        }
+        if len > best_len {
+            best_len = len;
+            best_distance = pos - start;
+        }
+    }
+
+    if best_len >= MIN_MATCH_LEN {
+        Some((best_distance, best_len))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrips_empty_input() {
+        let compressed = compress(&[]);
+        assert_eq!(decompress(&compressed).unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_roundtrips_short_input_with_no_matches() {
+        let input = b"abc".to_vec();
+        let compressed = compress(&input);
+        assert_eq!(decompress(&compressed).unwrap(), input);
+    }
+
+    #[test]
+    fn test_roundtrips_highly_repetitive_input() {
+        let input = b"abcabcabcabcabcabcabcabcabcabcabcabcabc".to_vec();
+        let compressed = compress(&input);
+        assert!(compressed.len() < input.len());
+        assert_eq!(decompress(&compressed).unwrap(), input);
+    }
+
+    #[test]
+    fn test_roundtrips_overlapping_run_length_style_match() {
+        // "aaaa..." forces distance (1) to be shorter than several match lengths
+        let input = vec![b'a'; 50];
+        let compressed = compress(&input);
+        assert_eq!(decompress(&compressed).unwrap(), input);
+    }
+
+    #[test]
+    fn test_roundtrips_extended_length_back_reference() {
+        // A run long enough to require the 3-byte extended-length encoding (length > 17)
+        let mut input = b"0123456789abcdef".repeat(1);
+        input.extend(input.clone());
+        input.extend(input.clone());
+        input.extend(input.clone());
+        let compressed = compress(&input);
+        assert_eq!(decompress(&compressed).unwrap(), input);
+    }
+
+    #[test]
+    fn test_roundtrips_input_spanning_multiple_groups() {
+        let input: Vec<u8> = (0..500u32).map(|i| (i % 251) as u8).collect();
+        let compressed = compress(&input);
+        assert_eq!(decompress(&compressed).unwrap(), input);
+    }
+
+    #[test]
+    fn test_decompress_rejects_bad_magic() {
+        let err = decompress(b"NOPE0000").unwrap_err();
+        assert!(err.to_string().contains("magic"));
+    }
+
+    #[test]
+    fn test_decompress_rejects_truncated_stream() {
+        let compressed = compress(b"hello world hello world hello world");
+        let truncated = &compressed[..compressed.len() - 2];
+        assert!(decompress(truncated).is_err());
+    }
+}
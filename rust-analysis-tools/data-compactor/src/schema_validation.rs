@@ -0,0 +1,223 @@
+//! Schema-conformance checks for compaction input.
+//!
+//! `EfficientCompactor::infer_schema` determines which fields are required
+//! vs optional and what `FieldType` each field should hold, but
+//! `compact_comprehensive_data` itself tolerates resources that violate
+//! that schema: a missing required field just leaves the column absent,
+//! and a value that disagrees with the inferred type is coerced or dropped
+//! silently. `SchemaValidator::validate` re-checks raw resources against an
+//! already-inferred `CompactionSchema` and reports exactly which required
+//! fields are missing and which fields carry a mismatched type, per
+//! resource type and row, so callers can fix extraction upstream instead of
+//! discovering malformed rows only after reconstruction.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::efficient_compactor::{extract_type_name, infer_field_type, CompactionSchema, FieldType};
+
+/// One schema violation found in a raw (pre-compaction) resource
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ValidationIssue {
+    pub resource_type: String,
+    pub row_index: usize,
+    pub kind: ValidationIssueKind,
+}
+
+/// The specific way a resource disagreed with its `ResourceSchema`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ValidationIssueKind {
+    /// A field `ResourceSchema::required` lists was absent from this row
+    MissingRequiredField { field: String },
+    /// A field was present but its inferred type didn't match the schema
+    TypeMismatch {
+        field: String,
+        expected: FieldType,
+        found: FieldType,
+    },
+}
+
+/// Checks raw (pre-compaction) resources against an already-inferred `CompactionSchema`
+pub struct SchemaValidator;
+
+impl SchemaValidator {
+    /// Walk every subresource in `data`, enumerate each missing required
+    /// field and each type mismatch against `schema`'s `ResourceSchema` for
+    /// that resource's type, and return every issue found. A resource whose
+    /// `resource_type` has no entry in `schema` is skipped: there's nothing
+    /// to check it against.
+    pub fn validate(data: &Value, schema: &CompactionSchema) -> Vec<ValidationIssue> {
+        let empty_vec = vec![];
+        let subresources = data
+            .get("subresources")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+
+        let mut issues = Vec::new();
+
+        for (row_index, resource) in subresources.iter().enumerate() {
+            let Some(raw_type) = resource.get("resource_type").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let type_name = extract_type_name(raw_type);
+            let Some(resource_schema) = schema.resource_types.get(&type_name) else {
+                continue;
+            };
+
+            for field in &resource_schema.required {
+                if resource.get(field).is_none() {
+                    issues.push(ValidationIssue {
+                        resource_type: type_name.clone(),
+                        row_index,
+                        kind: ValidationIssueKind::MissingRequiredField {
+                            field: field.clone(),
+                        },
+                    });
+                }
+            }
+
+            for (field, expected) in &resource_schema.types {
+                let Some(value) = resource.get(field) else {
+                    continue;
+                };
+                let found = infer_field_type(value);
+                if !Self::types_compatible(expected, &found) {
+                    issues.push(ValidationIssue {
+                        resource_type: type_name.clone(),
+                        row_index,
+                        kind: ValidationIssueKind::TypeMismatch {
+                            field: field.clone(),
+                            expected: expected.clone(),
+                            found,
+                        },
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// `FieldType::Array` is never produced by `infer_field_type` (a JSON
+    /// array infers as `Json`), so an expected `Array` is satisfied by a
+    /// found `Json` value rather than requiring an exact match.
+    fn types_compatible(expected: &FieldType, found: &FieldType) -> bool {
+        match expected {
+            FieldType::Array(_) => matches!(found, FieldType::Json),
+            _ => expected == found,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::efficient_compactor::EfficientCompactor;
+    use serde_json::json;
+
+    fn schema_for(data: &Value) -> CompactionSchema {
+        let mut compactor = EfficientCompactor::new();
+        compactor.compact_comprehensive_data(data).unwrap().schema
+    }
+
+    #[test]
+    fn test_missing_required_field_is_reported_with_row_index() {
+        let training = json!({
+            "subresources": [
+                {
+                    "url": "https://example.com/1",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "Acme"
+                }
+            ]
+        });
+        let schema = schema_for(&training);
+
+        let candidate = json!({
+            "subresources": [
+                {
+                    "url": "https://example.com/2",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step"
+                }
+            ]
+        });
+
+        let issues = SchemaValidator::validate(&candidate, &schema);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].row_index, 0);
+        assert_eq!(issues[0].resource_type, "company_information_and_history");
+        assert_eq!(
+            issues[0].kind,
+            ValidationIssueKind::MissingRequiredField {
+                field: "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_type_mismatch_is_reported() {
+        let training = json!({
+            "subresources": [
+                {
+                    "url": "https://example.com/1",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation": 2020
+                }
+            ]
+        });
+        let schema = schema_for(&training);
+
+        let candidate = json!({
+            "subresources": [
+                {
+                    "url": "https://example.com/2",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation": "not a number"
+                }
+            ]
+        });
+
+        let issues = SchemaValidator::validate(&candidate, &schema);
+        assert_eq!(
+            issues
+                .iter()
+                .filter(|issue| matches!(issue.kind, ValidationIssueKind::TypeMismatch { .. }))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_conforming_resource_has_no_issues() {
+        let data = json!({
+            "subresources": [
+                {
+                    "url": "https://example.com/1",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "Acme"
+                }
+            ]
+        });
+        let schema = schema_for(&data);
+
+        assert!(SchemaValidator::validate(&data, &schema).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_resource_type_is_skipped() {
+        let schema = CompactionSchema {
+            resource_types: std::collections::HashMap::new(),
+            field_types: std::collections::HashMap::new(),
+        };
+        let data = json!({
+            "subresources": [
+                {
+                    "url": "https://example.com/1",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/unknown-step"
+                }
+            ]
+        });
+
+        assert!(SchemaValidator::validate(&data, &schema).is_empty());
+    }
+}
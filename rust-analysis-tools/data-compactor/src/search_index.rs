@@ -0,0 +1,234 @@
+//! Inverted-index keyword search over `EfficientCompactedData`.
+//!
+//! Every string/URL cell is already interned to a `u16` dictionary id, so
+//! the index tokenizes each distinct dictionary entry exactly once and
+//! walks each `TypedResourceGroup`'s columns to build postings, rather than
+//! re-scanning text per row.
+
+use std::collections::HashMap;
+
+use crate::efficient_compactor::{EfficientCompactedData, EfficientCompactor};
+use serde_json::Value;
+
+/// One postings entry: a resource of `resource_type`, at row `row_index`
+/// within that type's `TypedResourceGroup`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Posting {
+    resource_type: String,
+    row_index: usize,
+}
+
+/// Inverted index over a compacted dataset's interned strings and URLs,
+/// keyed by lowercase alphanumeric token
+#[derive(Debug, Clone)]
+pub struct SearchIndex {
+    data: EfficientCompactedData,
+    postings: HashMap<String, Vec<Posting>>,
+}
+
+impl SearchIndex {
+    /// Tokenize every distinct entry in `dictionaries.strings`/`.urls` once,
+    /// then walk each `TypedResourceGroup`'s `str_cols`/`url_cols`/`json_cols`
+    /// to record which (resource_type, row) each token appears in.
+    pub fn build(data: &EfficientCompactedData) -> Self {
+        let string_tokens: HashMap<u16, Vec<String>> = data
+            .dictionaries
+            .strings
+            .iter()
+            .map(|(&id, value)| (id, Self::tokenize(value)))
+            .collect();
+        let url_tokens: HashMap<u16, Vec<String>> = data
+            .dictionaries
+            .urls
+            .iter()
+            .map(|(&id, value)| (id, Self::tokenize(value)))
+            .collect();
+
+        let mut postings: HashMap<String, Vec<Posting>> = HashMap::new();
+
+        for (resource_type, group) in &data.data.resources {
+            let decoded = group.decode();
+            for values in decoded.str_cols.values().chain(decoded.json_cols.values()) {
+                Self::index_column(values, &string_tokens, resource_type, &mut postings);
+            }
+            for values in decoded.url_cols.values() {
+                Self::index_column(values, &url_tokens, resource_type, &mut postings);
+            }
+        }
+
+        Self {
+            data: data.clone(),
+            postings,
+        }
+    }
+
+    /// Tokenize `query`, intersect postings for AND semantics (a matching
+    /// resource must contain every token), and reconstruct only those rows.
+    pub fn query(&self, query: &str) -> Vec<Value> {
+        let tokens = Self::tokenize(query);
+        if tokens.is_empty() {
+            return Vec::new();
+        }
+
+        let mut matches: Option<Vec<&Posting>> = None;
+        for token in &tokens {
+            let hits: Vec<&Posting> = self.postings.get(token).map(|v| v.iter().collect()).unwrap_or_default();
+            matches = Some(match matches {
+                None => hits,
+                Some(previous) => previous
+                    .into_iter()
+                    .filter(|p| hits.contains(p))
+                    .collect(),
+            });
+        }
+
+        matches
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|posting| {
+                let group = self.data.data.resources.get(&posting.resource_type)?;
+                let decoded = group.decode();
+                Some(EfficientCompactor::reconstruct_resource(
+                    &self.data,
+                    &posting.resource_type,
+                    &decoded,
+                    posting.row_index,
+                ))
+            })
+            .collect()
+    }
+
+    fn index_column(
+        values: &[Option<u16>],
+        token_cache: &HashMap<u16, Vec<String>>,
+        resource_type: &str,
+        postings: &mut HashMap<String, Vec<Posting>>,
+    ) {
+        for (row_index, value) in values.iter().enumerate() {
+            let Some(id) = value else { continue };
+            let Some(tokens) = token_cache.get(id) else { continue };
+            for token in tokens {
+                let entry = postings.entry(token.clone()).or_default();
+                let posting = Posting {
+                    resource_type: resource_type.to_string(),
+                    row_index,
+                };
+                // The same token can come from more than one column (e.g. a
+                // str_col and a json_col) for the same row; only record the
+                // posting once so a single-token query doesn't return that
+                // resource twice.
+                if !entry.contains(&posting) {
+                    entry.push(posting);
+                }
+            }
+        }
+    }
+
+    /// Lowercase and split on runs of non-alphanumeric characters
+    fn tokenize(text: &str) -> Vec<String> {
+        text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .map(|token| token.to_string())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::efficient_compactor::EfficientCompactor;
+    use serde_json::json;
+
+    fn sample_data() -> EfficientCompactedData {
+        let mut compactor = EfficientCompactor::new();
+        let data = json!({
+            "subresources": [
+                {
+                    "url": "https://example.com/1",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "EcoBright Solutions"
+                },
+                {
+                    "url": "https://example.com/2",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "EcoBright Solutions"
+                },
+                {
+                    "url": "https://example.com/3",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "Totally Different Co"
+                }
+            ]
+        });
+        compactor.compact_comprehensive_data(&data).unwrap()
+    }
+
+    #[test]
+    fn test_query_finds_every_row_with_a_duplicated_value() {
+        let compacted = sample_data();
+        let index = SearchIndex::build(&compacted);
+
+        let results = index.query("ecobright");
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_query_is_case_insensitive_and_tokenizes_on_punctuation() {
+        let compacted = sample_data();
+        let index = SearchIndex::build(&compacted);
+
+        assert_eq!(index.query("ECOBRIGHT").len(), 2);
+        assert_eq!(index.query("solutions").len(), 2);
+    }
+
+    #[test]
+    fn test_query_with_multiple_tokens_uses_and_semantics() {
+        let compacted = sample_data();
+        let index = SearchIndex::build(&compacted);
+
+        assert_eq!(index.query("ecobright solutions").len(), 2);
+        assert_eq!(index.query("ecobright different").len(), 0);
+    }
+
+    #[test]
+    fn test_query_with_no_matches_returns_empty() {
+        let compacted = sample_data();
+        let index = SearchIndex::build(&compacted);
+
+        assert!(index.query("nonexistent").is_empty());
+    }
+
+    #[test]
+    fn test_query_does_not_duplicate_a_token_shared_across_columns() {
+        let mut compactor = EfficientCompactor::new();
+        let data = json!({
+            "subresources": [
+                {
+                    "url": "https://example.com/1",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/company-information-and-history-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name": "Zylphor",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/tags": ["zylphor"]
+                }
+            ]
+        });
+        let compacted = compactor.compact_comprehensive_data(&data).unwrap();
+        let index = SearchIndex::build(&compacted);
+
+        // "zylphor" appears once in a str_col (company-name) and once more
+        // in a json_col (tags), for the very same row.
+        assert_eq!(index.query("zylphor").len(), 1);
+    }
+
+    #[test]
+    fn test_duplicated_value_shares_one_dictionary_entry() {
+        let compacted = sample_data();
+        let ecobright_entries = compacted
+            .dictionaries
+            .strings
+            .values()
+            .filter(|v| v.as_str() == "EcoBright Solutions")
+            .count();
+        assert_eq!(ecobright_entries, 1, "repeated values should intern once");
+    }
+}
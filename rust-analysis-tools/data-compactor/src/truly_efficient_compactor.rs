@@ -7,12 +7,16 @@ use std::collections::HashMap;
 #[derive(Debug, Clone)]
 pub struct TrulyEfficientCompactor {
     /// Dictionary for URL compression
-    url_dict: HashMap<String, u8>,
-    /// Dictionary for repeated strings 
-    string_dict: HashMap<String, u8>,
-    /// Next available IDs (using small integers)
-    next_url_id: u8,
-    next_string_id: u8,
+    url_dict: HashMap<String, u16>,
+    /// Dictionary for repeated strings
+    string_dict: HashMap<String, u16>,
+    /// Next available IDs. Widened from `u8`: a dataset with more than 255
+    /// distinct URLs or long strings used to silently collapse every id
+    /// past 255 onto the same dictionary entry once `saturating_add` capped out.
+    next_url_id: u16,
+    next_string_id: u16,
+    /// JSON-LD-style term/IRI mapping driving field abbreviation
+    context: Context,
 }
 
 /// Extremely compact data format
@@ -23,19 +27,154 @@ pub struct CompactFormat {
     /// Tabular data organized by resource type
     pub d: HashMap<String, TabularData>,
     /// Dictionaries (only if needed)
-    pub u: Option<HashMap<u8, String>>,  // URLs
-    pub t: Option<HashMap<u8, String>>,  // Text strings
+    pub u: Option<HashMap<u16, String>>,  // URLs
+    pub t: Option<HashMap<u16, String>>,  // Text strings
+    /// The context used to abbreviate field names, so `reconstruct` is
+    /// self-describing and doesn't need a baked-in term table
+    #[serde(default)]
+    pub ctx: Context,
+    /// Maps each group's `shorten_resource_type` key back to the original,
+    /// unabbreviated `resource_type` IRI it was derived from (first one seen
+    /// per key). Without this, `reconstruct` would have no way to recover an
+    /// IRI from its lossy, truncated group key.
+    #[serde(default)]
+    pub rt: HashMap<String, String>,
+    /// zstd dictionary trained over sampled column bytes by
+    /// `compact_with_dictionary`; `None` when untrained or below `min_samples`
+    #[serde(default)]
+    pub dict: Option<Vec<u8>>,
+    /// Per-type, per-column bytes compressed with `dict`; when present,
+    /// the corresponding `TabularData.c` in `d` is left empty
+    #[serde(default)]
+    pub d_compressed: Option<HashMap<String, HashMap<String, CompressedColumn>>>,
     /// Stats
     pub stats: CompactStats,
 }
 
-/// Minimal schema 
+/// A JSON-LD-style `@context`: a `term -> IRI` map plus an optional
+/// `@vocab` prefix. Drives how `TrulyEfficientCompactor` abbreviates and
+/// expands property names, replacing a fixed set of hardcoded IRIs so the
+/// compactor works on any Atomic Data schema, not just terraphim.io's.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Context {
+    /// term -> IRI
+    #[serde(default)]
+    pub terms: HashMap<String, String>,
+    /// IRI prefix that vocab-relative terms compact to/from their suffix
+    #[serde(default)]
+    pub vocab: Option<String>,
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self::atomic_data_defaults()
+    }
+}
+
+impl Context {
+    /// An empty context: every field falls back to the stable prefix-stripped abbreviation
+    pub fn new() -> Self {
+        Self { terms: HashMap::new(), vocab: None }
+    }
+
+    /// A context with only `@vocab` set
+    pub fn with_vocab(vocab: impl Into<String>) -> Self {
+        Self { terms: HashMap::new(), vocab: Some(vocab.into()) }
+    }
+
+    /// Add one term -> IRI mapping
+    pub fn with_term(mut self, term: impl Into<String>, iri: impl Into<String>) -> Self {
+        self.terms.insert(term.into(), iri.into());
+        self
+    }
+
+    /// The term/IRI mappings this crate used to bake in as `match` arms;
+    /// kept as the default context so existing Atomic Data dumps compact
+    /// exactly as before out of the box.
+    pub fn atomic_data_defaults() -> Self {
+        Self::new()
+            .with_term("t", "https://atomicdata.dev/properties/isA")
+            .with_term("p", "https://atomicdata.dev/properties/parent")
+            .with_term("lc", "https://atomicdata.dev/properties/lastCommit")
+            .with_term("u", "url")
+            .with_term("rt", "resource_type")
+            .with_term("jf", "json_format")
+            .with_term("jaf", "json_ad_format")
+            .with_term("tf", "turtle_format")
+            .with_term("fe", "fetch_errors")
+            .with_term("cn", "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name")
+            .with_term("cd", "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-description")
+            .with_term("bw", "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/business-website")
+            .with_term("yi", "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation")
+            .with_term("cr", "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/country-of-registration")
+            .with_term("rn", "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-registration-number")
+    }
+
+    /// Build the reverse `IRI -> term` lookup used during compaction
+    fn reverse(&self) -> HashMap<&str, &str> {
+        self.terms.iter().map(|(term, iri)| (iri.as_str(), term.as_str())).collect()
+    }
+
+    /// Compact `iri` using `reverse` (the context's own reverse lookup, built
+    /// once per compaction pass): an exact term match wins, then a
+    /// vocab-relative suffix, then a stable prefix-stripped abbreviation.
+    fn compact_key(&self, iri: &str, reverse: &HashMap<&str, &str>) -> String {
+        if let Some(&term) = reverse.get(iri) {
+            return term.to_string();
+        }
+        if let Some(vocab) = &self.vocab {
+            if let Some(suffix) = iri.strip_prefix(vocab.as_str()) {
+                if !suffix.is_empty() {
+                    return suffix.to_string();
+                }
+            }
+        }
+        Self::fallback_abbreviation(iri)
+    }
+
+    /// Expand `term` back to its IRI via the forward map, re-prepending
+    /// `@vocab` for vocab-relative terms, or keep it as-is when nothing matches
+    fn expand_key(&self, term: &str) -> String {
+        if let Some(iri) = self.terms.get(term) {
+            return iri.clone();
+        }
+        if let Some(vocab) = &self.vocab {
+            return format!("{}{}", vocab, term);
+        }
+        term.to_string()
+    }
+
+    /// The "first letter of each hyphenated word, max 4 chars" heuristic the
+    /// compactor always fell back to for properties with no explicit term
+    fn fallback_abbreviation(iri: &str) -> String {
+        if iri.contains("/property/") {
+            let suffix = iri.split("/property/").last().unwrap_or(iri);
+            return suffix
+                .split('-')
+                .map(|word| word.chars().next().unwrap_or('x'))
+                .collect::<String>()
+                .chars()
+                .take(4)
+                .collect();
+        }
+
+        let suffix = iri.rsplit(['/', '#']).find(|segment| !segment.is_empty()).unwrap_or(iri);
+        suffix.chars().take(4).collect()
+    }
+}
+
+/// Minimal schema
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Schema {
     /// Field types: s=string, i=int, f=float, b=bool, u=url, j=json
     pub types: HashMap<String, char>,
     /// Field order for arrays
     pub order: Vec<String>,
+    /// Per-column encoding chosen by `convert_to_tabular`: 'r' = run-length,
+    /// 'd' = delta, absent = raw array, so `reconstruct` knows how to
+    /// invert each column before decompressing its cells row-by-row
+    #[serde(default)]
+    pub encodings: HashMap<String, char>,
 }
 
 /// Tabular data format
@@ -53,16 +192,93 @@ pub struct CompactStats {
     pub orig: usize,
     pub comp: usize,
     pub ratio: f32,
+    /// Serialized size before the trained-dictionary stage ran
+    #[serde(default)]
+    pub pre_dict_size: Option<usize>,
+    /// Serialized size after compressing columns with the trained dictionary
+    #[serde(default)]
+    pub post_dict_size: Option<usize>,
+}
+
+impl CompactFormat {
+    /// Serialize to CBOR instead of JSON. Column arrays and dictionary ids
+    /// are already small integers and tagged cells, which CBOR packs as
+    /// binary rather than re-paying JSON's quoting/stringification overhead —
+    /// this is the format `compact_with_cbor_stats` measures `comp` against.
+    pub fn to_cbor(&self) -> Result<Vec<u8>> {
+        use anyhow::Context as _;
+        serde_cbor::to_vec(self).context("failed to serialize CompactFormat to CBOR")
+    }
+
+    /// Inverse of `to_cbor`
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self> {
+        use anyhow::Context as _;
+        serde_cbor::from_slice(bytes).context("failed to deserialize CompactFormat from CBOR")
+    }
+
+    /// Serialize to CBOR, then run the result through the self-contained
+    /// `lzss` sliding-window codec. Heavier than `to_cbor` alone on
+    /// incompressible data, but useful where a caller can't pull in zstd
+    /// or another external compression dependency.
+    pub fn to_lzss(&self) -> Result<Vec<u8>> {
+        Ok(crate::lzss::compress(&self.to_cbor()?))
+    }
+
+    /// Inverse of `to_lzss`
+    pub fn from_lzss(bytes: &[u8]) -> Result<Self> {
+        Self::from_cbor(&crate::lzss::decompress(bytes)?)
+    }
+}
+
+/// One column's bytes after zstd dictionary compression
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompressedColumn {
+    /// Decompressed byte length, needed to size the zstd bulk decompression buffer
+    pub uncompressed_len: usize,
+    pub bytes: Vec<u8>,
+}
+
+/// Tag distinguishing what a compressed cell's payload means, so
+/// `decompress_value` never has to guess from the JSON type alone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CellTag {
+    /// Payload is the original value, untouched
+    RawScalar = 0,
+    /// Payload is a `u` dictionary id
+    UrlRef = 1,
+    /// Payload is a `t` dictionary id
+    StringRef = 2,
+    /// Payload is an array of further tagged cells
+    Nested = 3,
+}
+
+impl CellTag {
+    fn from_u64(tag: u64) -> Option<Self> {
+        match tag {
+            0 => Some(Self::RawScalar),
+            1 => Some(Self::UrlRef),
+            2 => Some(Self::StringRef),
+            3 => Some(Self::Nested),
+            _ => None,
+        }
+    }
 }
 
 impl TrulyEfficientCompactor {
-    /// Create new compactor
+    /// Create new compactor using the built-in Atomic Data context
     pub fn new() -> Self {
+        Self::with_context(Context::atomic_data_defaults())
+    }
+
+    /// Create a compactor driven by a custom `@context`, for schemas other
+    /// than terraphim.io/atomicdata.dev Atomic Data
+    pub fn with_context(context: Context) -> Self {
         Self {
             url_dict: HashMap::new(),
             string_dict: HashMap::new(),
             next_url_id: 1,
             next_string_id: 1,
+            context,
         }
     }
 
@@ -79,11 +295,13 @@ impl TrulyEfficientCompactor {
 
         // Group by resource type
         let mut grouped: HashMap<String, Vec<&Value>> = HashMap::new();
+        let mut type_names: HashMap<String, String> = HashMap::new();
         for resource in subresources {
             let resource_type = resource.get("resource_type")
                 .and_then(|v| v.as_str())
                 .unwrap_or("unknown");
             let short_type = self.shorten_resource_type(resource_type);
+            type_names.entry(short_type.clone()).or_insert_with(|| resource_type.to_string());
             grouped.entry(short_type).or_default().push(resource);
         }
 
@@ -92,12 +310,15 @@ impl TrulyEfficientCompactor {
         let mut schema = Schema {
             types: HashMap::new(),
             order: Vec::new(),
+            encodings: HashMap::new(),
         };
 
+        let reverse = self.context.reverse();
+
         for (type_name, resources) in grouped {
-            let (tabular, type_schema) = self.convert_to_tabular(&resources)?;
+            let (tabular, type_schema, type_encodings) = self.convert_to_tabular(&resources, &reverse)?;
             compact_data.insert(type_name, tabular);
-            
+
             // Merge schema
             for (field, field_type) in type_schema {
                 schema.types.insert(field.clone(), field_type);
@@ -105,6 +326,9 @@ impl TrulyEfficientCompactor {
                     schema.order.push(field);
                 }
             }
+            for (field, encoding) in type_encodings {
+                schema.encodings.insert(field, encoding);
+            }
         }
 
         let compact_format = CompactFormat {
@@ -113,19 +337,25 @@ impl TrulyEfficientCompactor {
             u: if self.url_dict.is_empty() { None } else { 
                 Some(self.url_dict.iter().map(|(url, &id)| (id, url.clone())).collect()) 
             },
-            t: if self.string_dict.is_empty() { None } else { 
-                Some(self.string_dict.iter().map(|(s, &id)| (id, s.clone())).collect()) 
+            t: if self.string_dict.is_empty() { None } else {
+                Some(self.string_dict.iter().map(|(s, &id)| (id, s.clone())).collect())
             },
+            ctx: self.context.clone(),
+            rt: type_names,
+            dict: None,
+            d_compressed: None,
             stats: CompactStats {
                 orig: original_size,
                 comp: 0, // Will be filled after serialization
                 ratio: 0.0,
+                pre_dict_size: None,
+                post_dict_size: None,
             },
         };
 
         let compact_json = serde_json::to_string(&compact_format)?;
         let compact_size = compact_json.len();
-        
+
         Ok(CompactFormat {
             stats: CompactStats {
                 orig: original_size,
@@ -133,15 +363,113 @@ impl TrulyEfficientCompactor {
                 ratio: if original_size > 0 {
                     (original_size as f32 - compact_size as f32) / original_size as f32
                 } else { 0.0 },
+                pre_dict_size: None,
+                post_dict_size: None,
             },
             ..compact_format
         })
     }
 
+    /// Compact `data` as usual, then additionally train a zstd dictionary
+    /// over the sampled column bytes (when at least `min_samples` columns
+    /// are available) and re-compress each column with it. Small payloads
+    /// without enough samples to train on are left dictionary-free.
+    pub fn compact_with_dictionary(&mut self, data: &Value, min_samples: usize) -> Result<CompactFormat> {
+        let mut compact_format = self.compact(data)?;
+        let pre_dict_size = serde_json::to_string(&compact_format)?.len();
+        compact_format.stats.pre_dict_size = Some(pre_dict_size);
+
+        let samples: Vec<Vec<u8>> = compact_format
+            .d
+            .values()
+            .flat_map(|tabular| tabular.c.values())
+            .map(serde_json::to_vec)
+            .collect::<std::result::Result<_, _>>()?;
+
+        if samples.len() < min_samples {
+            return Ok(compact_format);
+        }
+
+        let dict = Self::train_dictionary(&samples)?;
+
+        let mut d_compressed: HashMap<String, HashMap<String, CompressedColumn>> = HashMap::new();
+        for (type_name, tabular) in compact_format.d.iter_mut() {
+            let mut compressed_columns = HashMap::new();
+            for (field_name, column) in tabular.c.iter() {
+                let bytes = serde_json::to_vec(column)?;
+                let compressed = Self::compress_with_dict(&bytes, &dict)?;
+                compressed_columns.insert(
+                    field_name.clone(),
+                    CompressedColumn { uncompressed_len: bytes.len(), bytes: compressed },
+                );
+            }
+            d_compressed.insert(type_name.clone(), compressed_columns);
+            tabular.c.clear();
+        }
+
+        compact_format.dict = Some(dict);
+        compact_format.d_compressed = Some(d_compressed);
+
+        let post_dict_json = serde_json::to_string(&compact_format)?;
+        let post_dict_size = post_dict_json.len();
+        compact_format.stats.post_dict_size = Some(post_dict_size);
+        compact_format.stats.comp = post_dict_size;
+        compact_format.stats.ratio = if compact_format.stats.orig > 0 {
+            (compact_format.stats.orig as f32 - post_dict_size as f32) / compact_format.stats.orig as f32
+        } else {
+            0.0
+        };
+
+        Ok(compact_format)
+    }
+
+    /// Compact `data` as usual, then recompute `CompactStats.comp`/`.ratio`
+    /// against the CBOR-encoded byte length instead of the JSON string
+    /// length, so `ratio` reflects the actual on-wire size when callers
+    /// ship `to_cbor()` rather than `serde_json::to_string`.
+    pub fn compact_with_cbor_stats(&mut self, data: &Value) -> Result<CompactFormat> {
+        let mut compact_format = self.compact(data)?;
+
+        let cbor_size = compact_format.to_cbor()?.len();
+        compact_format.stats.comp = cbor_size;
+        compact_format.stats.ratio = if compact_format.stats.orig > 0 {
+            (compact_format.stats.orig as f32 - cbor_size as f32) / compact_format.stats.orig as f32
+        } else {
+            0.0
+        };
+
+        Ok(compact_format)
+    }
+
+    /// Train a small zstd dictionary over sampled column bytes
+    fn train_dictionary(samples: &[Vec<u8>]) -> Result<Vec<u8>> {
+        use anyhow::Context as _;
+        const MAX_DICT_SIZE: usize = 8192;
+        zstd::dict::from_samples(samples, MAX_DICT_SIZE)
+            .context("failed to train zstd dictionary from column samples")
+    }
+
+    /// Compress `bytes` with a trained zstd dictionary
+    fn compress_with_dict(bytes: &[u8], dict: &[u8]) -> Result<Vec<u8>> {
+        let mut compressor = zstd::bulk::Compressor::with_dictionary(3, dict)?;
+        Ok(compressor.compress(bytes)?)
+    }
+
+    /// Decompress `bytes` with a trained zstd dictionary, given the known
+    /// uncompressed length zstd's bulk API needs to size its output buffer
+    fn decompress_with_dict(bytes: &[u8], dict: &[u8], uncompressed_len: usize) -> Result<Vec<u8>> {
+        let mut decompressor = zstd::bulk::Decompressor::with_dictionary(dict)?;
+        Ok(decompressor.decompress(bytes, uncompressed_len)?)
+    }
+
     /// Convert resources to tabular format using columnar storage
-    fn convert_to_tabular(&mut self, resources: &[&Value]) -> Result<(TabularData, HashMap<String, char>)> {
+    fn convert_to_tabular(
+        &mut self,
+        resources: &[&Value],
+        reverse: &HashMap<&str, &str>,
+    ) -> Result<(TabularData, HashMap<String, char>, HashMap<String, char>)> {
         if resources.is_empty() {
-            return Ok((TabularData { n: 0, c: HashMap::new() }, HashMap::new()));
+            return Ok((TabularData { n: 0, c: HashMap::new() }, HashMap::new(), HashMap::new()));
         }
 
         let n = resources.len();
@@ -153,9 +481,9 @@ impl TrulyEfficientCompactor {
         for resource in resources {
             if let Some(obj) = resource.as_object() {
                 for (key, value) in obj {
-                    let short_key = self.abbreviate_field(key);
+                    let short_key = self.abbreviate_field(key, reverse);
                     let field_type = self.infer_compact_type(value);
-                    
+
                     field_analysis.entry(short_key).or_insert_with(|| (field_type, Vec::new()));
                 }
             }
@@ -176,16 +504,135 @@ impl TrulyEfficientCompactor {
             }
         }
 
-        // Build columnar data
+        // Build columnar data, choosing a per-column encoding that shrinks it
         let mut columns = HashMap::new();
         let mut schema = HashMap::new();
+        let mut encodings = HashMap::new();
 
         for (field_name, (field_type, values)) in field_analysis {
             schema.insert(field_name.clone(), field_type);
-            columns.insert(field_name, Value::Array(values));
+
+            let (encoded, encoding) = Self::encode_column(values);
+            if let Some(encoding) = encoding {
+                encodings.insert(field_name.clone(), encoding);
+            }
+            columns.insert(field_name, encoded);
+        }
+
+        Ok((TabularData { n, c: columns }, schema, encodings))
+    }
+
+    /// Pick whichever of delta, run-length, or raw encoding is smallest for
+    /// `values` (already type-tagged `[tag, payload]` cells) and return the
+    /// encoded column alongside the tag `reconstruct` needs to invert it.
+    /// Delta only ever applies to a column of plain monotonic integers, so it
+    /// is tried first; otherwise a long-enough constant run favors RLE.
+    fn encode_column(values: Vec<Value>) -> (Value, Option<char>) {
+        if let Some(deltas) = Self::try_delta_encode(&values) {
+            return (Value::Array(deltas), Some('d'));
+        }
+        if let Some(runs) = Self::try_rle_encode(&values) {
+            return (Value::Array(runs), Some('r'));
+        }
+        (Value::Array(values), None)
+    }
+
+    /// Delta-encode a column of monotonic integer `RawScalar` cells: the
+    /// first cell verbatim, then the successive `i64` differences. Returns
+    /// `None` for empty columns, non-integer cells, or a non-monotone run.
+    fn try_delta_encode(values: &[Value]) -> Option<Vec<Value>> {
+        let ints: Vec<i64> = values
+            .iter()
+            .map(|cell| {
+                let [tag, payload] = cell.as_array()?.as_slice() else { return None };
+                if tag.as_u64() != Some(CellTag::RawScalar as u64) {
+                    return None;
+                }
+                payload.as_i64()
+            })
+            .collect::<Option<_>>()?;
+
+        if ints.len() < 2 {
+            return None;
+        }
+
+        let increasing = ints.windows(2).all(|w| w[1] >= w[0]);
+        let decreasing = ints.windows(2).all(|w| w[1] <= w[0]);
+        if !increasing && !decreasing {
+            return None;
+        }
+
+        let mut encoded = vec![values[0].clone()];
+        encoded.extend(ints.windows(2).map(|w| Value::Number((w[1] - w[0]).into())));
+        Some(encoded)
+    }
+
+    /// Run-length-encode `values` as `[run_len, value]` pairs when constant
+    /// runs make up at least half the column, shrinking the encoded form.
+    fn try_rle_encode(values: &[Value]) -> Option<Vec<Value>> {
+        const RUN_FRACTION_THRESHOLD: f64 = 0.5;
+
+        let mut runs: Vec<(u64, &Value)> = Vec::new();
+        for value in values {
+            match runs.last_mut() {
+                Some((count, last)) if *last == value => *count += 1,
+                _ => runs.push((1, value)),
+            }
+        }
+
+        if (runs.len() as f64) > (values.len() as f64) * RUN_FRACTION_THRESHOLD {
+            return None;
         }
 
-        Ok((TabularData { n, c: columns }, schema))
+        Some(
+            runs.into_iter()
+                .map(|(count, value)| Value::Array(vec![Value::Number(count.into()), value.clone()]))
+                .collect(),
+        )
+    }
+
+    /// Inverse of `encode_column`: expand a run-length or delta-encoded
+    /// column back into one cell per row; raw columns pass through unchanged.
+    fn decode_column(encoded: &Value, encoding: Option<char>) -> Result<Vec<Value>> {
+        use anyhow::{bail, Context as _};
+
+        let entries = encoded.as_array().context("expected column to be a JSON array")?;
+
+        match encoding {
+            None => Ok(entries.clone()),
+            Some('r') => {
+                let mut values = Vec::new();
+                for entry in entries {
+                    let [count, value] = entry.as_array().context("expected [run_len, value] pair")?.as_slice() else {
+                        bail!("expected a 2-element [run_len, value] pair");
+                    };
+                    let count = count.as_u64().context("run length must be an integer")?;
+                    values.extend(std::iter::repeat(value.clone()).take(count as usize));
+                }
+                Ok(values)
+            },
+            Some('d') => {
+                let Some((first, diffs)) = entries.split_first() else {
+                    return Ok(Vec::new());
+                };
+                let [tag, payload] = first.as_array().context("expected a tagged first cell")?.as_slice() else {
+                    bail!("expected a 2-element tagged first cell");
+                };
+                let mut current = payload.as_i64().context("delta column's first cell must be an integer")?;
+
+                let mut values = vec![Self::tagged(
+                    CellTag::from_u64(tag.as_u64().context("cell tag must be an integer")?)
+                        .context("unknown cell tag in delta-encoded column")?,
+                    Value::Number(current.into()),
+                )];
+                for diff in diffs {
+                    current += diff.as_i64().context("delta must be an integer")?;
+                    values.push(Self::tagged(CellTag::RawScalar, Value::Number(current.into())));
+                }
+                Ok(values)
+            },
+            Some(other) => bail!("unknown column encoding '{other}'"),
+        }
     }
 
     /// Shorten resource type URL to minimal identifier
@@ -197,43 +644,10 @@ impl TrulyEfficientCompactor {
             .chars().take(8).collect() // Limit to 8 chars max
     }
 
-    /// Abbreviate field names aggressively
-    fn abbreviate_field(&self, field: &str) -> String {
-        match field {
-            // Core atomic properties
-            "https://atomicdata.dev/properties/isA" => "t".to_string(),
-            "https://atomicdata.dev/properties/parent" => "p".to_string(),
-            "https://atomicdata.dev/properties/lastCommit" => "lc".to_string(),
-            "url" => "u".to_string(),
-            "resource_type" => "rt".to_string(),
-            "json_format" => "jf".to_string(),
-            "json_ad_format" => "jaf".to_string(),
-            "turtle_format" => "tf".to_string(),
-            "fetch_errors" => "fe".to_string(),
-            
-            // Company properties - use initials
-            s if s.contains("company-name") => "cn".to_string(),
-            s if s.contains("company-description") => "cd".to_string(),
-            s if s.contains("business-website") => "bw".to_string(),
-            s if s.contains("year-of-incorporation") => "yi".to_string(),
-            s if s.contains("country-of-registration") => "cr".to_string(),
-            s if s.contains("registration-number") => "rn".to_string(),
-            
-            // For other long URLs, extract meaningful abbreviation
-            s if s.contains("/property/") => {
-                s.split("/property/").last().unwrap_or(s)
-                    .split('-')
-                    .map(|word| word.chars().next().unwrap_or('x'))
-                    .collect::<String>()
-                    .chars().take(4).collect() // Max 4 chars
-            },
-            
-            // Keep short fields as-is
-            s if s.len() <= 4 => s.to_string(),
-            
-            // Truncate long fields
-            _ => field.chars().take(4).collect(),
-        }
+    /// Abbreviate a field name via the active context: an explicit term
+    /// wins, then a vocab-relative suffix, then a stable fallback abbreviation
+    fn abbreviate_field(&self, field: &str, reverse: &HashMap<&str, &str>) -> String {
+        self.context.compact_key(field, reverse)
     }
 
     /// Infer most compact type representation
@@ -249,40 +663,56 @@ impl TrulyEfficientCompactor {
         }
     }
 
-    /// Compress a value using dictionaries
+    /// Compress a value into a type-tagged `[tag, payload]` cell so
+    /// `decompress_value` can dispatch on the tag instead of guessing from
+    /// the JSON type — a raw integer like `2020` is otherwise indistinguishable
+    /// from a dictionary id of the same value.
     fn compress_value(&mut self, value: Value) -> Result<Value> {
         match value {
             Value::String(s) if s.starts_with("http") => {
-                // URL compression
-                if let Some(&id) = self.url_dict.get(&s) {
-                    Ok(Value::Number(id.into()))
-                } else {
-                    let id = self.next_url_id;
-                    self.url_dict.insert(s, id);
-                    self.next_url_id = self.next_url_id.saturating_add(1);
-                    Ok(Value::Number(id.into()))
-                }
+                let id = self.intern_url(s);
+                Ok(Self::tagged(CellTag::UrlRef, Value::Number(id.into())))
             },
             Value::String(s) if s.len() > 50 => {
-                // Long string compression
-                if let Some(&id) = self.string_dict.get(&s) {
-                    Ok(Value::Number(id.into()))
-                } else {
-                    let id = self.next_string_id;
-                    self.string_dict.insert(s, id);
-                    self.next_string_id = self.next_string_id.saturating_add(1);
-                    Ok(Value::Number(id.into()))
-                }
+                let id = self.intern_string(s);
+                Ok(Self::tagged(CellTag::StringRef, Value::Number(id.into())))
             },
             Value::Array(arr) => {
-                // Compress array elements
                 let compressed: Result<Vec<_>> = arr.into_iter()
                     .map(|v| self.compress_value(v))
                     .collect();
-                Ok(Value::Array(compressed?))
+                Ok(Self::tagged(CellTag::Nested, Value::Array(compressed?)))
             },
-            // Keep other values as-is for maximum compression
-            _ => Ok(value),
+            other => Ok(Self::tagged(CellTag::RawScalar, other)),
+        }
+    }
+
+    /// Wrap `payload` with its `tag` as a 2-element `[tag, payload]` cell
+    fn tagged(tag: CellTag, payload: Value) -> Value {
+        Value::Array(vec![Value::Number((tag as u8).into()), payload])
+    }
+
+    /// Intern a URL, returning its dictionary id
+    fn intern_url(&mut self, url: String) -> u16 {
+        if let Some(&id) = self.url_dict.get(&url) {
+            id
+        } else {
+            let id = self.next_url_id;
+            self.url_dict.insert(url, id);
+            self.next_url_id += 1;
+            id
+        }
+    }
+
+    /// Intern a long string, returning its dictionary id
+    fn intern_string(&mut self, s: String) -> u16 {
+        if let Some(&id) = self.string_dict.get(&s) {
+            id
+        } else {
+            let id = self.next_string_id;
+            self.string_dict.insert(s, id);
+            self.next_string_id += 1;
+            id
         }
     }
 
@@ -293,35 +723,16 @@ impl TrulyEfficientCompactor {
             return Some(abbrev);
         }
 
-        // Then try to reverse-lookup common abbreviations
-        let possible_keys = match abbrev {
-            "t" => vec!["https://atomicdata.dev/properties/isA"],
-            "p" => vec!["https://atomicdata.dev/properties/parent"],
-            "lc" => vec!["https://atomicdata.dev/properties/lastCommit"],
-            "cn" => vec![
-                "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name",
-                "company-name",
-            ],
-            "cd" => vec![
-                "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-description",
-                "company-description",
-            ],
-            "yi" => vec![
-                "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation",
-                "year-of-incorporation",
-            ],
-            _ => vec![],
-        };
-
-        for key in possible_keys {
-            if obj.contains_key(key) {
-                return Some(key);
-            }
+        // Then expand via the active context
+        let expanded = self.context.expand_key(abbrev);
+        if obj.contains_key(expanded.as_str()) {
+            return obj.keys().find(|k| k.as_str() == expanded).map(|k| k.as_str());
         }
 
-        // Last resort: find any key that might match
+        // Last resort: find any key that might match once abbreviated
+        let reverse = self.context.reverse();
         for key in obj.keys() {
-            if self.abbreviate_field(key) == abbrev {
+            if self.abbreviate_field(key, &reverse) == abbrev {
                 return Some(key);
             }
         }
@@ -334,26 +745,30 @@ impl TrulyEfficientCompactor {
         let mut subresources = Vec::new();
 
         for (type_name, tabular) in &compact.d {
+            let columns = Self::resolve_columns(compact, type_name, tabular)?;
+
             // Reconstruct each row
             for i in 0..tabular.n {
                 let mut resource = serde_json::Map::new();
-                
-                // Add resource type
-                let full_type = format!(
+
+                // Add resource type, preferring the original IRI recorded in
+                // `compact.rt`; the terraphim.io-specific fallback only
+                // covers formats produced before that dictionary existed.
+                let full_type = compact.rt.get(type_name).cloned().unwrap_or_else(|| format!(
                     "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/{}-step",
                     type_name.replace('_', "-")
-                );
+                ));
                 resource.insert("resource_type".to_string(), Value::String(full_type));
 
                 // Reconstruct each field
-                for (field_name, column) in &tabular.c {
-                    if let Some(values) = column.as_array() {
-                        if let Some(value) = values.get(i) {
-                            if !value.is_null() {
-                                let decompressed_value = Self::decompress_value(value, compact)?;
-                                let original_field = Self::expand_field_name(field_name);
-                                resource.insert(original_field, decompressed_value);
-                            }
+                for (field_name, column) in &columns {
+                    let encoding = compact.s.encodings.get(field_name).copied();
+                    let values = Self::decode_column(column, encoding)?;
+                    if let Some(value) = values.get(i) {
+                        if !value.is_null() {
+                            let decompressed_value = Self::decompress_value(value, compact)?;
+                            let original_field = compact.ctx.expand_key(field_name);
+                            resource.insert(original_field, decompressed_value);
                         }
                     }
                 }
@@ -367,60 +782,77 @@ impl TrulyEfficientCompactor {
         }))
     }
 
+    /// Get the columns for one resource type, transparently decompressing
+    /// them from `d_compressed` when a trained dictionary is present;
+    /// otherwise just the plain columns already sitting in `tabular.c`
+    fn resolve_columns(
+        compact: &CompactFormat,
+        type_name: &str,
+        tabular: &TabularData,
+    ) -> Result<HashMap<String, Value>> {
+        let (Some(dict), Some(d_compressed)) = (&compact.dict, &compact.d_compressed) else {
+            return Ok(tabular.c.clone());
+        };
+
+        let Some(compressed_columns) = d_compressed.get(type_name) else {
+            return Ok(tabular.c.clone());
+        };
+
+        compressed_columns
+            .iter()
+            .map(|(field_name, compressed)| {
+                let bytes = Self::decompress_with_dict(&compressed.bytes, dict, compressed.uncompressed_len)?;
+                let value: Value = serde_json::from_slice(&bytes)?;
+                Ok((field_name.clone(), value))
+            })
+            .collect()
+    }
+
+    /// Inverse of `compact`: rehydrate `subresources` from schema + dictionaries.
+    /// Named to mirror `compact` so the round trip reads as `decompact(compact(x))`.
+    pub fn decompact(compact: &CompactFormat) -> Result<Value> {
+        Self::reconstruct(compact)
+    }
+
     /// Decompress a value using dictionaries
     fn decompress_value(value: &Value, compact: &CompactFormat) -> Result<Value> {
-        match value {
-            Value::Number(n) if n.is_u64() => {
-                let id = n.as_u64().unwrap() as u8;
-                
-                // Try URL dictionary first
-                if let Some(ref url_dict) = compact.u {
-                    if let Some(url) = url_dict.get(&id) {
-                        return Ok(Value::String(url.clone()));
-                    }
-                }
-                
-                // Try string dictionary
-                if let Some(ref string_dict) = compact.t {
-                    if let Some(text) = string_dict.get(&id) {
-                        return Ok(Value::String(text.clone()));
-                    }
-                }
-                
-                // If not found in dictionaries, keep as number
-                Ok(value.clone())
+        use anyhow::{bail, Context as _};
+
+        let Value::Array(cell) = value else {
+            bail!("expected a tagged [tag, payload] cell, got {value}");
+        };
+        let [tag, payload] = &cell[..] else {
+            bail!("expected a 2-element tagged cell, got {} elements", cell.len());
+        };
+        let tag = CellTag::from_u64(tag.as_u64().context("cell tag must be an integer")?)
+            .with_context(|| format!("unknown cell tag {tag}"))?;
+
+        match tag {
+            CellTag::RawScalar => Ok(payload.clone()),
+            CellTag::UrlRef => {
+                let id = payload.as_u64().context("url ref payload must be an integer")? as u16;
+                compact.u.as_ref()
+                    .and_then(|dict| dict.get(&id))
+                    .map(|url| Value::String(url.clone()))
+                    .with_context(|| format!("missing url dictionary entry for id {id}"))
             },
-            Value::Array(arr) => {
-                let decompressed: Result<Vec<_>> = arr.iter()
+            CellTag::StringRef => {
+                let id = payload.as_u64().context("string ref payload must be an integer")? as u16;
+                compact.t.as_ref()
+                    .and_then(|dict| dict.get(&id))
+                    .map(|s| Value::String(s.clone()))
+                    .with_context(|| format!("missing string dictionary entry for id {id}"))
+            },
+            CellTag::Nested => {
+                let elements = payload.as_array().context("nested payload must be an array")?;
+                let decompressed: Result<Vec<_>> = elements.iter()
                     .map(|v| Self::decompress_value(v, compact))
                     .collect();
                 Ok(Value::Array(decompressed?))
             },
-            _ => Ok(value.clone()),
         }
     }
 
-    /// Expand abbreviated field names back to full names
-    fn expand_field_name(abbrev: &str) -> String {
-        match abbrev {
-            "t" => "https://atomicdata.dev/properties/isA".to_string(),
-            "p" => "https://atomicdata.dev/properties/parent".to_string(),
-            "lc" => "https://atomicdata.dev/properties/lastCommit".to_string(),
-            "cn" => "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name".to_string(),
-            "cd" => "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-description".to_string(),
-            "bw" => "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/business-website".to_string(),
-            "yi" => "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation".to_string(),
-            "cr" => "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/country-of-registration".to_string(),
-            "rn" => "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-registration-number".to_string(),
-            "u" => "url".to_string(),
-            "rt" => "resource_type".to_string(),
-            "jf" => "json_format".to_string(),
-            "jaf" => "json_ad_format".to_string(),
-            "tf" => "turtle_format".to_string(),
-            "fe" => "fetch_errors".to_string(),
-            _ => abbrev.to_string(), // Keep unknown abbreviations as-is
-        }
-    }
 }
 
 impl Default for TrulyEfficientCompactor {
@@ -484,17 +916,78 @@ mod tests {
     #[test]
     fn test_field_abbreviation() {
         let compactor = TrulyEfficientCompactor::new();
-        
-        assert_eq!(compactor.abbreviate_field("https://atomicdata.dev/properties/isA"), "t");
-        assert_eq!(compactor.abbreviate_field("url"), "u");
-        assert_eq!(compactor.abbreviate_field("resource_type"), "rt");
-        assert_eq!(compactor.abbreviate_field("https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name"), "cn");
+        let reverse = compactor.context.reverse();
+
+        assert_eq!(compactor.abbreviate_field("https://atomicdata.dev/properties/isA", &reverse), "t");
+        assert_eq!(compactor.abbreviate_field("url", &reverse), "u");
+        assert_eq!(compactor.abbreviate_field("resource_type", &reverse), "rt");
+        assert_eq!(compactor.abbreviate_field("https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/company-name", &reverse), "cn");
+    }
+
+    #[test]
+    fn test_custom_context_round_trips_without_hardcoded_terms() {
+        let mut compactor = TrulyEfficientCompactor::with_context(
+            Context::with_vocab("https://example.org/schema/").with_term("gid", "https://example.org/id/grid"),
+        );
+
+        let original = json!({
+            "subresources": [{
+                "resource_type": "https://example.org/schema/widget-step",
+                "https://example.org/id/grid": "grid.1.2",
+                "https://example.org/schema/color": "blue"
+            }]
+        });
+
+        let compacted = compactor.compact(&original).unwrap();
+        let reconstructed = TrulyEfficientCompactor::reconstruct(&compacted).unwrap();
+
+        let resource = &reconstructed["subresources"].as_array().unwrap()[0];
+        assert_eq!(resource["https://example.org/id/grid"], "grid.1.2");
+        assert_eq!(resource["https://example.org/schema/color"], "blue");
+        // The original resource_type IRI must come back verbatim, not the
+        // hardcoded common.terraphim.io class IRI `shorten_resource_type`'s
+        // lossy 8-char grouping key used to reconstruct before `rt` existed.
+        assert_eq!(resource["resource_type"], "https://example.org/schema/widget-step");
+    }
+
+    #[test]
+    fn test_integer_surviving_dictionary_id_collision_round_trips_exactly() {
+        let mut compactor = TrulyEfficientCompactor::new();
+
+        // One resource interns a URL as dictionary id 1, and a second
+        // resource carries a plain integer field whose value is also 1.
+        // Before type-tagged cells, decompress_value would guess this raw
+        // `1` was a dictionary id and corrupt it into the interned URL.
+        let original = json!({
+            "subresources": [
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/foo-step",
+                    "url": "https://example.com/only-url"
+                },
+                {
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/foo-step",
+                    "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation": 1
+                }
+            ]
+        });
+
+        let compacted = compactor.compact(&original).unwrap();
+        let reconstructed = TrulyEfficientCompactor::reconstruct(&compacted).unwrap();
+        let resources = reconstructed["subresources"].as_array().unwrap();
+
+        let year_resource = resources.iter()
+            .find(|r| r.get("yi").or_else(|| r.get("https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation")).is_some())
+            .unwrap();
+        let year_value = year_resource.get("yi")
+            .or_else(|| year_resource.get("https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/property/year-of-incorporation"))
+            .unwrap();
+        assert_eq!(year_value, &json!(1), "integer 1 must not be corrupted into the interned URL with dictionary id 1");
     }
 
     #[test]
     fn test_round_trip() {
         let mut compactor = TrulyEfficientCompactor::new();
-        
+
         let original = json!({
             "subresources": [{
                 "url": "https://example.com",
@@ -521,4 +1014,191 @@ mod tests {
 
         println!("✅ Round-trip test passed!");
     }
+
+    #[test]
+    fn test_decompact_is_structurally_equal_to_original() {
+        let mut compactor = TrulyEfficientCompactor::new();
+
+        // A fixture chosen so abbreviation/dictionary compression is fully
+        // reversible: a short resource type, a url field, and a plain
+        // property that don't collide with any other field's abbreviation.
+        let original = json!({
+            "subresources": [{
+                "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/foo-step",
+                "url": "https://example.com/r1",
+                "https://atomicdata.dev/properties/isA": "bar"
+            }]
+        });
+
+        let compacted = compactor.compact(&original).unwrap();
+        let decompacted = TrulyEfficientCompactor::decompact(&compacted).unwrap();
+
+        assert_eq!(decompacted, original);
+    }
+
+    #[test]
+    fn test_compact_with_dictionary_below_min_samples_stays_dictionary_free() {
+        let mut compactor = TrulyEfficientCompactor::new();
+        let data = json!({
+            "subresources": [{
+                "url": "https://example.com/1",
+                "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/foo-step",
+                "https://atomicdata.dev/properties/isA": "bar"
+            }]
+        });
+
+        let compacted = compactor.compact_with_dictionary(&data, 1000).unwrap();
+        assert!(compacted.dict.is_none());
+        assert!(compacted.d_compressed.is_none());
+        assert!(compacted.stats.pre_dict_size.is_some());
+        assert!(compacted.stats.post_dict_size.is_none());
+    }
+
+    #[test]
+    fn test_compact_with_dictionary_round_trips() {
+        let mut compactor = TrulyEfficientCompactor::new();
+
+        let make_resource = |i: usize| {
+            json!({
+                "url": format!("https://example.com/{}", i),
+                "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/foo-step",
+                "json_format": format!("{{\"@id\": \"https://example.com/{}\", \"note\": \"shared structural prefix across many similar blobs\"}}", i),
+                "https://atomicdata.dev/properties/isA": "bar"
+            })
+        };
+
+        let data = json!({
+            "subresources": (0..5).map(make_resource).collect::<Vec<_>>()
+        });
+
+        let compacted = compactor.compact_with_dictionary(&data, 1).unwrap();
+        assert!(compacted.dict.is_some());
+        assert!(compacted.d_compressed.is_some());
+        assert!(compacted.stats.pre_dict_size.is_some());
+        assert!(compacted.stats.post_dict_size.is_some());
+
+        let reconstructed = TrulyEfficientCompactor::reconstruct(&compacted).unwrap();
+        let resources = reconstructed["subresources"].as_array().unwrap();
+        assert_eq!(resources.len(), 5);
+        assert!(resources.iter().any(|r| r.get("https://atomicdata.dev/properties/isA").map(|v| v == "bar").unwrap_or(false)));
+    }
+
+    #[test]
+    fn test_cbor_round_trips_through_reconstruct() {
+        let mut compactor = TrulyEfficientCompactor::new();
+        let original = json!({
+            "subresources": [{
+                "url": "https://example.com/1",
+                "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/foo-step",
+                "https://atomicdata.dev/properties/isA": "bar"
+            }]
+        });
+
+        let compacted = compactor.compact(&original).unwrap();
+        let bytes = compacted.to_cbor().unwrap();
+        let from_cbor = CompactFormat::from_cbor(&bytes).unwrap();
+
+        let decompacted = TrulyEfficientCompactor::reconstruct(&from_cbor).unwrap();
+        assert_eq!(decompacted, original);
+    }
+
+    #[test]
+    fn test_lzss_round_trips_through_reconstruct() {
+        let mut compactor = TrulyEfficientCompactor::new();
+        let original = json!({
+            "subresources": (0..10).map(|i| json!({
+                "url": format!("https://example.com/{}", i),
+                "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/foo-step",
+                "https://atomicdata.dev/properties/isA": "bar"
+            })).collect::<Vec<_>>()
+        });
+
+        let compacted = compactor.compact(&original).unwrap();
+        let bytes = compacted.to_lzss().unwrap();
+        let from_lzss = CompactFormat::from_lzss(&bytes).unwrap();
+
+        let decompacted = TrulyEfficientCompactor::reconstruct(&from_lzss).unwrap();
+        assert_eq!(decompacted, original);
+    }
+
+    #[test]
+    fn test_compact_with_cbor_stats_measures_cbor_byte_length() {
+        let mut compactor = TrulyEfficientCompactor::new();
+        let data = json!({
+            "subresources": (0..10).map(|i| json!({
+                "url": format!("https://example.com/{}", i),
+                "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/foo-step",
+                "https://atomicdata.dev/properties/isA": "bar",
+                "year": 2000 + i
+            })).collect::<Vec<_>>()
+        });
+
+        let compacted = compactor.compact_with_cbor_stats(&data).unwrap();
+        let cbor_len = compacted.to_cbor().unwrap().len();
+
+        assert_eq!(compacted.stats.comp, cbor_len);
+        assert!(cbor_len < compacted.stats.orig);
+    }
+
+    #[test]
+    fn test_constant_column_is_run_length_encoded_and_round_trips() {
+        let mut compactor = TrulyEfficientCompactor::new();
+        let data = json!({
+            "subresources": (0..20).map(|i| json!({
+                "url": format!("https://example.com/{}", i),
+                "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/foo-step",
+                "https://atomicdata.dev/properties/isA": "bar"
+            })).collect::<Vec<_>>()
+        });
+
+        let compacted = compactor.compact(&data).unwrap();
+        assert_eq!(compacted.s.encodings.get("t"), Some(&'r'));
+
+        let reconstructed = TrulyEfficientCompactor::reconstruct(&compacted).unwrap();
+        let resources = reconstructed["subresources"].as_array().unwrap();
+        assert_eq!(resources.len(), 20);
+        assert!(resources.iter().all(|r| r.get("https://atomicdata.dev/properties/isA").map(|v| v == "bar").unwrap_or(false)));
+    }
+
+    #[test]
+    fn test_monotonic_integer_column_is_delta_encoded_and_round_trips() {
+        let mut compactor = TrulyEfficientCompactor::new();
+        let data = json!({
+            "subresources": (0..20).map(|i| json!({
+                "url": format!("https://example.com/{}", i),
+                "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/foo-step",
+                "year": 2000 + i
+            })).collect::<Vec<_>>()
+        });
+
+        let compacted = compactor.compact(&data).unwrap();
+        assert_eq!(compacted.s.encodings.get("year"), Some(&'d'));
+
+        let reconstructed = TrulyEfficientCompactor::reconstruct(&compacted).unwrap();
+        let resources = reconstructed["subresources"].as_array().unwrap();
+        let years: Vec<i64> = resources
+            .iter()
+            .map(|r| r.get("year").and_then(|v| v.as_i64()).unwrap())
+            .collect();
+        assert_eq!(years, (2000..2020).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_heterogeneous_column_falls_back_to_raw_encoding() {
+        let mut compactor = TrulyEfficientCompactor::new();
+        let data = json!({
+            "subresources": (0..20).map(|i| json!({
+                "url": format!("https://example.com/{}", i),
+                "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/foo-step",
+                "rank": if i % 3 == 0 { i } else { 20 - i }
+            })).collect::<Vec<_>>()
+        });
+
+        let compacted = compactor.compact(&data).unwrap();
+        assert_eq!(compacted.s.encodings.get("rank"), None);
+
+        let reconstructed = TrulyEfficientCompactor::reconstruct(&compacted).unwrap();
+        let resources = reconstructed["subresources"].as_array().unwrap();
+        assert_eq!(resources.len(), 20);
+    }
 }
\ No newline at end of file
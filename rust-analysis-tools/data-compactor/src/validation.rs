@@ -0,0 +1,243 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Direction a `monotonic` field is expected to move across the ordered
+/// sequence of resources it appears in
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum MonotonicDirection {
+    Increasing,
+    Decreasing,
+}
+
+/// Validation constraints for one property, keyed by its full (unabbreviated)
+/// property name in a `ValidationSpec`
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct FieldValidationRule {
+    /// Lower bound for numeric fields; ignored unless `min_disabled` is false
+    pub min: Option<f64>,
+    /// Distinguishes "no lower bound" from a bound of exactly `0.0`
+    #[serde(default)]
+    pub min_disabled: bool,
+    /// Upper bound for numeric fields; ignored unless `max_disabled` is false
+    pub max: Option<f64>,
+    /// Distinguishes "no upper bound" from a bound of exactly `0.0`
+    #[serde(default)]
+    pub max_disabled: bool,
+    /// Pattern a string/URL field's value must match; unanchored, so a match
+    /// anywhere in the value passes unless the pattern uses `^`/`$`
+    pub regex: Option<String>,
+    /// Expected direction across the ordered sequence of resources sharing this field
+    pub monotonic: Option<MonotonicDirection>,
+}
+
+/// A validation spec maps a full property name to the constraints it must satisfy
+pub type ValidationSpec = HashMap<String, FieldValidationRule>;
+
+/// One constraint violation found during compaction
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ValidationError {
+    pub resource_url: String,
+    pub field: String,
+    pub reason: String,
+}
+
+/// Checks raw (pre-compaction) resources against a `ValidationSpec`
+pub struct FieldValidator;
+
+impl FieldValidator {
+    /// Validate `resources` (in their given order) against `spec`, returning
+    /// every constraint violation found. Resources are expected to carry a
+    /// `url` field; when absent, `"unknown"` is used in the error instead.
+    pub fn validate(resources: &[&Value], spec: &ValidationSpec) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+
+        for (field, rule) in spec {
+            let regex = rule.regex.as_ref().and_then(|pattern| Regex::new(pattern).ok());
+            let mut sequence: Vec<(String, f64)> = Vec::new();
+
+            for resource in resources {
+                let resource_url = Self::resource_url(resource);
+                let Some(value) = resource.get(field) else {
+                    continue;
+                };
+
+                if !rule.min_disabled {
+                    if let (Some(min), Some(n)) = (rule.min, value.as_f64()) {
+                        if n < min {
+                            errors.push(ValidationError {
+                                resource_url: resource_url.clone(),
+                                field: field.clone(),
+                                reason: format!("value {} is below minimum {}", n, min),
+                            });
+                        }
+                    }
+                }
+
+                if !rule.max_disabled {
+                    if let (Some(max), Some(n)) = (rule.max, value.as_f64()) {
+                        if n > max {
+                            errors.push(ValidationError {
+                                resource_url: resource_url.clone(),
+                                field: field.clone(),
+                                reason: format!("value {} is above maximum {}", n, max),
+                            });
+                        }
+                    }
+                }
+
+                if let (Some(re), Some(s)) = (&regex, value.as_str()) {
+                    if !re.is_match(s) {
+                        errors.push(ValidationError {
+                            resource_url: resource_url.clone(),
+                            field: field.clone(),
+                            reason: format!("value '{}' does not match pattern /{}/", s, re.as_str()),
+                        });
+                    }
+                }
+
+                if rule.monotonic.is_some() {
+                    if let Some(n) = value.as_f64() {
+                        sequence.push((resource_url, n));
+                    }
+                }
+            }
+
+            if let Some(direction) = &rule.monotonic {
+                errors.extend(Self::check_monotonic(field, direction, &sequence));
+            }
+        }
+
+        errors
+    }
+
+    fn check_monotonic(
+        field: &str,
+        direction: &MonotonicDirection,
+        sequence: &[(String, f64)],
+    ) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+
+        for window in sequence.windows(2) {
+            let (_, prev) = &window[0];
+            let (url, curr) = &window[1];
+
+            let violated = match direction {
+                MonotonicDirection::Increasing => curr < prev,
+                MonotonicDirection::Decreasing => curr > prev,
+            };
+
+            if violated {
+                errors.push(ValidationError {
+                    resource_url: url.clone(),
+                    field: field.to_string(),
+                    reason: format!(
+                        "value {} breaks expected {:?} order after {}",
+                        curr, direction, prev
+                    ),
+                });
+            }
+        }
+
+        errors
+    }
+
+    fn resource_url(resource: &Value) -> String {
+        resource
+            .get("url")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown")
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_min_max_violations_are_reported() {
+        let resources = vec![
+            json!({"url": "https://example.com/1", "year": 1800}),
+            json!({"url": "https://example.com/2", "year": 2100}),
+        ];
+        let refs: Vec<&Value> = resources.iter().collect();
+
+        let mut spec = ValidationSpec::new();
+        spec.insert(
+            "year".to_string(),
+            FieldValidationRule {
+                min: Some(1900.0),
+                max: Some(2030.0),
+                ..Default::default()
+            },
+        );
+
+        let errors = FieldValidator::validate(&refs, &spec);
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().any(|e| e.resource_url == "https://example.com/1"));
+        assert!(errors.iter().any(|e| e.resource_url == "https://example.com/2"));
+    }
+
+    #[test]
+    fn test_min_disabled_suppresses_zero_bound_check() {
+        let resources = vec![json!({"url": "https://example.com/1", "balance": -50})];
+        let refs: Vec<&Value> = resources.iter().collect();
+
+        let mut spec = ValidationSpec::new();
+        spec.insert(
+            "balance".to_string(),
+            FieldValidationRule {
+                min: Some(0.0),
+                min_disabled: true,
+                ..Default::default()
+            },
+        );
+
+        assert!(FieldValidator::validate(&refs, &spec).is_empty());
+    }
+
+    #[test]
+    fn test_regex_violation_is_reported() {
+        let resources = vec![json!({"url": "https://example.com/1", "code": "abc123"})];
+        let refs: Vec<&Value> = resources.iter().collect();
+
+        let mut spec = ValidationSpec::new();
+        spec.insert(
+            "code".to_string(),
+            FieldValidationRule {
+                regex: Some(r"^[0-9]+$".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let errors = FieldValidator::validate(&refs, &spec);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "code");
+    }
+
+    #[test]
+    fn test_monotonic_increasing_detects_regression() {
+        let resources = vec![
+            json!({"url": "https://example.com/1", "year": 2010}),
+            json!({"url": "https://example.com/2", "year": 2005}),
+            json!({"url": "https://example.com/3", "year": 2020}),
+        ];
+        let refs: Vec<&Value> = resources.iter().collect();
+
+        let mut spec = ValidationSpec::new();
+        spec.insert(
+            "year".to_string(),
+            FieldValidationRule {
+                monotonic: Some(MonotonicDirection::Increasing),
+                ..Default::default()
+            },
+        );
+
+        let errors = FieldValidator::validate(&refs, &spec);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].resource_url, "https://example.com/2");
+    }
+}
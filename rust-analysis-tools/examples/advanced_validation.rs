@@ -24,6 +24,7 @@ async fn main() -> Result<()> {
         aipack_config_path: ".aipack/custom_config.toml".to_string(),
         output_directory: "custom_results".to_string(),
         deep_model_validation: true,
+        ..ValidationConfig::default()
     };
     
     // Run validation with customized configuration
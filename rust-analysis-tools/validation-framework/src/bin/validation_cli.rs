@@ -1,137 +1,239 @@
 /// # Validation CLI Tool
-/// 
+///
 /// Command-line interface for running comprehensive evaluation pre-flight validation.
-/// This tool provides an easy way to validate all prerequisites before running 
+/// This tool provides an easy way to validate all prerequisites before running
 /// model evaluations.
 
 use anyhow::Result;
+use clap::{Parser, Subcommand};
 use evaluation_validator::{
-    ValidationConfig, 
-    validate_evaluation_prerequisites, 
+    ValidationConfig,
     validate_evaluation_prerequisites_with_config,
     init_logging_with_level
 };
-use std::env;
+use std::time::Duration;
 use serde_json;
 
+/// Evaluation pre-flight validation CLI
+#[derive(Parser)]
+#[command(name = "validation-cli", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Path to AIPACK configuration file
+    #[arg(short = 'c', long, global = true)]
+    config: Option<String>,
+
+    /// Output format: detailed, summary, json
+    #[arg(short = 'f', long, global = true, default_value = "detailed")]
+    format: String,
+
+    /// Enable verbose logging
+    #[arg(short, long, global = true)]
+    verbose: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the full validation suite once
+    Validate {
+        /// Exit 1 if any warnings are reported, even with no critical issues
+        #[arg(long)]
+        deny_warnings: bool,
+        /// Skip the real-inference model health-check stage
+        #[arg(long)]
+        no_health: bool,
+    },
+    /// Re-run validation on an interval until prerequisites turn green
+    Watch {
+        /// Seconds between validation runs
+        #[arg(long, default_value_t = 30)]
+        interval: u64,
+        /// Exit 1 if any warnings are reported, even with no critical issues
+        #[arg(long)]
+        deny_warnings: bool,
+        /// Skip the real-inference model health-check stage
+        #[arg(long)]
+        no_health: bool,
+    },
+    /// Print the remediation guidance for a single named check without running the suite
+    Explain {
+        /// Check name, e.g. "Ollama Service", "Disk Space", "Model Health"
+        check_name: String,
+    },
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize logging
     init_logging_with_level(log::LevelFilter::Info);
-    
-    let args: Vec<String> = env::args().collect();
-    
-    // Parse command line arguments
-    let (config_path, output_format, verbose) = parse_args(&args);
-    
-    // Set verbose logging if requested
-    if verbose {
+
+    let cli = Cli::parse();
+
+    if cli.verbose {
         init_logging_with_level(log::LevelFilter::Debug);
     }
-    
-    println!("🔍 Starting comprehensive evaluation pre-flight validation...\n");
-    
-    // Run validation with appropriate configuration
-    let result = if let Some(path) = config_path {
+
+    match cli.command {
+        Command::Explain { check_name } => {
+            run_explain(&check_name);
+            Ok(())
+        },
+        Command::Validate { deny_warnings, no_health } => {
+            let config = build_config(cli.config.clone(), no_health);
+            println!("🔍 Starting comprehensive evaluation pre-flight validation...\n");
+            let result = validate_evaluation_prerequisites_with_config(config).await?;
+            print_result(&result, &cli.format);
+            std::process::exit(exit_code_for(&result, deny_warnings));
+        },
+        Command::Watch { interval, deny_warnings, no_health } => {
+            let config = build_config(cli.config.clone(), no_health);
+            run_watch(config, &cli.format, interval, deny_warnings).await
+        },
+    }
+}
+
+/// Build a `ValidationConfig` from the shared `--config`/`--no-health` flags
+fn build_config(config_path: Option<String>, no_health: bool) -> ValidationConfig {
+    if let Some(path) = &config_path {
         println!("📋 Using custom configuration: {}", path);
-        let config = ValidationConfig {
-            aipack_config_path: path,
-            ..ValidationConfig::default()
-        };
-        validate_evaluation_prerequisites_with_config(config).await?
     } else {
         println!("📋 Using default configuration");
-        validate_evaluation_prerequisites().await?
-    };
-    
-    // Display results based on format
-    match output_format.as_str() {
-        "json" => {
-            println!("{}", serde_json::to_string_pretty(&result)?);
-        },
-        "summary" => {
-            print_summary_format(&result);
-        },
-        _ => {
-            print_detailed_format(&result);
+    }
+
+    ValidationConfig {
+        aipack_config_path: config_path.unwrap_or_else(|| ValidationConfig::default().aipack_config_path),
+        health_check_enabled: !no_health,
+        ..ValidationConfig::default()
+    }
+}
+
+/// Exit code logic: 2 for critical failures, 1 for warnings under
+/// `--deny-warnings`, 0 otherwise - lets CI distinguish hard failures from
+/// soft ones.
+fn exit_code_for(result: &evaluation_validator::ValidationResult, deny_warnings: bool) -> i32 {
+    if !result.is_valid {
+        2
+    } else if deny_warnings && !result.warnings.is_empty() {
+        1
+    } else {
+        0
+    }
+}
+
+/// Re-run validation on `interval` seconds, only reprinting the report when
+/// `is_valid` or the set of `critical_issues` changes - useful while a user
+/// is pulling models or freeing disk space and waiting for prerequisites to
+/// turn green.
+async fn run_watch(config: ValidationConfig, format: &str, interval: u64, deny_warnings: bool) -> Result<()> {
+    println!("👀 Watching validation status every {}s (Ctrl+C to stop)\n", interval);
+
+    let mut last_state: Option<(bool, Vec<String>)> = None;
+
+    loop {
+        let result = validate_evaluation_prerequisites_with_config(config.clone()).await?;
+        let state = (result.is_valid, result.critical_issues.clone());
+
+        if last_state.as_ref() != Some(&state) {
+            println!("── status changed at {} ──", result.timestamp);
+            print_result(&result, format);
+            last_state = Some(state);
         }
+
+        if result.is_valid {
+            std::process::exit(exit_code_for(&result, deny_warnings));
+        }
+
+        tokio::time::sleep(Duration::from_secs(interval)).await;
     }
-    
-    // Exit with appropriate code
-    std::process::exit(if result.is_valid { 0 } else { 1 });
 }
 
-/// Parse command line arguments
-fn parse_args(args: &[String]) -> (Option<String>, String, bool) {
-    let mut config_path = None;
-    let mut output_format = "detailed".to_string();
-    let mut verbose = false;
-    
-    let mut i = 1;
-    while i < args.len() {
-        match args[i].as_str() {
-            "--config" | "-c" => {
-                if i + 1 < args.len() {
-                    config_path = Some(args[i + 1].clone());
-                    i += 2;
-                } else {
-                    eprintln!("Error: --config requires a path argument");
-                    std::process::exit(1);
-                }
-            },
-            "--format" | "-f" => {
-                if i + 1 < args.len() {
-                    output_format = args[i + 1].clone();
-                    i += 2;
-                } else {
-                    eprintln!("Error: --format requires an argument (detailed|summary|json)");
-                    std::process::exit(1);
-                }
-            },
-            "--verbose" | "-v" => {
-                verbose = true;
-                i += 1;
-            },
-            "--help" | "-h" => {
-                print_help();
-                std::process::exit(0);
-            },
-            _ => {
-                eprintln!("Error: Unknown argument: {}", args[i]);
-                print_help();
-                std::process::exit(1);
-            }
+/// Print the remediation `action`/`description` for a single named check,
+/// mirroring the guidance `ValidationCoordinator::generate_recommendations`
+/// would produce for that check if it failed
+fn run_explain(check_name: &str) {
+    match explain_check(check_name) {
+        Some(explanation) => {
+            println!("🔎 {}", check_name);
+            println!("  Priority:    {}", explanation.priority);
+            println!("  Description: {}", explanation.description);
+            println!("  Action:      {}", explanation.action);
+        },
+        None => {
+            eprintln!("Error: Unknown check '{}'", check_name);
+            eprintln!();
+            eprintln!("Known checks: Ollama Service, Model Availability, Disk Space, AIPACK Config, System Resources, Model Health");
+            std::process::exit(1);
         }
     }
-    
-    (config_path, output_format, verbose)
 }
 
-/// Print help information
-fn print_help() {
-    println!("Evaluation Pre-flight Validation CLI");
-    println!();
-    println!("USAGE:");
-    println!("    validation-cli [OPTIONS]");
-    println!();
-    println!("OPTIONS:");
-    println!("    -c, --config <PATH>     Path to AIPACK configuration file");
-    println!("    -f, --format <FORMAT>   Output format: detailed, summary, json [default: detailed]");
-    println!("    -v, --verbose           Enable verbose logging");
-    println!("    -h, --help              Print this help information");
-    println!();
-    println!("EXAMPLES:");
-    println!("    validation-cli                              # Run with default settings");
-    println!("    validation-cli --config .aipack/config.toml # Use custom config");
-    println!("    validation-cli --format json                # Output as JSON");
-    println!("    validation-cli --verbose                    # Enable debug logging");
+/// Static remediation guidance for a single named check
+struct CheckExplanation {
+    priority: &'static str,
+    description: &'static str,
+    action: &'static str,
+}
+
+fn explain_check(check_name: &str) -> Option<CheckExplanation> {
+    let normalized = check_name.to_lowercase().replace(['-', '_'], " ");
+
+    match normalized.as_str() {
+        "ollama service" | "ollama" => Some(CheckExplanation {
+            priority: "High",
+            description: "Ollama service is not responding properly",
+            action: "Start Ollama service with 'ollama serve' and verify it's accessible at the configured URL",
+        }),
+        "model availability" | "models" => Some(CheckExplanation {
+            priority: "High",
+            description: "One or more required models are not installed",
+            action: "Run 'ollama pull <model>' for each missing model, or enable auto-pull via OllamaValidator::validate_required_models_with_pull",
+        }),
+        "disk space" | "disk" => Some(CheckExplanation {
+            priority: "High",
+            description: "Insufficient disk space for evaluation outputs",
+            action: "Free up disk space or change output_directory in ValidationConfig",
+        }),
+        "aipack config" | "aipack" => Some(CheckExplanation {
+            priority: "High",
+            description: "AIPACK configuration file has issues",
+            action: "Review and fix AIPACK configuration file syntax and model references",
+        }),
+        "system resources" | "system" => Some(CheckExplanation {
+            priority: "Medium",
+            description: "System resources may be insufficient for optimal performance",
+            action: "Free up memory/CPU headroom before running a full evaluation, or lower parallelism",
+        }),
+        "model health" | "health" => Some(CheckExplanation {
+            priority: "High",
+            description: "Model(s) present but not responding to a real-inference health-check prompt",
+            action: "Check 'ollama ps'/'ollama logs' for the affected model(s), or re-pull if the weights are corrupted",
+        }),
+        _ => None,
+    }
+}
+
+/// Display results based on the requested output format
+fn print_result(result: &evaluation_validator::ValidationResult, format: &str) {
+    match format {
+        "json" => {
+            println!("{}", serde_json::to_string_pretty(result).unwrap_or_default());
+        },
+        "summary" => {
+            print_summary_format(result);
+        },
+        _ => {
+            print_detailed_format(result);
+        }
+    }
 }
 
 /// Print detailed validation results
 fn print_detailed_format(result: &evaluation_validator::ValidationResult) {
     println!("📊 VALIDATION RESULTS");
     println!("════════════════════════════════════════════════════════════════");
-    
+
     // Overall status
     if result.is_valid {
         println!("✅ OVERALL STATUS: PASSED");
@@ -139,7 +241,7 @@ fn print_detailed_format(result: &evaluation_validator::ValidationResult) {
         println!("❌ OVERALL STATUS: FAILED");
     }
     println!();
-    
+
     // Summary metrics
     println!("📈 SUMMARY:");
     println!("  Available Models: {}", result.summary.available_models);
@@ -149,7 +251,7 @@ fn print_detailed_format(result: &evaluation_validator::ValidationResult) {
     println!("  Ollama Response Time: {}ms", result.summary.ollama_response_time_ms);
     println!("  AIPACK Config Valid: {}", result.summary.aipack_config_valid);
     println!();
-    
+
     // Individual check results
     println!("🔍 DETAILED CHECKS:");
     print_check_result("Ollama Service", &result.checks.ollama_service);
@@ -157,8 +259,9 @@ fn print_detailed_format(result: &evaluation_validator::ValidationResult) {
     print_check_result("Disk Space", &result.checks.disk_space);
     print_check_result("AIPACK Config", &result.checks.aipack_config);
     print_check_result("System Resources", &result.checks.system_resources);
+    print_check_result("Model Health", &result.checks.model_health);
     println!();
-    
+
     // Critical issues
     if !result.critical_issues.is_empty() {
         println!("🚨 CRITICAL ISSUES:");
@@ -167,7 +270,7 @@ fn print_detailed_format(result: &evaluation_validator::ValidationResult) {
         }
         println!();
     }
-    
+
     // Warnings
     if !result.warnings.is_empty() {
         println!("⚠️  WARNINGS:");
@@ -176,26 +279,26 @@ fn print_detailed_format(result: &evaluation_validator::ValidationResult) {
         }
         println!();
     }
-    
+
     // Recommendations
     if !result.recommendations.is_empty() {
         println!("💡 RECOMMENDATIONS:");
         for rec in &result.recommendations {
-            println!("  {} [{}]: {}", 
+            println!("  {} [{}]: {}",
                 match rec.priority.as_str() {
                     "High" => "🔴",
-                    "Medium" => "🟡", 
+                    "Medium" => "🟡",
                     "Low" => "🟢",
                     _ => "📋"
                 },
-                rec.category, 
+                rec.category,
                 rec.description
             );
             println!("     Action: {}", rec.action);
         }
         println!();
     }
-    
+
     println!("Validation completed at: {}", result.timestamp);
 }
 
@@ -203,20 +306,20 @@ fn print_detailed_format(result: &evaluation_validator::ValidationResult) {
 fn print_summary_format(result: &evaluation_validator::ValidationResult) {
     println!("VALIDATION SUMMARY");
     println!("==================");
-    
+
     let status = if result.is_valid { "PASSED ✅" } else { "FAILED ❌" };
     println!("Status: {}", status);
     println!("Models Available: {}", result.summary.available_models);
     println!("Disk Space: {:.1}GB available", result.summary.available_disk_space_gb);
     println!("Response Time: {}ms", result.summary.ollama_response_time_ms);
-    
+
     if !result.critical_issues.is_empty() {
         println!("\nCritical Issues: {}", result.critical_issues.len());
         for issue in &result.critical_issues {
             println!("  - {}", issue);
         }
     }
-    
+
     if !result.recommendations.is_empty() {
         println!("\nRecommendations: {}", result.recommendations.len());
         for rec in &result.recommendations {
@@ -231,7 +334,7 @@ fn print_summary_format(result: &evaluation_validator::ValidationResult) {
 fn print_check_result(name: &str, check: &evaluation_validator::validation::CheckResult) {
     let status = if check.passed { "✅" } else { "❌" };
     println!("  {} {}: {} ({}ms)", status, name, check.message, check.duration_ms);
-    
+
     if !check.metadata.is_empty() && log::log_enabled!(log::Level::Debug) {
         for (key, value) in &check.metadata {
             println!("     {}: {}", key, value);
@@ -1,6 +1,10 @@
 use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 use regex::Regex;
-use anyhow::{Result, Context};
+use anyhow::{Result, Context, bail};
 use serde::{Deserialize, Serialize};
 
 /// Comprehensive evaluation metrics extracted from model output
@@ -19,6 +23,19 @@ pub struct EvaluationMetrics {
     pub timestamp: String,
     /// Helper field for tracking which prompt was used (for caching)
     pub prompt_used: String,
+    /// Score derived from running `objective::run_suite` against the generated code,
+    /// rather than trusting the model's self-reported score
+    pub objective_score: Option<u8>,
+    /// Number of test cases that passed when objective scoring was performed
+    pub cases_passed: Option<u32>,
+    /// Total number of test cases in the suite used for objective scoring
+    pub cases_total: Option<u32>,
+    /// Per-criterion rubric scores (e.g. "Correctness" -> 8), when the model
+    /// reported a multi-criteria breakdown instead of a single overall score
+    pub criteria_scores: HashMap<String, u8>,
+    /// Signed change in `score` vs. the most recent prior run for the same
+    /// (model_name, prompt_used) pair, set by `EvaluationHistory::record_and_compare`
+    pub score_delta: Option<i16>,
 }
 
 impl EvaluationMetrics {
@@ -36,6 +53,11 @@ impl EvaluationMetrics {
             status: EvaluationStatus::Success,
             timestamp: chrono::Utc::now().to_rfc3339(),
             prompt_used: prompt,
+            objective_score: None,
+            cases_passed: None,
+            cases_total: None,
+            criteria_scores: HashMap::new(),
+            score_delta: None,
         }
     }
 }
@@ -46,6 +68,27 @@ pub enum EvaluationStatus {
     Failed,
     Timeout,
     ParseError,
+    /// Score dropped by at least the configured threshold vs. the prior run
+    /// for the same (model, prompt) pair
+    Regressed,
+    /// Score rose by at least the configured threshold vs. the prior run
+    /// for the same (model, prompt) pair
+    Improved,
+}
+
+impl EvaluationStatus {
+    /// Stable lowercase string form for machine-readable exports (JSON/CSV),
+    /// independent of the enum's derived Debug/Serialize representation
+    pub fn as_export_str(&self) -> &'static str {
+        match self {
+            EvaluationStatus::Success => "success",
+            EvaluationStatus::Failed => "failed",
+            EvaluationStatus::Timeout => "timeout",
+            EvaluationStatus::ParseError => "parse_error",
+            EvaluationStatus::Regressed => "regressed",
+            EvaluationStatus::Improved => "improved",
+        }
+    }
 }
 
 /// Enhanced parser for evaluation results with multiple fallback strategies
@@ -55,6 +98,7 @@ pub struct EvaluationParser {
     issue_patterns: Vec<Regex>,
     token_patterns: Vec<Regex>,
     duration_patterns: Vec<Regex>,
+    criteria_pattern: Regex,
 }
 
 impl Default for EvaluationParser {
@@ -111,12 +155,16 @@ impl EvaluationParser {
             Regex::new(r"\*\*Duration:\*\*\s*(\d+)s").unwrap(),
         ];
 
+        // Per-criterion rubric lines, e.g. **Correctness: 8/10**
+        let criteria_pattern = Regex::new(r"\*\*([A-Za-z][A-Za-z ]*?):\s*(\d{1,2})/10\*\*").unwrap();
+
         Self {
             score_patterns,
             strength_patterns,
             issue_patterns,
             token_patterns,
             duration_patterns,
+            criteria_pattern,
         }
     }
 
@@ -140,11 +188,22 @@ impl EvaluationParser {
             status: EvaluationStatus::Success,
             timestamp: chrono::Utc::now().to_rfc3339(),
             prompt_used: prompt.to_string(),
+            objective_score: None,
+            cases_passed: None,
+            cases_total: None,
+            criteria_scores: HashMap::new(),
+            score_delta: None,
         };
 
         // Extract score with multiple fallback patterns
         metrics.score = self.extract_score(content);
 
+        // Extract per-criterion rubric scores, falling back to the single score above when absent
+        metrics.criteria_scores = self.extract_criteria_scores(content);
+        if !metrics.criteria_scores.is_empty() {
+            metrics.score = Some(Self::weighted_mean_score(&metrics.criteria_scores, None));
+        }
+
         // Extract duration
         metrics.duration_seconds = self.extract_duration(content);
 
@@ -182,6 +241,51 @@ impl EvaluationParser {
         None
     }
 
+    /// Extract repeated per-criterion rubric lines (e.g. **Correctness: 8/10**)
+    fn extract_criteria_scores(&self, content: &str) -> HashMap<String, u8> {
+        let mut scores = HashMap::new();
+        for captures in self.criteria_pattern.captures_iter(content) {
+            let name = captures[1].trim().to_string();
+            // "Score" alone is the overall score, not a rubric criterion
+            if name.eq_ignore_ascii_case("score") {
+                continue;
+            }
+            if let Ok(score) = captures[2].parse::<u8>() {
+                if score <= 10 {
+                    scores.insert(name, score);
+                }
+            }
+        }
+        scores
+    }
+
+    /// Aggregate per-criterion scores into a single 0..=10 score, weighting each
+    /// criterion by `weights` (defaulting to 1.0 for any criterion not listed)
+    fn weighted_mean_score(criteria_scores: &HashMap<String, u8>, weights: Option<&HashMap<String, f64>>) -> u8 {
+        if criteria_scores.is_empty() {
+            return 0;
+        }
+        let (weighted_sum, weight_total) = criteria_scores.iter().fold((0.0, 0.0), |(sum, total), (name, score)| {
+            let weight = weights.and_then(|w| w.get(name)).copied().unwrap_or(1.0);
+            (sum + weight * (*score as f64), total + weight)
+        });
+        if weight_total <= 0.0 {
+            return 0;
+        }
+        (weighted_sum / weight_total).round().clamp(0.0, 10.0) as u8
+    }
+
+    /// Recompute the aggregate `score` as a weighted mean of `criteria_scores`
+    ///
+    /// No-op when the parsed content had no per-criterion breakdown. Any criterion
+    /// missing from `weights` defaults to a weight of 1.0.
+    pub fn apply_criteria_weights(&self, metrics: &mut EvaluationMetrics, weights: &HashMap<String, f64>) {
+        if metrics.criteria_scores.is_empty() {
+            return;
+        }
+        metrics.score = Some(Self::weighted_mean_score(&metrics.criteria_scores, Some(weights)));
+    }
+
     /// Extract duration in seconds with multiple format support
     fn extract_duration(&self, content: &str) -> Option<u64> {
         for pattern in &self.duration_patterns {
@@ -372,6 +476,265 @@ impl EvaluationParser {
             "Unknown".to_string()
         }
     }
+
+    /// Apply an objective test-suite scoring result to `metrics`, overriding the
+    /// self-reported `score` so downstream charts reflect measured behavior
+    pub fn apply_objective_score(&self, metrics: &mut EvaluationMetrics, result: ObjectiveScoreResult) {
+        metrics.cases_passed = Some(result.cases_passed);
+        metrics.cases_total = Some(result.cases_total);
+        metrics.objective_score = Some(result.objective_score);
+        metrics.score = Some(result.objective_score);
+        if matches!(result.status, EvaluationStatus::Timeout) {
+            metrics.status = EvaluationStatus::Timeout;
+        }
+    }
+}
+
+/// A single test case for objective scoring: stdin input and expected stdout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestCase {
+    pub input: String,
+    pub expected: String,
+}
+
+/// How a test case's actual stdout is compared against its expected stdout
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum MatchMode {
+    /// Byte-for-byte match
+    Exact,
+    /// Split on ASCII whitespace and compare tokens, ignoring trailing whitespace/newlines
+    WhitespaceNormalized,
+    /// Compare corresponding tokens as floats, accepting `|a-e| <= abs_tol || |a-e| <= rel_tol*|e|`
+    Numeric { abs_tol: f64, rel_tol: f64 },
+}
+
+/// A batch of test cases plus how to judge them and how long to wait per case
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestSuite {
+    pub cases: Vec<TestCase>,
+    pub match_mode: MatchMode,
+    pub timeout: Duration,
+}
+
+/// Outcome of running a `TestSuite` against model-generated code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectiveScoreResult {
+    pub status: EvaluationStatus,
+    pub objective_score: u8,
+    pub cases_passed: u32,
+    pub cases_total: u32,
+}
+
+/// Result of executing a single test case's process
+enum CaseOutcome {
+    Passed,
+    Failed,
+    TimedOut,
+}
+
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Compiles/runs model-generated code against a batch test suite and derives a
+/// score from the pass ratio, rather than trusting the model's self-reported score
+pub struct ObjectiveScorer;
+
+impl ObjectiveScorer {
+    /// Run `suite` against `code` by invoking `command` with the code written to a
+    /// temp file whose path is appended as the final argument (e.g. `["python3"]`
+    /// becomes `python3 <temp-file>`). A non-zero exit or a timeout marks the case failed.
+    pub fn run_suite(code: &str, command: &[String], suite: &TestSuite) -> Result<ObjectiveScoreResult> {
+        if suite.cases.is_empty() {
+            bail!("Test suite has no cases");
+        }
+        let (program, fixed_args) = command.split_first()
+            .context("Command must name at least an executable")?;
+
+        let code_path = Self::write_temp_file(code)?;
+
+        let mut passed = 0u32;
+        let mut timed_out = false;
+
+        for case in &suite.cases {
+            match Self::run_case(program, fixed_args, &code_path, case, &suite.match_mode, suite.timeout)? {
+                CaseOutcome::Passed => passed += 1,
+                CaseOutcome::Failed => {},
+                CaseOutcome::TimedOut => timed_out = true,
+            }
+        }
+
+        let _ = std::fs::remove_file(&code_path);
+
+        let total = suite.cases.len() as u32;
+        let objective_score = (10.0 * passed as f64 / total as f64).round() as u8;
+        let status = if timed_out { EvaluationStatus::Timeout } else { EvaluationStatus::Success };
+
+        Ok(ObjectiveScoreResult { status, objective_score, cases_passed: passed, cases_total: total })
+    }
+
+    fn write_temp_file(code: &str) -> Result<std::path::PathBuf> {
+        let id = TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!("objective_score_{}_{}.tmp", std::process::id(), id));
+        std::fs::write(&path, code).context("Failed to write generated code to temp file")?;
+        Ok(path)
+    }
+
+    fn run_case(
+        program: &str,
+        fixed_args: &[String],
+        code_path: &std::path::Path,
+        case: &TestCase,
+        match_mode: &MatchMode,
+        timeout: Duration,
+    ) -> Result<CaseOutcome> {
+        let mut child = Command::new(program)
+            .args(fixed_args)
+            .arg(code_path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .context("Failed to spawn test case process")?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(case.input.as_bytes());
+        }
+
+        let start = Instant::now();
+        let exit_status = loop {
+            if let Some(status) = child.try_wait().context("Failed to poll test case process")? {
+                break status;
+            }
+            if start.elapsed() >= timeout {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Ok(CaseOutcome::TimedOut);
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        };
+
+        let output = child.wait_with_output().context("Failed to collect test case output")?;
+        if !exit_status.success() {
+            return Ok(CaseOutcome::Failed);
+        }
+
+        let actual = String::from_utf8_lossy(&output.stdout);
+        if Self::outputs_match(&actual, &case.expected, match_mode) {
+            Ok(CaseOutcome::Passed)
+        } else {
+            Ok(CaseOutcome::Failed)
+        }
+    }
+
+    fn outputs_match(actual: &str, expected: &str, match_mode: &MatchMode) -> bool {
+        match match_mode {
+            MatchMode::Exact => actual == expected,
+            MatchMode::WhitespaceNormalized => {
+                let actual_tokens: Vec<&str> = actual.split_ascii_whitespace().collect();
+                let expected_tokens: Vec<&str> = expected.split_ascii_whitespace().collect();
+                actual_tokens == expected_tokens
+            },
+            MatchMode::Numeric { abs_tol, rel_tol } => {
+                let actual_tokens: Vec<&str> = actual.split_ascii_whitespace().collect();
+                let expected_tokens: Vec<&str> = expected.split_ascii_whitespace().collect();
+                if actual_tokens.len() != expected_tokens.len() {
+                    return false;
+                }
+                actual_tokens.iter().zip(expected_tokens.iter()).all(|(a, e)| {
+                    match (a.parse::<f64>(), e.parse::<f64>()) {
+                        (Ok(a), Ok(e)) if a.is_finite() && e.is_finite() => {
+                            (a - e).abs() <= *abs_tol || (a - e).abs() <= *rel_tol * e.abs()
+                        },
+                        _ => a == e,
+                    }
+                })
+            }
+        }
+    }
+}
+
+/// Aggregated score statistics across repeated runs of the same (model, prompt)
+/// pair, since a single LLM-graded run is noisy enough to be misleading on its own
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScoreAggregate {
+    pub n: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub min: u8,
+    pub max: u8,
+    /// Sample standard deviation (Bessel-corrected); `None` when `n < 2`
+    pub stddev: Option<f64>,
+    /// 95% normal-approximation confidence interval for the mean
+    /// (`mean ± 1.96 * stddev / sqrt(n)`); `None` when `n < 2`
+    pub confidence_interval_95: Option<(f64, f64)>,
+}
+
+impl ScoreAggregate {
+    /// Render as `mean ± margin (n=N)`, or just `mean (n=1)` when there's no interval
+    pub fn format_mean_ci(&self) -> String {
+        match self.confidence_interval_95 {
+            Some((lo, hi)) => format!("{:.1} ± {:.1} (n={})", self.mean, (hi - lo) / 2.0, self.n),
+            None => format!("{:.1} (n={})", self.mean, self.n),
+        }
+    }
+}
+
+/// Aggregates repeated-sampling runs of the same (model, prompt) pair into
+/// summary statistics, so callers can tell whether a score difference between
+/// two models is real or within run-to-run grading noise
+pub struct RunAggregator;
+
+impl RunAggregator {
+    /// Aggregate the `score` field across `runs`. Returns `None` if no run in
+    /// the slice has a score. Standard deviation and the confidence interval
+    /// are `None` when fewer than 2 runs have a score.
+    pub fn aggregate(runs: &[EvaluationMetrics]) -> Option<ScoreAggregate> {
+        let mut scores: Vec<u8> = runs.iter().filter_map(|m| m.score).collect();
+        if scores.is_empty() {
+            return None;
+        }
+        scores.sort_unstable();
+
+        let n = scores.len();
+        let min = scores[0];
+        let max = scores[n - 1];
+        let mean = scores.iter().map(|&s| s as f64).sum::<f64>() / n as f64;
+        let median = if n % 2 == 0 {
+            (scores[n / 2 - 1] as f64 + scores[n / 2] as f64) / 2.0
+        } else {
+            scores[n / 2] as f64
+        };
+
+        let (stddev, confidence_interval_95) = if n >= 2 {
+            let variance = scores.iter()
+                .map(|&s| (s as f64 - mean).powi(2))
+                .sum::<f64>() / (n - 1) as f64;
+            let stddev = variance.sqrt();
+            let margin = 1.96 * stddev / (n as f64).sqrt();
+            (Some(stddev), Some((mean - margin, mean + margin)))
+        } else {
+            (None, None)
+        };
+
+        Some(ScoreAggregate { n, mean, median, min, max, stddev, confidence_interval_95 })
+    }
+
+    /// Group `metrics` by `model_name`, preserving first-seen order
+    pub fn group_by_model(metrics: &[EvaluationMetrics]) -> Vec<(String, Vec<&EvaluationMetrics>)> {
+        let mut order: Vec<String> = Vec::new();
+        let mut groups: HashMap<String, Vec<&EvaluationMetrics>> = HashMap::new();
+        for metric in metrics {
+            if !groups.contains_key(&metric.model_name) {
+                order.push(metric.model_name.clone());
+            }
+            groups.entry(metric.model_name.clone()).or_default().push(metric);
+        }
+        order.into_iter()
+            .map(|name| {
+                let runs = groups.remove(&name).unwrap_or_default();
+                (name, runs)
+            })
+            .collect()
+    }
 }
 
 /// Generate terminal-friendly visualization charts
@@ -404,16 +767,29 @@ impl VisualizationGenerator {
     /// Generate comparison table with visual elements
     pub fn generate_comparison_table(metrics: &[EvaluationMetrics]) -> String {
         let mut table = String::new();
-        table.push_str("| Model | Visual Score | Score | Duration | Tokens (P/C) | Size | Status |\n");
-        table.push_str("|-------|-------------|-------|----------|--------------|------|--------|\n");
-        
+        let criteria_names = Self::collect_criteria_names(metrics);
+
+        let criteria_header: String = criteria_names.iter().map(|name| format!(" {} |", name)).collect();
+        let criteria_separator: String = criteria_names.iter().map(|_| "------|").collect();
+
+        table.push_str(&format!("| Model | Visual Score | Score | Mean ± CI95 |{} Duration | Tokens (P/C) | Size | Status |\n", criteria_header));
+        table.push_str(&format!("|-------|-------------|-------|-------------|{} ----------|--------------|------|--------|\n", criteria_separator));
+
+        let mean_ci_by_model: HashMap<String, String> = RunAggregator::group_by_model(metrics)
+            .into_iter()
+            .filter_map(|(name, runs)| {
+                let owned: Vec<EvaluationMetrics> = runs.into_iter().cloned().collect();
+                RunAggregator::aggregate(&owned).map(|agg| (name, agg.format_mean_ci()))
+            })
+            .collect();
+
         for metric in metrics {
             let score = metric.score.unwrap_or(0);
             let visual_bar = "█".repeat(score as usize) + &"░".repeat((10 - score) as usize);
             let duration = metric.duration_seconds
                 .map(|d| format!("{}s", d))
                 .unwrap_or_else(|| "N/A".to_string());
-            let tokens = format!("{}/{}", 
+            let tokens = format!("{}/{}",
                 metric.prompt_tokens.unwrap_or(0),
                 metric.completion_tokens.unwrap_or(0)
             );
@@ -422,23 +798,82 @@ impl VisualizationGenerator {
                 EvaluationStatus::Failed => "❌",
                 EvaluationStatus::Timeout => "⏰",
                 EvaluationStatus::ParseError => "⚠️",
+                EvaluationStatus::Regressed => "📉",
+                EvaluationStatus::Improved => "📈",
             };
-            
+            let criteria_cells: String = criteria_names.iter()
+                .map(|name| match metric.criteria_scores.get(name) {
+                    Some(score) => format!(" {}/10 |", score),
+                    None => " - |".to_string(),
+                })
+                .collect();
+            let mean_ci = mean_ci_by_model.get(&metric.model_name)
+                .cloned()
+                .unwrap_or_else(|| "N/A".to_string());
+
             table.push_str(&format!(
-                "| {} | {} | {}/10 | {} | {} | {} | {} |\n",
+                "| {} | {} | {}/10 | {} |{} {} | {} | {} | {} |\n",
                 metric.model_name,
                 visual_bar,
                 score,
+                mean_ci,
+                criteria_cells,
                 duration,
                 tokens,
                 metric.model_size,
                 status
             ));
         }
-        
+
         table
     }
 
+    /// Render a per-model stacked ASCII bar, one segment per criterion, so
+    /// reviewers can see why a model's score landed where it did
+    pub fn generate_criteria_breakdown(metrics: &[EvaluationMetrics]) -> String {
+        let mut chart = String::new();
+        chart.push_str("Criteria Breakdown (segment length = criterion score out of 10)\n\n");
+
+        let criteria_names = Self::collect_criteria_names(metrics);
+        if criteria_names.is_empty() {
+            chart.push_str("(no per-criterion scores reported)\n");
+            return chart;
+        }
+
+        const SEGMENT_GLYPHS: [char; 6] = ['█', '▓', '▒', '▞', '▚', '▤'];
+        for (i, name) in criteria_names.iter().enumerate() {
+            let glyph = SEGMENT_GLYPHS[i % SEGMENT_GLYPHS.len()];
+            chart.push_str(&format!("  {} {}\n", glyph, name));
+        }
+        chart.push('\n');
+
+        for metric in metrics {
+            if metric.criteria_scores.is_empty() {
+                continue;
+            }
+            let mut bar = String::new();
+            for (i, name) in criteria_names.iter().enumerate() {
+                let glyph = SEGMENT_GLYPHS[i % SEGMENT_GLYPHS.len()];
+                let score = metric.criteria_scores.get(name).copied().unwrap_or(0);
+                bar.push_str(&glyph.to_string().repeat(score as usize));
+            }
+            chart.push_str(&format!("{:<20} {}\n", metric.model_name, bar));
+        }
+
+        chart
+    }
+
+    /// Stable, sorted union of all criterion names reported across `metrics`
+    fn collect_criteria_names(metrics: &[EvaluationMetrics]) -> Vec<String> {
+        let mut names: Vec<String> = metrics.iter()
+            .flat_map(|m| m.criteria_scores.keys().cloned())
+            .collect::<std::collections::HashSet<_>>()
+            .into_iter()
+            .collect();
+        names.sort();
+        names
+    }
+
     /// Generate performance statistics
     pub fn generate_statistics(metrics: &[EvaluationMetrics]) -> String {
         let mut stats = String::new();
@@ -470,7 +905,35 @@ impl VisualizationGenerator {
             stats.push_str(&format!("- **Best Performer:** {} ({}/10)\n", best_model, best_score));
             stats.push_str(&format!("- **Needs Improvement:** {} ({}/10)\n", worst_model, worst_score));
         }
-        
+
+        let repeated_samples: Vec<(String, ScoreAggregate)> = RunAggregator::group_by_model(metrics)
+            .into_iter()
+            .filter_map(|(name, runs)| {
+                let owned: Vec<EvaluationMetrics> = runs.into_iter().cloned().collect();
+                RunAggregator::aggregate(&owned)
+                    .filter(|agg| agg.n >= 2)
+                    .map(|agg| (name, agg))
+            })
+            .collect();
+
+        if !repeated_samples.is_empty() {
+            stats.push_str("- **Repeated-Sampling Confidence:**\n");
+            for (model_name, agg) in &repeated_samples {
+                stats.push_str(&format!(
+                    "  - {}: mean {:.2}, median {:.1}, min {}, max {}, stddev {:.2}, 95% CI [{:.2}, {:.2}] (n={})\n",
+                    model_name,
+                    agg.mean,
+                    agg.median,
+                    agg.min,
+                    agg.max,
+                    agg.stddev.unwrap_or(0.0),
+                    agg.confidence_interval_95.map(|(lo, _)| lo).unwrap_or(agg.mean),
+                    agg.confidence_interval_95.map(|(_, hi)| hi).unwrap_or(agg.mean),
+                    agg.n,
+                ));
+            }
+        }
+
         let successful_evals = metrics
             .iter()
             .filter(|m| matches!(m.status, EvaluationStatus::Success))
@@ -478,11 +941,234 @@ impl VisualizationGenerator {
         
         stats.push_str(&format!("- **Successful Evaluations:** {}/{}\n", successful_evals, metrics.len()));
         stats.push_str(&format!("- **Total Models Evaluated:** {}\n", metrics.len()));
-        
+
+        let regressions: Vec<&EvaluationMetrics> = metrics
+            .iter()
+            .filter(|m| matches!(m.status, EvaluationStatus::Regressed))
+            .collect();
+
+        if !regressions.is_empty() {
+            stats.push_str("- **Regressions:**\n");
+            for metric in &regressions {
+                let drop = metric.score_delta.map(|d| d.unsigned_abs()).unwrap_or(0);
+                stats.push_str(&format!("  - {} dropped by {} point(s)\n", metric.model_name, drop));
+            }
+        }
+
         stats
     }
 }
 
+/// A single historical run, as persisted to the JSON-lines history file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HistoryRecord {
+    model_name: String,
+    prompt_used: String,
+    score: Option<u8>,
+    timestamp: String,
+}
+
+/// Persists evaluation runs to a JSON-lines file and compares new runs against
+/// the most recent prior run for the same (model_name, prompt_used) key, so a
+/// CI pipeline can fail the build when a model/prompt combination regresses
+pub struct EvaluationHistory {
+    path: std::path::PathBuf,
+    regression_threshold: u8,
+}
+
+impl EvaluationHistory {
+    /// Open (or create on first write) a history file at `path`. A score swing
+    /// of at least `regression_threshold` points vs. the prior run for the same
+    /// key is flagged as a regression or improvement.
+    pub fn new(path: impl Into<std::path::PathBuf>, regression_threshold: u8) -> Self {
+        Self { path: path.into(), regression_threshold }
+    }
+
+    /// Compare `metrics` against the most recent prior record for the same
+    /// (model_name, prompt_used) key, setting `metrics.status` to `Regressed`
+    /// or `Improved` and `metrics.score_delta` to the signed change when the
+    /// threshold is met, then append `metrics` to the history file.
+    ///
+    /// Returns the signed delta, or `None` if there was no prior record or
+    /// either score is missing.
+    pub fn record_and_compare(&self, metrics: &mut EvaluationMetrics) -> Result<Option<i16>> {
+        let previous = self.most_recent_prior(&metrics.model_name, &metrics.prompt_used)?;
+
+        let delta = match (previous.and_then(|p| p.score), metrics.score) {
+            (Some(prev), Some(curr)) => {
+                let delta = curr as i16 - prev as i16;
+                if delta <= -(self.regression_threshold as i16) {
+                    metrics.status = EvaluationStatus::Regressed;
+                } else if delta >= self.regression_threshold as i16 {
+                    metrics.status = EvaluationStatus::Improved;
+                }
+                metrics.score_delta = Some(delta);
+                Some(delta)
+            },
+            _ => None,
+        };
+
+        self.append(metrics)?;
+        Ok(delta)
+    }
+
+    /// Find the most recent record for `model_name`/`prompt_used`, assuming
+    /// records are appended in chronological order
+    fn most_recent_prior(&self, model_name: &str, prompt_used: &str) -> Result<Option<HistoryRecord>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&self.path)
+            .context("Failed to read evaluation history file")?;
+
+        let record = content
+            .lines()
+            .filter_map(|line| serde_json::from_str::<HistoryRecord>(line).ok())
+            .filter(|record| record.model_name == model_name && record.prompt_used == prompt_used)
+            .last();
+
+        Ok(record)
+    }
+
+    fn append(&self, metrics: &EvaluationMetrics) -> Result<()> {
+        let record = HistoryRecord {
+            model_name: metrics.model_name.clone(),
+            prompt_used: metrics.prompt_used.clone(),
+            score: metrics.score,
+            timestamp: metrics.timestamp.clone(),
+        };
+        let line = serde_json::to_string(&record).context("Failed to serialize history record")?;
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .context("Failed to open evaluation history file")?;
+
+        writeln!(file, "{}", line).context("Failed to append to evaluation history file")?;
+        Ok(())
+    }
+}
+
+/// One row of a machine-readable evaluation export, with `status` reduced to
+/// a stable lowercase string so downstream dashboards don't depend on Rust's
+/// derived enum representation
+#[derive(Debug, Clone, Serialize)]
+struct ExportedMetrics {
+    model_name: String,
+    score: Option<u8>,
+    duration_seconds: Option<u64>,
+    prompt_tokens: Option<u32>,
+    completion_tokens: Option<u32>,
+    model_size: String,
+    status: String,
+    strength_count: usize,
+    issue_count: usize,
+}
+
+impl From<&EvaluationMetrics> for ExportedMetrics {
+    fn from(metric: &EvaluationMetrics) -> Self {
+        Self {
+            model_name: metric.model_name.clone(),
+            score: metric.score,
+            duration_seconds: metric.duration_seconds,
+            prompt_tokens: metric.prompt_tokens,
+            completion_tokens: metric.completion_tokens,
+            model_size: metric.model_size.clone(),
+            status: metric.status.as_export_str().to_string(),
+            strength_count: metric.strengths.len(),
+            issue_count: metric.issues.len(),
+        }
+    }
+}
+
+/// Aggregate statistics included alongside the per-model rows in a full report export
+#[derive(Debug, Clone, Serialize)]
+struct ReportAggregateStats {
+    average_score: Option<f64>,
+    successful_evaluations: usize,
+    total_evaluations: usize,
+}
+
+impl ReportAggregateStats {
+    fn compute(metrics: &[EvaluationMetrics]) -> Self {
+        let scores: Vec<u8> = metrics.iter().filter_map(|m| m.score).collect();
+        let average_score = if scores.is_empty() {
+            None
+        } else {
+            Some(scores.iter().map(|&s| s as f64).sum::<f64>() / scores.len() as f64)
+        };
+        let successful_evaluations = metrics
+            .iter()
+            .filter(|m| matches!(m.status, EvaluationStatus::Success))
+            .count();
+
+        Self {
+            average_score,
+            successful_evaluations,
+            total_evaluations: metrics.len(),
+        }
+    }
+}
+
+/// A full report, ready to serialize: per-model rows plus the computed aggregate block
+#[derive(Debug, Clone, Serialize)]
+struct ExportedReport {
+    metrics: Vec<ExportedMetrics>,
+    aggregate: ReportAggregateStats,
+}
+
+/// Serializes a full evaluation report to machine-readable formats, so
+/// downstream dashboards and spreadsheets can ingest results without
+/// scraping the human-formatted Markdown/ASCII tables
+pub struct ReportExporter;
+
+impl ReportExporter {
+    /// Serialize `metrics` plus their computed aggregate statistics to pretty-printed JSON
+    pub fn to_json(metrics: &[EvaluationMetrics]) -> Result<String> {
+        let report = ExportedReport {
+            metrics: metrics.iter().map(ExportedMetrics::from).collect(),
+            aggregate: ReportAggregateStats::compute(metrics),
+        };
+        serde_json::to_string_pretty(&report).context("Failed to serialize evaluation report to JSON")
+    }
+
+    /// Flatten `metrics` to CSV, one row per model
+    pub fn to_csv(metrics: &[EvaluationMetrics]) -> Result<String> {
+        let mut csv = String::from(
+            "model_name,score,duration_seconds,prompt_tokens,completion_tokens,model_size,status,strength_count,issue_count\n",
+        );
+
+        for metric in metrics {
+            let row = ExportedMetrics::from(metric);
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{}\n",
+                Self::csv_escape(&row.model_name),
+                row.score.map(|s| s.to_string()).unwrap_or_default(),
+                row.duration_seconds.map(|d| d.to_string()).unwrap_or_default(),
+                row.prompt_tokens.map(|t| t.to_string()).unwrap_or_default(),
+                row.completion_tokens.map(|t| t.to_string()).unwrap_or_default(),
+                Self::csv_escape(&row.model_size),
+                row.status,
+                row.strength_count,
+                row.issue_count,
+            ));
+        }
+
+        Ok(csv)
+    }
+
+    /// Quote a field if it contains a comma, quote, or newline, per RFC 4180
+    fn csv_escape(field: &str) -> String {
+        if field.contains(',') || field.contains('"') || field.contains('\n') {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -526,4 +1212,217 @@ mod tests {
         assert_eq!(parser.estimate_model_size("gemma3:27b"), "27B");
         assert_eq!(parser.estimate_model_size("unknown-model"), "Unknown");
     }
+
+    #[test]
+    fn test_outputs_match_whitespace_normalized() {
+        assert!(ObjectiveScorer::outputs_match("1 2   3\n", "1 2 3", &MatchMode::WhitespaceNormalized));
+        assert!(!ObjectiveScorer::outputs_match("1 2 3", "1 2 4", &MatchMode::WhitespaceNormalized));
+    }
+
+    #[test]
+    fn test_outputs_match_numeric_tolerance() {
+        let mode = MatchMode::Numeric { abs_tol: 0.01, rel_tol: 0.001 };
+        assert!(ObjectiveScorer::outputs_match("3.14159", "3.14160", &mode));
+        assert!(!ObjectiveScorer::outputs_match("3.14159", "4.0", &mode));
+    }
+
+    #[test]
+    fn test_outputs_match_numeric_requires_equal_token_count() {
+        let mode = MatchMode::Numeric { abs_tol: 0.01, rel_tol: 0.001 };
+        assert!(!ObjectiveScorer::outputs_match("1 2", "1 2 3", &mode));
+    }
+
+    #[test]
+    fn test_objective_scorer_runs_suite_against_shell_script() {
+        let suite = TestSuite {
+            cases: vec![
+                TestCase { input: "hello\n".to_string(), expected: "hello\n".to_string() },
+                TestCase { input: "world\n".to_string(), expected: "world\n".to_string() },
+            ],
+            match_mode: MatchMode::Exact,
+            timeout: Duration::from_secs(5),
+        };
+
+        let result = ObjectiveScorer::run_suite("cat", &["sh".to_string()], &suite).unwrap();
+
+        assert_eq!(result.cases_passed, 2);
+        assert_eq!(result.cases_total, 2);
+        assert_eq!(result.objective_score, 10);
+        assert!(matches!(result.status, EvaluationStatus::Success));
+    }
+
+    #[test]
+    fn test_criteria_scores_extraction() {
+        let parser = EvaluationParser::new();
+        let content = "**Correctness: 8/10**\n**Readability: 6/10**\n**Score: 7/10**";
+
+        let metrics = parser.parse_evaluation(content, "test-model").unwrap();
+
+        assert_eq!(metrics.criteria_scores.get("Correctness"), Some(&8));
+        assert_eq!(metrics.criteria_scores.get("Readability"), Some(&6));
+        assert!(!metrics.criteria_scores.contains_key("Score"));
+        assert_eq!(metrics.score, Some(7));
+    }
+
+    #[test]
+    fn test_criteria_scores_fallback_to_single_score() {
+        let parser = EvaluationParser::new();
+        let metrics = parser.parse_evaluation("**Score: 9/10**", "test-model").unwrap();
+
+        assert!(metrics.criteria_scores.is_empty());
+        assert_eq!(metrics.score, Some(9));
+    }
+
+    #[test]
+    fn test_apply_criteria_weights() {
+        let parser = EvaluationParser::new();
+        let mut metrics = parser.parse_evaluation(
+            "**Correctness: 10/10**\n**Performance: 2/10**",
+            "test-model",
+        ).unwrap();
+
+        let mut weights = HashMap::new();
+        weights.insert("Correctness".to_string(), 3.0);
+        weights.insert("Performance".to_string(), 1.0);
+
+        parser.apply_criteria_weights(&mut metrics, &weights);
+
+        // (10*3 + 2*1) / 4 = 8
+        assert_eq!(metrics.score, Some(8));
+    }
+
+    #[test]
+    fn test_generate_criteria_breakdown_renders_segments() {
+        let mut metrics = EvaluationMetrics::new_with_prompt("model-a".to_string(), String::new());
+        metrics.criteria_scores.insert("Correctness".to_string(), 8);
+        metrics.criteria_scores.insert("Readability".to_string(), 4);
+
+        let chart = VisualizationGenerator::generate_criteria_breakdown(&[metrics]);
+
+        assert!(chart.contains("Correctness"));
+        assert!(chart.contains("Readability"));
+        assert!(chart.contains("model-a"));
+    }
+
+    #[test]
+    fn test_evaluation_history_flags_regression() {
+        let path = std::env::temp_dir().join(format!("eval_history_test_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let history = EvaluationHistory::new(path.clone(), 2);
+
+        let mut first = EvaluationMetrics::new_with_prompt("model-a".to_string(), "prompt-1".to_string());
+        first.score = Some(8);
+        history.record_and_compare(&mut first).unwrap();
+
+        let mut second = EvaluationMetrics::new_with_prompt("model-a".to_string(), "prompt-1".to_string());
+        second.score = Some(5);
+        let delta = history.record_and_compare(&mut second).unwrap();
+
+        assert_eq!(delta, Some(-3));
+        assert!(matches!(second.status, EvaluationStatus::Regressed));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_evaluation_history_ignores_small_swings() {
+        let path = std::env::temp_dir().join(format!("eval_history_test_stable_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let history = EvaluationHistory::new(path.clone(), 2);
+
+        let mut first = EvaluationMetrics::new_with_prompt("model-b".to_string(), "prompt-1".to_string());
+        first.score = Some(7);
+        history.record_and_compare(&mut first).unwrap();
+
+        let mut second = EvaluationMetrics::new_with_prompt("model-b".to_string(), "prompt-1".to_string());
+        second.score = Some(6);
+        history.record_and_compare(&mut second).unwrap();
+
+        assert!(matches!(second.status, EvaluationStatus::Success));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_run_aggregator_computes_mean_stddev_and_ci() {
+        let scores = [6u8, 8, 7, 9];
+        let runs: Vec<EvaluationMetrics> = scores.iter().map(|&s| {
+            let mut m = EvaluationMetrics::new_with_prompt("model-a".to_string(), "prompt-1".to_string());
+            m.score = Some(s);
+            m
+        }).collect();
+
+        let agg = RunAggregator::aggregate(&runs).unwrap();
+
+        assert_eq!(agg.n, 4);
+        assert_eq!(agg.min, 6);
+        assert_eq!(agg.max, 9);
+        assert!((agg.mean - 7.5).abs() < 1e-9);
+        assert!(agg.stddev.unwrap() > 0.0);
+        let (lo, hi) = agg.confidence_interval_95.unwrap();
+        assert!(lo < agg.mean && agg.mean < hi);
+    }
+
+    #[test]
+    fn test_run_aggregator_single_run_has_no_interval() {
+        let mut m = EvaluationMetrics::new_with_prompt("model-a".to_string(), "prompt-1".to_string());
+        m.score = Some(8);
+
+        let agg = RunAggregator::aggregate(&[m]).unwrap();
+
+        assert_eq!(agg.n, 1);
+        assert_eq!(agg.mean, 8.0);
+        assert!(agg.stddev.is_none());
+        assert!(agg.confidence_interval_95.is_none());
+    }
+
+    #[test]
+    fn test_run_aggregator_groups_by_model_preserving_order() {
+        let a1 = EvaluationMetrics::new_with_prompt("model-a".to_string(), "p".to_string());
+        let b1 = EvaluationMetrics::new_with_prompt("model-b".to_string(), "p".to_string());
+        let a2 = EvaluationMetrics::new_with_prompt("model-a".to_string(), "p".to_string());
+        let metrics = vec![a1, b1, a2];
+
+        let groups = RunAggregator::group_by_model(&metrics);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, "model-a");
+        assert_eq!(groups[0].1.len(), 2);
+        assert_eq!(groups[1].0, "model-b");
+        assert_eq!(groups[1].1.len(), 1);
+    }
+
+    #[test]
+    fn test_report_exporter_to_json_includes_stable_status_and_aggregate() {
+        let mut metrics = EvaluationMetrics::new_with_prompt("model-a".to_string(), "prompt-1".to_string());
+        metrics.score = Some(8);
+        metrics.status = EvaluationStatus::ParseError;
+
+        let json = ReportExporter::to_json(&[metrics]).unwrap();
+
+        assert!(json.contains("\"status\": \"parse_error\""));
+        assert!(json.contains("\"average_score\": 8.0"));
+        assert!(json.contains("\"total_evaluations\": 1"));
+    }
+
+    #[test]
+    fn test_report_exporter_to_csv_has_header_and_row_per_model() {
+        let mut metrics = EvaluationMetrics::new_with_prompt("model-a".to_string(), "prompt-1".to_string());
+        metrics.score = Some(7);
+        metrics.strengths = vec!["clear".to_string()];
+
+        let csv = ReportExporter::to_csv(&[metrics]).unwrap();
+        let mut lines = csv.lines();
+
+        assert_eq!(lines.next().unwrap(), "model_name,score,duration_seconds,prompt_tokens,completion_tokens,model_size,status,strength_count,issue_count");
+        let row = lines.next().unwrap();
+        assert!(row.starts_with("model-a,7,"));
+        assert!(row.ends_with(",success,1,0"));
+    }
+
+    #[test]
+    fn test_report_exporter_csv_escapes_commas() {
+        let field = ReportExporter::csv_escape("Acme, Inc.");
+        assert_eq!(field, "\"Acme, Inc.\"");
+    }
 }
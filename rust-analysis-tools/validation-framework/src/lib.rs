@@ -43,7 +43,7 @@
 ///         min_disk_space_gb: 10.0,
 ///         aipack_config_path: "custom_config.toml".to_string(),
 ///         output_directory: "custom_results".to_string(),
-///         deep_model_validation: true,
+///         ..ValidationConfig::default()
 ///     };
 ///     
 ///     let result = validate_evaluation_prerequisites_with_config(config).await?;
@@ -95,7 +95,8 @@ pub fn init_logging_with_level(level: log::LevelFilter) {
 pub use validation::{
     ValidationResult, 
     ValidationSummary, 
-    ValidationConfig, 
+    ValidationConfig,
+    ValidationProfile,
     ValidationCoordinator,
     validate_evaluation_prerequisites,
     validate_evaluation_prerequisites_with_config
@@ -0,0 +1,227 @@
+/// Bounded LRU cache for evaluation results
+///
+/// Keeps `ResponseCache::put`/`get` within `PerformanceConfig::max_cache_size_mb`
+/// by tracking each entry's serialized size and evicting least-recently-used
+/// entries once the running total would exceed the bound, and expires
+/// entries older than `cache_expiration_hours` on read.
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+
+use chrono::{DateTime, Utc};
+use tokio::sync::Mutex;
+
+use crate::evaluation::EvaluationMetrics;
+use super::PerformanceConfig;
+
+struct CacheEntry {
+    value: EvaluationMetrics,
+    size_bytes: usize,
+    timestamp: DateTime<Utc>,
+}
+
+/// All cache state guarded by a single lock, since eviction needs to see
+/// the entries, the access order, and the running size total together
+struct CacheState {
+    entries: HashMap<String, CacheEntry>,
+    /// Access order, least-recently-used at the front
+    order: VecDeque<String>,
+    size_bytes: u64,
+}
+
+pub struct ResponseCache {
+    max_size_bytes: u64,
+    expiration: chrono::Duration,
+    state: Mutex<CacheState>,
+}
+
+impl ResponseCache {
+    pub fn new(config: &PerformanceConfig) -> Self {
+        Self {
+            max_size_bytes: config.max_cache_size_mb * 1024 * 1024,
+            expiration: chrono::Duration::hours(config.cache_expiration_hours as i64),
+            state: Mutex::new(CacheState {
+                entries: HashMap::new(),
+                order: VecDeque::new(),
+                size_bytes: 0,
+            }),
+        }
+    }
+
+    /// A stable, content-based cache key for a (model, prompt) pair
+    pub fn generate_cache_key(&self, model: &str, prompt: &str) -> String {
+        let mut hasher = DefaultHasher::new();
+        model.hash(&mut hasher);
+        prompt.hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+
+    /// Look up `cache_key`, dropping it first if it has expired; a surviving
+    /// hit is moved to the most-recently-used position
+    pub async fn get(&self, cache_key: &str) -> Option<EvaluationMetrics> {
+        let mut state = self.state.lock().await;
+
+        let expired = state
+            .entries
+            .get(cache_key)
+            .map(|entry| Utc::now() - entry.timestamp > self.expiration)
+            .unwrap_or(false);
+
+        if expired {
+            Self::remove_entry(&mut state, cache_key);
+            return None;
+        }
+
+        let value = state.entries.get(cache_key).map(|entry| entry.value.clone())?;
+        Self::touch(&mut state, cache_key);
+        Some(value)
+    }
+
+    /// Insert or replace `cache_key`, then evict least-recently-used entries
+    /// until the running total is back under `max_cache_size_mb`
+    pub async fn put(&self, cache_key: String, value: EvaluationMetrics) {
+        let size_bytes = serde_json::to_vec(&value).map(|bytes| bytes.len()).unwrap_or(0);
+
+        let mut state = self.state.lock().await;
+
+        if state.entries.contains_key(&cache_key) {
+            Self::remove_entry(&mut state, &cache_key);
+        }
+
+        state.size_bytes += size_bytes as u64;
+        state.entries.insert(
+            cache_key.clone(),
+            CacheEntry { value, size_bytes, timestamp: Utc::now() },
+        );
+        state.order.push_back(cache_key);
+
+        while state.size_bytes > self.max_size_bytes {
+            let Some(lru_key) = state.order.front().cloned() else { break };
+            Self::remove_entry(&mut state, &lru_key);
+        }
+    }
+
+    /// Drop every cached entry
+    pub async fn clear(&self) {
+        let mut state = self.state.lock().await;
+        state.entries.clear();
+        state.order.clear();
+        state.size_bytes = 0;
+    }
+
+    /// Total serialized size of every entry currently cached, in bytes
+    pub async fn cache_memory_bytes(&self) -> u64 {
+        self.state.lock().await.size_bytes
+    }
+
+    /// Move `cache_key` to the most-recently-used position in `order`
+    fn touch(state: &mut CacheState, cache_key: &str) {
+        if let Some(pos) = state.order.iter().position(|k| k == cache_key) {
+            if let Some(key) = state.order.remove(pos) {
+                state.order.push_back(key);
+            }
+        }
+    }
+
+    /// Remove `cache_key` from both the entry map and the access order,
+    /// keeping `size_bytes` in sync
+    fn remove_entry(state: &mut CacheState, cache_key: &str) {
+        if let Some(entry) = state.entries.remove(cache_key) {
+            state.size_bytes = state.size_bytes.saturating_sub(entry.size_bytes as u64);
+        }
+        if let Some(pos) = state.order.iter().position(|k| k == cache_key) {
+            state.order.remove(pos);
+        }
+    }
+}
+
+#[cfg(test)]
+impl ResponseCache {
+    /// Byte-granularity constructor for tests; `PerformanceConfig` only
+    /// exposes whole-megabyte budgets, too coarse for small test payloads
+    fn with_max_bytes(max_size_bytes: u64, expiration_hours: u64) -> Self {
+        Self {
+            max_size_bytes,
+            expiration: chrono::Duration::hours(expiration_hours as i64),
+            state: Mutex::new(CacheState {
+                entries: HashMap::new(),
+                order: VecDeque::new(),
+                size_bytes: 0,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_metrics(model: &str) -> EvaluationMetrics {
+        EvaluationMetrics::new_with_prompt(model.to_string(), "prompt".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_put_then_get_round_trips() {
+        let cache = ResponseCache::new(&PerformanceConfig::default());
+        let key = cache.generate_cache_key("llama3.2:3b", "prompt");
+
+        cache.put(key.clone(), make_metrics("llama3.2:3b")).await;
+        let result = cache.get(&key).await;
+
+        assert!(result.is_some());
+        assert_eq!(result.unwrap().model_name, "llama3.2:3b");
+    }
+
+    #[tokio::test]
+    async fn test_get_expires_old_entries() {
+        let mut config = PerformanceConfig::default();
+        config.cache_expiration_hours = 0;
+        let cache = ResponseCache::new(&config);
+        let key = cache.generate_cache_key("llama3.2:3b", "prompt");
+
+        cache.put(key.clone(), make_metrics("llama3.2:3b")).await;
+        // cache_expiration_hours=0 means anything with nonzero age has expired
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+        assert!(cache.get(&key).await.is_none());
+        assert_eq!(cache.cache_memory_bytes().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_put_evicts_least_recently_used_when_over_budget() {
+        let metrics_a = make_metrics("model-a");
+        let entry_size = serde_json::to_vec(&metrics_a).unwrap().len() as u64;
+        // Budget for one entry only, so inserting a second must evict the first
+        let cache = ResponseCache::with_max_bytes(entry_size + 10, 24);
+
+        let key_a = cache.generate_cache_key("model-a", "prompt");
+        let key_b = cache.generate_cache_key("model-b", "prompt");
+
+        cache.put(key_a.clone(), metrics_a).await;
+        cache.put(key_b.clone(), make_metrics("model-b")).await;
+
+        assert!(cache.get(&key_a).await.is_none());
+        assert!(cache.get(&key_b).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_moves_entry_to_most_recently_used() {
+        let entry_size = serde_json::to_vec(&make_metrics("model-a")).unwrap().len() as u64;
+        // Budget for two entries; touching `a` via get() should make `b`
+        // (not `a`) the eviction victim once `c` pushes the cache over budget
+        let cache = ResponseCache::with_max_bytes(entry_size * 2 + 20, 24);
+
+        let key_a = cache.generate_cache_key("model-a", "prompt");
+        let key_b = cache.generate_cache_key("model-b", "prompt");
+        let key_c = cache.generate_cache_key("model-c", "prompt");
+
+        cache.put(key_a.clone(), make_metrics("model-a")).await;
+        cache.put(key_b.clone(), make_metrics("model-b")).await;
+        cache.get(&key_a).await; // touch a, making b the LRU victim
+        cache.put(key_c.clone(), make_metrics("model-c")).await;
+
+        assert!(cache.get(&key_a).await.is_some());
+        assert!(cache.get(&key_b).await.is_none());
+        assert!(cache.get(&key_c).await.is_some());
+    }
+}
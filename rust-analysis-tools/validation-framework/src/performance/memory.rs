@@ -0,0 +1,259 @@
+/// Memory tracking for `PerformanceEvaluationCoordinator`
+///
+/// Polls real OS-level resident memory (RSS) on a background task rather
+/// than estimating it, and keeps both a running peak and a coarse
+/// distribution of the samples seen so far.
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use anyhow::Result;
+use serde::Serialize;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use super::PerformanceConfig;
+
+/// Upper bound (MB) of each histogram bucket; the last bucket catches
+/// everything above `2048`. Chosen as an exponential ladder so both small
+/// CLI runs and large batch evaluations land in a meaningful bucket.
+const HISTOGRAM_BOUNDARIES_MB: [u64; 6] = [64, 128, 256, 512, 1024, 2048];
+
+/// A coarse, exponentially-bucketed distribution of memory samples
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryHistogram {
+    /// `buckets[i]` counts samples `<= HISTOGRAM_BOUNDARIES_MB[i]` MB (and
+    /// above the previous boundary); the final entry counts everything
+    /// above the largest boundary
+    pub buckets: Vec<u64>,
+}
+
+impl MemoryHistogram {
+    fn new() -> Self {
+        Self { buckets: vec![0; HISTOGRAM_BOUNDARIES_MB.len() + 1] }
+    }
+
+    fn record(&mut self, mb: f64) {
+        let bucket = HISTOGRAM_BOUNDARIES_MB
+            .iter()
+            .position(|&boundary| mb <= boundary as f64)
+            .unwrap_or(HISTOGRAM_BOUNDARIES_MB.len());
+        self.buckets[bucket] += 1;
+    }
+}
+
+/// Tracks process memory usage via a background sampler, exposing the
+/// running peak (in MB) and a histogram of every sample taken
+pub struct MemoryManager {
+    sample_interval: Duration,
+    peak_usage_mb_bits: Arc<AtomicU64>,
+    histogram: Arc<Mutex<MemoryHistogram>>,
+    sampler: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl MemoryManager {
+    /// Create a manager whose sampler polls at a fraction of
+    /// `memory_cleanup_interval_seconds`, so memory is tracked far more
+    /// often than the coarse cleanup cadence it's configured alongside
+    pub fn new(config: &PerformanceConfig) -> Self {
+        let sample_seconds = (config.memory_cleanup_interval_seconds / 10).max(1);
+
+        Self {
+            sample_interval: Duration::from_secs(sample_seconds),
+            peak_usage_mb_bits: Arc::new(AtomicU64::new(0f64.to_bits())),
+            histogram: Arc::new(Mutex::new(MemoryHistogram::new())),
+            sampler: Mutex::new(None),
+        }
+    }
+
+    /// Start the background sampling task; a no-op if already started.
+    /// Call `stop_sampling` once the evaluation that needed it completes.
+    pub async fn start_sampling(&self) {
+        let mut sampler = self.sampler.lock().await;
+        if sampler.is_some() {
+            return;
+        }
+
+        let interval = self.sample_interval;
+        let peak_bits = self.peak_usage_mb_bits.clone();
+        let histogram = self.histogram.clone();
+
+        *sampler = Some(tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+
+                let sample_mb = Self::sample_rss_mb();
+                Self::record_peak(&peak_bits, sample_mb);
+                histogram.lock().await.record(sample_mb);
+            }
+        }));
+    }
+
+    /// Stop the background sampling task started by `start_sampling`
+    pub async fn stop_sampling(&self) {
+        if let Some(handle) = self.sampler.lock().await.take() {
+            handle.abort();
+        }
+    }
+
+    /// The highest RSS sample recorded so far, in MB
+    pub async fn get_peak_usage_mb(&self) -> f64 {
+        f64::from_bits(self.peak_usage_mb_bits.load(Ordering::Relaxed))
+    }
+
+    /// The distribution of every sample recorded so far
+    pub async fn get_memory_histogram(&self) -> MemoryHistogram {
+        self.histogram.lock().await.clone()
+    }
+
+    /// Drop cleanup hook run between evaluation batches; takes one sample
+    /// immediately so short-lived runs still get at least one data point
+    pub async fn cleanup_resources(&self) -> Result<()> {
+        let sample_mb = Self::sample_rss_mb();
+        Self::record_peak(&self.peak_usage_mb_bits, sample_mb);
+        self.histogram.lock().await.record(sample_mb);
+        Ok(())
+    }
+
+    /// Reset the tracked peak and histogram, used by `reset_performance_state`
+    pub async fn reset_peak(&self) {
+        self.peak_usage_mb_bits.store(0f64.to_bits(), Ordering::Relaxed);
+        *self.histogram.lock().await = MemoryHistogram::new();
+    }
+
+    fn record_peak(peak_bits: &AtomicU64, sample_mb: f64) {
+        let mut current = peak_bits.load(Ordering::Relaxed);
+        loop {
+            let current_mb = f64::from_bits(current);
+            if sample_mb <= current_mb {
+                return;
+            }
+            match peak_bits.compare_exchange_weak(
+                current,
+                sample_mb.to_bits(),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// Read resident memory via `getrusage(RUSAGE_SELF, …)`, falling back to
+    /// `/proc/self/statm` on Linux if that fails, and returning `0.0` rather
+    /// than erroring when neither is available (e.g. non-unix targets).
+    pub(crate) fn sample_rss_mb() -> f64 {
+        #[cfg(unix)]
+        {
+            if let Some(mb) = Self::sample_rss_mb_via_getrusage() {
+                return mb;
+            }
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            if let Some(mb) = Self::sample_rss_mb_via_statm() {
+                return mb;
+            }
+        }
+
+        0.0
+    }
+
+    #[cfg(unix)]
+    fn sample_rss_mb_via_getrusage() -> Option<f64> {
+        use std::mem::MaybeUninit;
+
+        let mut usage = MaybeUninit::<libc::rusage>::uninit();
+        let result = unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) };
+        if result != 0 {
+            return None;
+        }
+
+        let ru_maxrss = unsafe { usage.assume_init() }.ru_maxrss;
+        if ru_maxrss < 0 {
+            return None;
+        }
+
+        // Linux reports ru_maxrss in kilobytes, macOS in bytes
+        #[cfg(target_os = "macos")]
+        let bytes = ru_maxrss as f64;
+        #[cfg(not(target_os = "macos"))]
+        let bytes = ru_maxrss as f64 * 1024.0;
+
+        Some(bytes / (1024.0 * 1024.0))
+    }
+
+    #[cfg(target_os = "linux")]
+    fn sample_rss_mb_via_statm() -> Option<f64> {
+        let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+        let resident_pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+        let page_size_bytes = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        if page_size_bytes <= 0 {
+            return None;
+        }
+
+        let bytes = resident_pages as f64 * page_size_bytes as f64;
+        Some(bytes / (1024.0 * 1024.0))
+    }
+
+    /// Total user+system CPU time consumed by the process so far, in
+    /// seconds; `0.0` on platforms without `getrusage` (e.g. non-unix)
+    pub(crate) fn sample_cpu_seconds() -> f64 {
+        #[cfg(unix)]
+        {
+            use std::mem::MaybeUninit;
+
+            let mut usage = MaybeUninit::<libc::rusage>::uninit();
+            let result = unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) };
+            if result == 0 {
+                let usage = unsafe { usage.assume_init() };
+                let user = usage.ru_utime.tv_sec as f64 + usage.ru_utime.tv_usec as f64 / 1_000_000.0;
+                let system = usage.ru_stime.tv_sec as f64 + usage.ru_stime.tv_usec as f64 / 1_000_000.0;
+                return user + system;
+            }
+        }
+
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets_by_boundary() {
+        let mut histogram = MemoryHistogram::new();
+        histogram.record(10.0); // below first boundary
+        histogram.record(64.0); // exactly the first boundary
+        histogram.record(3000.0); // above the last boundary
+
+        assert_eq!(histogram.buckets[0], 2);
+        assert_eq!(histogram.buckets[HISTOGRAM_BOUNDARIES_MB.len()], 1);
+    }
+
+    #[tokio::test]
+    async fn test_peak_usage_tracks_the_maximum_sample() {
+        let peak_bits = Arc::new(AtomicU64::new(0f64.to_bits()));
+        MemoryManager::record_peak(&peak_bits, 50.0);
+        MemoryManager::record_peak(&peak_bits, 20.0);
+        MemoryManager::record_peak(&peak_bits, 75.0);
+
+        assert_eq!(f64::from_bits(peak_bits.load(Ordering::Relaxed)), 75.0);
+    }
+
+    #[tokio::test]
+    async fn test_reset_peak_clears_peak_and_histogram() {
+        let manager = MemoryManager::new(&PerformanceConfig::default());
+        manager.cleanup_resources().await.unwrap();
+
+        manager.reset_peak().await;
+
+        assert_eq!(manager.get_peak_usage_mb().await, 0.0);
+        assert_eq!(
+            manager.get_memory_histogram().await.buckets,
+            vec![0; HISTOGRAM_BOUNDARIES_MB.len() + 1]
+        );
+    }
+}
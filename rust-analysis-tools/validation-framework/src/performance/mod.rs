@@ -34,8 +34,14 @@
 /// - Real-time progress indicators with completion percentages
 /// - ETA calculations based on current throughput
 /// - Detailed status reporting for individual model evaluations
+///
+/// ### Profiling
+/// - Pluggable `Profiler` hooks invoked around each evaluation phase
+/// - A system-monitor profiler that samples CPU/memory during a phase
+/// - No-op by default so profiling has zero cost unless opted into
 
 use std::collections::HashMap;
+use std::process::Command;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{Semaphore, RwLock, Mutex};
@@ -50,12 +56,14 @@ pub mod warmup;
 pub mod progress;
 pub mod parallel;
 pub mod memory;
+pub mod profiler;
 
 use cache::ResponseCache;
 use warmup::ModelWarmup;
 use progress::{ProgressTracker, EvaluationProgress};
 use parallel::ParallelEvaluator;
-use memory::MemoryManager;
+use memory::{MemoryHistogram, MemoryManager};
+use profiler::{PhaseProfile, Profiler, ProfilerKind};
 
 /// Configuration for performance optimization features
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -82,6 +90,21 @@ pub struct PerformanceConfig {
     pub enable_progress_tracking: bool,
     /// Progress update interval in milliseconds
     pub progress_update_interval_ms: u64,
+    /// Which `Profiler` to sample each phase with
+    pub profiler_kind: ProfilerKind,
+    /// Base wall-clock timeout for a single evaluation attempt, in seconds
+    pub evaluation_timeout_seconds: u64,
+    /// Multiplier applied to `evaluation_timeout_seconds` on the first
+    /// attempt, to give a cold (not-yet-loaded) model extra slack
+    pub timeout_wall_clock_factor: f64,
+    /// Number of retries after a timed-out or transiently-failed attempt,
+    /// before the task is recorded as failed
+    pub max_evaluation_retries: u32,
+    /// Base delay before the first retry, in milliseconds (doubled each
+    /// subsequent retry up to `retry_backoff_cap_ms`)
+    pub retry_backoff_base_ms: u64,
+    /// Upper bound on the retry backoff delay, in milliseconds
+    pub retry_backoff_cap_ms: u64,
 }
 
 impl Default for PerformanceConfig {
@@ -98,6 +121,12 @@ impl Default for PerformanceConfig {
             memory_cleanup_interval_seconds: 300, // 5 minutes
             enable_progress_tracking: true,
             progress_update_interval_ms: 500,
+            profiler_kind: ProfilerKind::default(),
+            evaluation_timeout_seconds: 60,
+            timeout_wall_clock_factor: 3.0,
+            max_evaluation_retries: 2,
+            retry_backoff_base_ms: 1000,
+            retry_backoff_cap_ms: 4000,
         }
     }
 }
@@ -113,6 +142,7 @@ pub struct PerformanceEvaluationCoordinator {
     parallel_evaluator: Arc<ParallelEvaluator>,
     memory_manager: Arc<MemoryManager>,
     evaluation_semaphore: Arc<Semaphore>,
+    profiler: Arc<dyn Profiler>,
 }
 
 impl PerformanceEvaluationCoordinator {
@@ -134,7 +164,8 @@ impl PerformanceEvaluationCoordinator {
         let progress_tracker = Arc::new(ProgressTracker::new(&config));
         let parallel_evaluator = Arc::new(ParallelEvaluator::new(&config, http_client.clone()));
         let memory_manager = Arc::new(MemoryManager::new(&config));
-        
+        let profiler = config.profiler_kind.build();
+
         let evaluation_semaphore = Arc::new(Semaphore::new(config.max_concurrent_evaluations));
 
         Self {
@@ -147,6 +178,7 @@ impl PerformanceEvaluationCoordinator {
             parallel_evaluator,
             memory_manager,
             evaluation_semaphore,
+            profiler,
         }
     }
 
@@ -166,37 +198,46 @@ impl PerformanceEvaluationCoordinator {
         ollama_url: &str,
     ) -> Result<OptimizedEvaluationResult> {
         let start_time = Instant::now();
-        
-        log::info!("🚀 Starting optimized evaluation for {} models with {} prompts", 
+
+        log::info!("🚀 Starting optimized evaluation for {} models with {} prompts",
                    models.len(), prompts.len());
 
+        if self.config.enable_memory_optimization {
+            self.memory_manager.start_sampling().await;
+        }
+
         // Initialize progress tracking
         let total_evaluations = models.len() * prompts.len();
         let progress = self.progress_tracker.start_evaluation(total_evaluations).await;
 
         // Phase 1: Model warm-up (if enabled)
         if self.config.enable_warmup {
+            self.profiler.on_phase_start("warm-up");
             log::info!("🔥 Warming up {} models...", models.len());
             let warmup_start = Instant::now();
-            
+
             let warmup_result = self.model_warmup.warmup_models(&models, ollama_url).await
                 .context("Failed to warm up models")?;
-            
+
             let warmup_duration = warmup_start.elapsed();
             log::info!("✅ Model warm-up completed in {:.2}s", warmup_duration.as_secs_f64());
-            
+            self.profiler.on_phase_end("warm-up", warmup_duration.as_secs_f64());
+
             // Update progress
             self.progress_tracker.update_phase(&progress, "Warm-up completed").await;
         }
 
         // Phase 2: Check cache and prepare evaluation tasks
+        self.profiler.on_phase_start("cache-check");
+        let cache_check_start = Instant::now();
+
         let mut evaluation_tasks = Vec::new();
         let mut cached_results = Vec::new();
-        
+
         for model in &models {
             for prompt in &prompts {
                 let cache_key = self.response_cache.generate_cache_key(model, prompt);
-                
+
                 if self.config.enable_cache {
                     if let Some(cached_result) = self.response_cache.get(&cache_key).await {
                         log::debug!("📦 Using cached result for model: {}", model);
@@ -205,7 +246,7 @@ impl PerformanceEvaluationCoordinator {
                         continue;
                     }
                 }
-                
+
                 evaluation_tasks.push(EvaluationTask {
                     model: model.clone(),
                     prompt: prompt.clone(),
@@ -214,18 +255,21 @@ impl PerformanceEvaluationCoordinator {
             }
         }
 
-        log::info!("📊 Evaluation plan: {} cached results, {} new evaluations", 
+        log::info!("📊 Evaluation plan: {} cached results, {} new evaluations",
                    cached_results.len(), evaluation_tasks.len());
+        self.profiler.on_phase_end("cache-check", cache_check_start.elapsed().as_secs_f64());
 
         // Phase 3: Parallel evaluation execution
+        self.profiler.on_phase_start("parallel-execution");
+        let parallel_execution_start = Instant::now();
         let mut all_results = cached_results;
-        
+
         if !evaluation_tasks.is_empty() {
             let parallel_results = self.parallel_evaluator
                 .evaluate_parallel(evaluation_tasks, ollama_url, &progress)
                 .await
                 .context("Failed to execute parallel evaluations")?;
-            
+
             // Cache new results
             if self.config.enable_cache {
                 for result in &parallel_results {
@@ -233,14 +277,21 @@ impl PerformanceEvaluationCoordinator {
                     self.response_cache.put(cache_key, result.clone()).await;
                 }
             }
-            
+
             all_results.extend(parallel_results);
         }
+        self.profiler.on_phase_end("parallel-execution", parallel_execution_start.elapsed().as_secs_f64());
 
         // Phase 4: Memory cleanup
         if self.config.enable_memory_optimization {
+            self.profiler.on_phase_start("memory-cleanup");
+            let cleanup_start = Instant::now();
+
             self.memory_manager.cleanup_resources().await
                 .context("Failed to cleanup memory resources")?;
+            self.memory_manager.stop_sampling().await;
+
+            self.profiler.on_phase_end("memory-cleanup", cleanup_start.elapsed().as_secs_f64());
         }
 
         // Phase 5: Generate comprehensive results
@@ -254,13 +305,18 @@ impl PerformanceEvaluationCoordinator {
                 cache_hit_rate: if total_evaluations > 0 {
                     cached_results.len() as f64 / total_evaluations as f64
                 } else { 0.0 },
+                cache_memory_bytes: self.response_cache.cache_memory_bytes().await,
                 average_evaluation_time_secs: if evaluation_tasks.len() > 0 {
                     total_duration.as_secs_f64() / evaluation_tasks.len() as f64
                 } else { 0.0 },
                 parallel_efficiency: final_progress.parallel_efficiency,
                 memory_peak_usage_mb: self.memory_manager.get_peak_usage_mb().await,
+                memory_histogram: self.memory_manager.get_memory_histogram().await,
                 warmup_enabled: self.config.enable_warmup,
                 cache_enabled: self.config.enable_cache,
+                phase_profiles: self.profiler.phase_profiles(),
+                total_retries: self.parallel_evaluator.retry_count(),
+                total_timeouts: self.parallel_evaluator.timeout_count(),
             },
             progress_summary: final_progress,
         };
@@ -276,11 +332,16 @@ impl PerformanceEvaluationCoordinator {
         PerformanceStats {
             total_duration_secs: 0.0,
             cache_hit_rate: 0.0,
+            cache_memory_bytes: self.response_cache.cache_memory_bytes().await,
             average_evaluation_time_secs: 0.0,
             parallel_efficiency: 0.0,
             memory_peak_usage_mb: self.memory_manager.get_peak_usage_mb().await,
+            memory_histogram: self.memory_manager.get_memory_histogram().await,
             warmup_enabled: self.config.enable_warmup,
             cache_enabled: self.config.enable_cache,
+            phase_profiles: self.profiler.phase_profiles(),
+            total_retries: self.parallel_evaluator.retry_count(),
+            total_timeouts: self.parallel_evaluator.timeout_count(),
         }
     }
 
@@ -291,11 +352,184 @@ impl PerformanceEvaluationCoordinator {
         }
         
         self.memory_manager.cleanup_resources().await?;
+        self.memory_manager.reset_peak().await;
         self.progress_tracker.reset().await;
         
         log::info!("🔄 Performance state reset completed");
         Ok(())
     }
+
+    /// Run each model/prompt combination repeatedly and summarize duration
+    /// and throughput (completion tokens/sec) per model, for use as a
+    /// regression-tracking harness rather than a one-shot evaluation. Warm-up
+    /// runs are excluded from the reported statistics.
+    pub async fn benchmark_models(
+        &self,
+        models: Vec<String>,
+        prompts: Vec<String>,
+        ollama_url: &str,
+        benchmark_config: BenchmarkConfig,
+    ) -> Result<MetricsReport> {
+        log::info!(
+            "📈 Benchmarking {} models x {} prompts, {} trial(s) each ({} warm-up run(s) excluded)",
+            models.len(), prompts.len(), benchmark_config.repetitions, benchmark_config.warmup_runs
+        );
+
+        if self.config.enable_warmup && benchmark_config.warmup_runs > 0 {
+            for _ in 0..benchmark_config.warmup_runs {
+                self.model_warmup.warmup_models(&models, ollama_url).await
+                    .context("Failed to warm up models before benchmarking")?;
+            }
+        }
+
+        let mut samples_by_model: HashMap<String, Vec<TrialSample>> = HashMap::new();
+
+        for _ in 0..benchmark_config.repetitions {
+            let tasks: Vec<EvaluationTask> = models
+                .iter()
+                .flat_map(|model| {
+                    prompts.iter().map(move |prompt| EvaluationTask {
+                        model: model.clone(),
+                        prompt: prompt.clone(),
+                        cache_key: String::new(),
+                    })
+                })
+                .collect();
+
+            let trial_progress = self.progress_tracker.start_evaluation(tasks.len()).await;
+            let results = self.parallel_evaluator
+                .evaluate_parallel(tasks, ollama_url, &trial_progress)
+                .await
+                .context("Failed to execute benchmark trial")?;
+
+            for result in results {
+                let Some(duration_secs) = result.duration_seconds else { continue };
+                let tokens_per_sec = result
+                    .completion_tokens
+                    .filter(|_| duration_secs > 0)
+                    .map(|tokens| tokens as f64 / duration_secs as f64)
+                    .unwrap_or(0.0);
+
+                samples_by_model.entry(result.model_name.clone()).or_default().push(TrialSample {
+                    duration_secs: duration_secs as f64,
+                    tokens_per_sec,
+                });
+            }
+        }
+
+        let models_report = models
+            .iter()
+            .filter_map(|model| {
+                let samples = samples_by_model.get(model)?;
+                Some(ModelBenchmarkStats::from_samples(model.clone(), samples))
+            })
+            .collect();
+
+        Ok(MetricsReport {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            git_revision: Self::git_command(&["rev-parse", "HEAD"]),
+            git_describe: Self::git_command(&["describe", "--dirty", "--always"]),
+            models: models_report,
+        })
+    }
+
+    /// Shell out to `git`, returning `"unknown"` rather than failing the
+    /// benchmark run when not in a git checkout or `git` isn't on `PATH`
+    fn git_command(args: &[&str]) -> String {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|s| s.trim().to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+}
+
+/// One trial's duration and throughput, before being folded into
+/// `ModelBenchmarkStats` for its model
+struct TrialSample {
+    duration_secs: f64,
+    tokens_per_sec: f64,
+}
+
+/// Parameters for `benchmark_models`
+#[derive(Debug, Clone, Copy)]
+pub struct BenchmarkConfig {
+    /// Number of timed trials run per model/prompt combination
+    pub repetitions: usize,
+    /// Number of warm-up passes run (and excluded from statistics) before timing starts
+    pub warmup_runs: usize,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self { repetitions: 5, warmup_runs: 1 }
+    }
+}
+
+/// Mean/stddev/min/max duration and throughput for one model across every
+/// timed trial
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelBenchmarkStats {
+    pub model_name: String,
+    pub trials: usize,
+    pub duration_mean_secs: f64,
+    pub duration_stddev_secs: f64,
+    pub duration_min_secs: f64,
+    pub duration_max_secs: f64,
+    pub throughput_mean_tokens_per_sec: f64,
+    pub throughput_stddev_tokens_per_sec: f64,
+    pub throughput_min_tokens_per_sec: f64,
+    pub throughput_max_tokens_per_sec: f64,
+}
+
+impl ModelBenchmarkStats {
+    fn from_samples(model_name: String, samples: &[TrialSample]) -> Self {
+        let durations: Vec<f64> = samples.iter().map(|s| s.duration_secs).collect();
+        let throughputs: Vec<f64> = samples.iter().map(|s| s.tokens_per_sec).collect();
+
+        let (duration_mean_secs, duration_stddev_secs) = Self::mean_and_stddev(&durations);
+        let (throughput_mean_tokens_per_sec, throughput_stddev_tokens_per_sec) = Self::mean_and_stddev(&throughputs);
+
+        Self {
+            model_name,
+            trials: samples.len(),
+            duration_mean_secs,
+            duration_stddev_secs,
+            duration_min_secs: durations.iter().cloned().fold(f64::INFINITY, f64::min),
+            duration_max_secs: durations.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            throughput_mean_tokens_per_sec,
+            throughput_stddev_tokens_per_sec,
+            throughput_min_tokens_per_sec: throughputs.iter().cloned().fold(f64::INFINITY, f64::min),
+            throughput_max_tokens_per_sec: throughputs.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+
+    /// Population mean and standard deviation; both `0.0` for an empty sample set
+    fn mean_and_stddev(values: &[f64]) -> (f64, f64) {
+        if values.is_empty() {
+            return (0.0, 0.0);
+        }
+
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+        (mean, variance.sqrt())
+    }
+}
+
+/// A point-in-time benchmark result, suitable for CI to store and diff
+/// evaluation performance over time
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsReport {
+    /// When this report was generated (RFC 3339)
+    pub timestamp: String,
+    /// `git rev-parse HEAD` at the time of the run
+    pub git_revision: String,
+    /// `git describe --dirty --always` at the time of the run
+    pub git_describe: String,
+    pub models: Vec<ModelBenchmarkStats>,
 }
 
 /// Task for individual model evaluation
@@ -324,16 +558,26 @@ pub struct PerformanceStats {
     pub total_duration_secs: f64,
     /// Cache hit rate (0.0 to 1.0)
     pub cache_hit_rate: f64,
+    /// Total serialized size of every entry currently in the response cache
+    pub cache_memory_bytes: u64,
     /// Average time per evaluation in seconds
     pub average_evaluation_time_secs: f64,
     /// Parallel processing efficiency (0.0 to 1.0)
     pub parallel_efficiency: f64,
     /// Peak memory usage in MB during evaluation
     pub memory_peak_usage_mb: f64,
+    /// Distribution of sampled memory usage over the evaluation
+    pub memory_histogram: MemoryHistogram,
     /// Whether model warm-up was enabled
     pub warmup_enabled: bool,
     /// Whether response caching was enabled
     pub cache_enabled: bool,
+    /// Per-phase resource-usage series, populated when `profiler_kind` samples
+    pub phase_profiles: Vec<PhaseProfile>,
+    /// Total attempts retried after a timeout or transient failure, across every evaluation
+    pub total_retries: u64,
+    /// Total attempts that hit the per-attempt wall-clock timeout
+    pub total_timeouts: u64,
 }
 
 // Add methods to EvaluationMetrics for extended functionality
@@ -404,4 +648,28 @@ mod tests {
         assert_eq!(task.model, "llama3.2:3b");
         assert_eq!(task.prompt, "Test prompt");
     }
+
+    #[test]
+    fn test_model_benchmark_stats_from_samples() {
+        let samples = vec![
+            TrialSample { duration_secs: 1.0, tokens_per_sec: 10.0 },
+            TrialSample { duration_secs: 3.0, tokens_per_sec: 30.0 },
+        ];
+
+        let stats = ModelBenchmarkStats::from_samples("llama3.2:3b".to_string(), &samples);
+
+        assert_eq!(stats.trials, 2);
+        assert_eq!(stats.duration_mean_secs, 2.0);
+        assert_eq!(stats.duration_min_secs, 1.0);
+        assert_eq!(stats.duration_max_secs, 3.0);
+        assert_eq!(stats.throughput_mean_tokens_per_sec, 20.0);
+    }
+
+    #[test]
+    fn test_benchmark_config_defaults() {
+        let config = BenchmarkConfig::default();
+
+        assert_eq!(config.repetitions, 5);
+        assert_eq!(config.warmup_runs, 1);
+    }
 }
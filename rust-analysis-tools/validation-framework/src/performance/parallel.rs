@@ -0,0 +1,468 @@
+/// Bounded-concurrency model evaluation with per-task timeout and retry
+///
+/// Each `EvaluationTask` is run through an `OllamaTransport` and parsed into
+/// `EvaluationMetrics`. A hung or transiently-failing call does not abort
+/// the batch: it is retried up to a configurable number of times with
+/// exponential backoff, and a task that exhausts its retries still produces
+/// an `EvaluationMetrics` (with `EvaluationStatus::Timeout` or `Failed`)
+/// instead of being dropped.
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Semaphore;
+
+use crate::evaluation::{EvaluationMetrics, EvaluationParser, EvaluationStatus};
+use super::{EvaluationTask, PerformanceConfig};
+use super::progress::EvaluationProgress;
+
+/// The unparsed result of a single `generate` call, before being run through
+/// `EvaluationParser`
+#[derive(Debug, Clone)]
+pub struct RawGenerateResponse {
+    pub response: String,
+    pub prompt_eval_count: Option<u32>,
+    pub eval_count: Option<u32>,
+}
+
+/// Abstracts the Ollama `/api/generate` call so `ParallelEvaluator` can be
+/// driven by a scripted fake in tests instead of a live server
+pub trait OllamaTransport: Send + Sync {
+    fn generate<'a>(
+        &'a self,
+        ollama_url: &'a str,
+        model: &'a str,
+        prompt: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Result<RawGenerateResponse>> + Send + 'a>>;
+}
+
+/// Default transport: issues a real HTTP request to Ollama's `/api/generate`
+pub struct ReqwestTransport {
+    http_client: Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(http_client: Client) -> Self {
+        Self { http_client }
+    }
+}
+
+impl OllamaTransport for ReqwestTransport {
+    fn generate<'a>(
+        &'a self,
+        ollama_url: &'a str,
+        model: &'a str,
+        prompt: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Result<RawGenerateResponse>> + Send + 'a>> {
+        Box::pin(async move {
+            let generate_url = format!("{}/api/generate", ollama_url);
+            let body = OllamaGenerateRequest { model, prompt, stream: false };
+
+            let response = self.http_client
+                .post(&generate_url)
+                .json(&body)
+                .send()
+                .await
+                .context("Failed to send generate request")?;
+
+            if !response.status().is_success() {
+                anyhow::bail!("Ollama returned status {}", response.status());
+            }
+
+            let generated: OllamaGenerateResponse = response
+                .json()
+                .await
+                .context("Failed to parse generate response")?;
+
+            Ok(RawGenerateResponse {
+                response: generated.response,
+                prompt_eval_count: generated.prompt_eval_count,
+                eval_count: generated.eval_count,
+            })
+        })
+    }
+}
+
+/// Runs evaluation tasks with bounded concurrency, per-attempt wall-clock
+/// timeouts, and exponential-backoff retries for transient failures
+pub struct ParallelEvaluator {
+    transport: Arc<dyn OllamaTransport>,
+    evaluation_parser: Arc<EvaluationParser>,
+    concurrency_limit: Arc<Semaphore>,
+    base_timeout: Duration,
+    timeout_wall_clock_factor: f64,
+    max_retries: u32,
+    retry_backoff_base: Duration,
+    retry_backoff_cap: Duration,
+    retry_count: Arc<AtomicU64>,
+    timeout_count: Arc<AtomicU64>,
+}
+
+impl ParallelEvaluator {
+    pub fn new(config: &PerformanceConfig, http_client: Client) -> Self {
+        Self::with_transport(config, Arc::new(ReqwestTransport::new(http_client)))
+    }
+
+    /// Construct an evaluator driven by a custom `OllamaTransport`, e.g. a
+    /// scripted mock in tests
+    pub fn with_transport(config: &PerformanceConfig, transport: Arc<dyn OllamaTransport>) -> Self {
+        Self {
+            transport,
+            evaluation_parser: Arc::new(EvaluationParser::new()),
+            concurrency_limit: Arc::new(Semaphore::new(config.max_concurrent_evaluations)),
+            base_timeout: Duration::from_secs(config.evaluation_timeout_seconds),
+            timeout_wall_clock_factor: config.timeout_wall_clock_factor,
+            max_retries: config.max_evaluation_retries,
+            retry_backoff_base: Duration::from_millis(config.retry_backoff_base_ms),
+            retry_backoff_cap: Duration::from_millis(config.retry_backoff_cap_ms),
+            retry_count: Arc::new(AtomicU64::new(0)),
+            timeout_count: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Run every task concurrently (bounded by `max_concurrent_evaluations`),
+    /// returning one `EvaluationMetrics` per task regardless of whether it
+    /// ultimately succeeded, timed out, or failed
+    pub async fn evaluate_parallel(
+        &self,
+        tasks: Vec<EvaluationTask>,
+        ollama_url: &str,
+        _progress: &EvaluationProgress,
+    ) -> Result<Vec<EvaluationMetrics>> {
+        let mut handles = Vec::with_capacity(tasks.len());
+
+        for task in tasks {
+            let semaphore = self.concurrency_limit.clone();
+            let transport = self.transport.clone();
+            let evaluation_parser = self.evaluation_parser.clone();
+            let ollama_url = ollama_url.to_string();
+            let base_timeout = self.base_timeout;
+            let timeout_wall_clock_factor = self.timeout_wall_clock_factor;
+            let max_retries = self.max_retries;
+            let retry_backoff_base = self.retry_backoff_base;
+            let retry_backoff_cap = self.retry_backoff_cap;
+            let retry_count = self.retry_count.clone();
+            let timeout_count = self.timeout_count.clone();
+
+            handles.push(tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await.expect("evaluation semaphore closed");
+
+                Self::evaluate_with_retries(
+                    transport.as_ref(),
+                    &evaluation_parser,
+                    &task,
+                    &ollama_url,
+                    base_timeout,
+                    timeout_wall_clock_factor,
+                    max_retries,
+                    retry_backoff_base,
+                    retry_backoff_cap,
+                    &retry_count,
+                    &timeout_count,
+                ).await
+            }));
+        }
+
+        let mut results = Vec::with_capacity(handles.len());
+        for handle in handles {
+            results.push(handle.await.context("Evaluation task panicked")?);
+        }
+
+        Ok(results)
+    }
+
+    /// Run a single task through the retry/timeout machinery without the
+    /// surrounding concurrency/progress plumbing; used by tests driving a
+    /// `MockTransport` directly
+    #[cfg(test)]
+    async fn evaluate_one(&self, task: &EvaluationTask, ollama_url: &str) -> EvaluationMetrics {
+        Self::evaluate_with_retries(
+            self.transport.as_ref(),
+            &self.evaluation_parser,
+            task,
+            ollama_url,
+            self.base_timeout,
+            self.timeout_wall_clock_factor,
+            self.max_retries,
+            self.retry_backoff_base,
+            self.retry_backoff_cap,
+            &self.retry_count,
+            &self.timeout_count,
+        ).await
+    }
+
+    /// Total number of retried attempts across every task run so far
+    pub fn retry_count(&self) -> u64 {
+        self.retry_count.load(Ordering::Relaxed)
+    }
+
+    /// Total number of attempts that hit the per-attempt wall-clock timeout
+    pub fn timeout_count(&self) -> u64 {
+        self.timeout_count.load(Ordering::Relaxed)
+    }
+
+    /// How long a single attempt gets before it is killed and (maybe)
+    /// retried. The first attempt may include model load time, so it gets
+    /// `base_timeout * wall_clock_factor`; later attempts assume the model
+    /// is already warm and use `base_timeout` directly.
+    fn timeout_for_attempt(base_timeout: Duration, wall_clock_factor: f64, attempt: u32) -> Duration {
+        if attempt == 1 {
+            base_timeout.mul_f64(wall_clock_factor.max(1.0))
+        } else {
+            base_timeout
+        }
+    }
+
+    /// Exponential backoff (1x, 2x, 4x, ... the base) capped at `backoff_cap`
+    fn backoff_for_attempt(backoff_base: Duration, backoff_cap: Duration, attempt: u32) -> Duration {
+        backoff_base
+            .saturating_mul(1u32 << attempt.saturating_sub(1).min(16))
+            .min(backoff_cap)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn evaluate_with_retries(
+        transport: &dyn OllamaTransport,
+        evaluation_parser: &EvaluationParser,
+        task: &EvaluationTask,
+        ollama_url: &str,
+        base_timeout: Duration,
+        timeout_wall_clock_factor: f64,
+        max_retries: u32,
+        retry_backoff_base: Duration,
+        retry_backoff_cap: Duration,
+        retry_count: &AtomicU64,
+        timeout_count: &AtomicU64,
+    ) -> EvaluationMetrics {
+        let max_attempts = max_retries + 1;
+        let mut attempt = 1;
+
+        loop {
+            let attempt_timeout = Self::timeout_for_attempt(base_timeout, timeout_wall_clock_factor, attempt);
+
+            match tokio::time::timeout(
+                attempt_timeout,
+                Self::run_once(transport, evaluation_parser, task, ollama_url),
+            ).await {
+                Ok(Ok(metrics)) => return metrics,
+                Ok(Err(e)) => {
+                    if attempt >= max_attempts {
+                        log::warn!(
+                            "Evaluation of '{}' failed after {} attempt(s): {}",
+                            task.model, attempt, e
+                        );
+                        return Self::failed_metrics(task, EvaluationStatus::Failed);
+                    }
+                }
+                Err(_) => {
+                    timeout_count.fetch_add(1, Ordering::Relaxed);
+                    if attempt >= max_attempts {
+                        log::warn!(
+                            "Evaluation of '{}' timed out after {} attempt(s) ({:.1}s budget)",
+                            task.model, attempt, attempt_timeout.as_secs_f64()
+                        );
+                        return Self::failed_metrics(task, EvaluationStatus::Timeout);
+                    }
+                }
+            }
+
+            let backoff = Self::backoff_for_attempt(retry_backoff_base, retry_backoff_cap, attempt);
+            log::info!(
+                "Retrying evaluation of '{}' (attempt {}/{}) in {:.1}s",
+                task.model, attempt + 1, max_attempts, backoff.as_secs_f64()
+            );
+            retry_count.fetch_add(1, Ordering::Relaxed);
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+
+    async fn run_once(
+        transport: &dyn OllamaTransport,
+        evaluation_parser: &EvaluationParser,
+        task: &EvaluationTask,
+        ollama_url: &str,
+    ) -> Result<EvaluationMetrics> {
+        let start = Instant::now();
+        let generated = transport.generate(ollama_url, &task.model, &task.prompt).await?;
+        let duration_seconds = start.elapsed().as_secs();
+
+        let mut metrics = evaluation_parser
+            .parse_evaluation_with_prompt(&generated.response, &task.model, &task.prompt)
+            .context("Failed to parse evaluation content")?;
+
+        metrics.duration_seconds = Some(duration_seconds);
+        metrics.prompt_tokens = generated.prompt_eval_count;
+        metrics.completion_tokens = generated.eval_count;
+
+        Ok(metrics)
+    }
+
+    fn failed_metrics(task: &EvaluationTask, status: EvaluationStatus) -> EvaluationMetrics {
+        let mut metrics = EvaluationMetrics::new_with_prompt(task.model.clone(), task.prompt.clone());
+        metrics.status = status;
+        metrics
+    }
+}
+
+/// Minimal request body for Ollama's `/api/generate` endpoint
+#[derive(Debug, Serialize)]
+struct OllamaGenerateRequest<'a> {
+    model: &'a str,
+    prompt: &'a str,
+    stream: bool,
+}
+
+/// Fields of Ollama's `/api/generate` response relevant to evaluation
+#[derive(Debug, Deserialize)]
+struct OllamaGenerateResponse {
+    response: String,
+    #[serde(default)]
+    prompt_eval_count: Option<u32>,
+    #[serde(default)]
+    eval_count: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_timeout_for_attempt_gives_first_attempt_extra_slack() {
+        let base = Duration::from_secs(10);
+        assert_eq!(ParallelEvaluator::timeout_for_attempt(base, 3.0, 1), Duration::from_secs(30));
+        assert_eq!(ParallelEvaluator::timeout_for_attempt(base, 3.0, 2), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_backoff_for_attempt_doubles_and_caps() {
+        let base = Duration::from_secs(1);
+        let cap = Duration::from_secs(4);
+
+        assert_eq!(ParallelEvaluator::backoff_for_attempt(base, cap, 1), Duration::from_secs(1));
+        assert_eq!(ParallelEvaluator::backoff_for_attempt(base, cap, 2), Duration::from_secs(2));
+        assert_eq!(ParallelEvaluator::backoff_for_attempt(base, cap, 3), Duration::from_secs(4));
+        assert_eq!(ParallelEvaluator::backoff_for_attempt(base, cap, 10), Duration::from_secs(4));
+    }
+
+    /// One scripted outcome for `MockTransport::generate`
+    enum MockStep {
+        Fail,
+        MalformedJson,
+        Sleep(Duration),
+        Success,
+    }
+
+    /// Transport driven by a fixed script of outcomes, one per call, so
+    /// retry/backoff/timeout/degradation behavior can be tested offline
+    struct MockTransport {
+        script: Mutex<VecDeque<MockStep>>,
+    }
+
+    impl MockTransport {
+        fn new(script: Vec<MockStep>) -> Self {
+            Self { script: Mutex::new(script.into_iter().collect()) }
+        }
+    }
+
+    impl OllamaTransport for MockTransport {
+        fn generate<'a>(
+            &'a self,
+            _ollama_url: &'a str,
+            _model: &'a str,
+            _prompt: &'a str,
+        ) -> Pin<Box<dyn Future<Output = Result<RawGenerateResponse>> + Send + 'a>> {
+            let step = self.script.lock().unwrap().pop_front();
+            Box::pin(async move {
+                match step {
+                    None | Some(MockStep::Fail) => anyhow::bail!("mock transient failure"),
+                    Some(MockStep::MalformedJson) => anyhow::bail!("mock malformed JSON response"),
+                    Some(MockStep::Sleep(duration)) => {
+                        tokio::time::sleep(duration).await;
+                        Ok(RawGenerateResponse { response: "**Score: 9/10**".to_string(), prompt_eval_count: Some(5), eval_count: Some(10) })
+                    }
+                    Some(MockStep::Success) => {
+                        Ok(RawGenerateResponse { response: "**Score: 9/10**".to_string(), prompt_eval_count: Some(5), eval_count: Some(10) })
+                    }
+                }
+            })
+        }
+    }
+
+    fn test_config() -> PerformanceConfig {
+        PerformanceConfig {
+            evaluation_timeout_seconds: 60,
+            timeout_wall_clock_factor: 1.0,
+            max_evaluation_retries: 2,
+            retry_backoff_base_ms: 1,
+            retry_backoff_cap_ms: 2,
+            ..PerformanceConfig::default()
+        }
+    }
+
+    fn test_task() -> EvaluationTask {
+        EvaluationTask { model: "llama3.2:3b".to_string(), prompt: "Write a function".to_string(), cache_key: String::new() }
+    }
+
+    #[tokio::test]
+    async fn test_succeeds_on_first_attempt() {
+        let evaluator = ParallelEvaluator::with_transport(
+            &test_config(),
+            Arc::new(MockTransport::new(vec![MockStep::Success])),
+        );
+
+        let metrics = evaluator.evaluate_one(&test_task(), "http://localhost:11434").await;
+
+        assert!(matches!(metrics.status, EvaluationStatus::Success));
+        assert_eq!(metrics.score, Some(9));
+        assert_eq!(evaluator.retry_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_failures_before_succeeding() {
+        let evaluator = ParallelEvaluator::with_transport(
+            &test_config(),
+            Arc::new(MockTransport::new(vec![MockStep::Fail, MockStep::MalformedJson, MockStep::Success])),
+        );
+
+        let metrics = evaluator.evaluate_one(&test_task(), "http://localhost:11434").await;
+
+        assert!(matches!(metrics.status, EvaluationStatus::Success));
+        assert_eq!(evaluator.retry_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_degrades_to_failed_after_exhausting_retries() {
+        let evaluator = ParallelEvaluator::with_transport(
+            &test_config(),
+            Arc::new(MockTransport::new(vec![MockStep::Fail, MockStep::Fail, MockStep::Fail])),
+        );
+
+        let metrics = evaluator.evaluate_one(&test_task(), "http://localhost:11434").await;
+
+        assert!(matches!(metrics.status, EvaluationStatus::Failed));
+    }
+
+    #[tokio::test]
+    async fn test_degrades_to_timeout_when_model_hangs() {
+        let mut config = test_config();
+        config.evaluation_timeout_seconds = 0;
+        config.max_evaluation_retries = 0;
+
+        let evaluator = ParallelEvaluator::with_transport(
+            &config,
+            Arc::new(MockTransport::new(vec![MockStep::Sleep(Duration::from_millis(50))])),
+        );
+
+        let metrics = evaluator.evaluate_one(&test_task(), "http://localhost:11434").await;
+
+        assert!(matches!(metrics.status, EvaluationStatus::Timeout));
+        assert_eq!(evaluator.timeout_count(), 1);
+    }
+}
@@ -0,0 +1,211 @@
+/// Pluggable profiling hooks around the phases of `evaluate_models_optimized`
+///
+/// `PerformanceEvaluationCoordinator` calls `on_phase_start`/`on_phase_end`
+/// around each phase (warm-up, cache check, parallel execution, memory
+/// cleanup) regardless of which `Profiler` is configured, so external
+/// samplers can be plugged in without the coordinator hard-coding them.
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use serde::Serialize;
+use tokio::task::JoinHandle;
+
+use super::memory::MemoryManager;
+
+/// Which `Profiler` implementation `PerformanceEvaluationCoordinator` should
+/// build from `PerformanceConfig`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfilerKind {
+    /// No profiling; zero overhead (the default)
+    NoOp,
+    /// Sample CPU and memory usage during each phase
+    SystemMonitor,
+}
+
+impl Default for ProfilerKind {
+    fn default() -> Self {
+        ProfilerKind::NoOp
+    }
+}
+
+impl ProfilerKind {
+    /// Build the `Profiler` this variant names
+    pub fn build(self) -> Arc<dyn Profiler> {
+        match self {
+            ProfilerKind::NoOp => Arc::new(NoOpProfiler),
+            ProfilerKind::SystemMonitor => Arc::new(SystemMonitorProfiler::new()),
+        }
+    }
+}
+
+/// One phase's collected resource-usage series, as sampled by
+/// `SystemMonitorProfiler` while that phase was running
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct PhaseProfile {
+    pub phase: String,
+    pub duration_secs: f64,
+    /// RSS samples (MB) taken at `SystemMonitorProfiler::SAMPLE_INTERVAL` while the phase ran
+    pub memory_mb_samples: Vec<f64>,
+    /// CPU time samples (seconds of user+system time consumed since the previous sample)
+    pub cpu_seconds_samples: Vec<f64>,
+}
+
+/// Hooks invoked around each phase of `evaluate_models_optimized`
+pub trait Profiler: Send + Sync {
+    /// Called immediately before a phase begins
+    fn on_phase_start(&self, phase: &str);
+    /// Called immediately after a phase completes, with its wall-clock duration
+    fn on_phase_end(&self, phase: &str, duration_secs: f64);
+    /// Every phase profile collected so far; empty for profilers that don't sample
+    fn phase_profiles(&self) -> Vec<PhaseProfile>;
+}
+
+/// Profiler that does nothing; the default so profiling has zero overhead
+/// unless a caller opts in via `PerformanceConfig`
+#[derive(Debug, Default)]
+pub struct NoOpProfiler;
+
+impl Profiler for NoOpProfiler {
+    fn on_phase_start(&self, _phase: &str) {}
+    fn on_phase_end(&self, _phase: &str, _duration_secs: f64) {}
+    fn phase_profiles(&self) -> Vec<PhaseProfile> {
+        Vec::new()
+    }
+}
+
+/// Lightweight profiler that samples process CPU and memory usage at a
+/// fixed interval while a phase is running, and attaches the series to that
+/// phase's `PhaseProfile`
+pub struct SystemMonitorProfiler {
+    sample_interval: Duration,
+    active: Mutex<Option<ActiveSampler>>,
+    profiles: Mutex<Vec<PhaseProfile>>,
+}
+
+struct ActiveSampler {
+    phase: String,
+    handle: JoinHandle<()>,
+    memory_mb_samples: Arc<Mutex<Vec<f64>>>,
+    cpu_seconds_samples: Arc<Mutex<Vec<f64>>>,
+}
+
+impl SystemMonitorProfiler {
+    /// How often the background sampler takes a reading while a phase runs
+    const SAMPLE_INTERVAL: Duration = Duration::from_millis(200);
+
+    pub fn new() -> Self {
+        Self {
+            sample_interval: Self::SAMPLE_INTERVAL,
+            active: Mutex::new(None),
+            profiles: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl Default for SystemMonitorProfiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Profiler for SystemMonitorProfiler {
+    fn on_phase_start(&self, phase: &str) {
+        let mut active = self.active.lock().unwrap();
+        if active.is_some() {
+            // Nested/overlapping phases aren't expected; keep sampling the outer one.
+            return;
+        }
+
+        let memory_mb_samples = Arc::new(Mutex::new(Vec::new()));
+        let cpu_seconds_samples = Arc::new(Mutex::new(Vec::new()));
+        let interval = self.sample_interval;
+
+        let memory_handle = memory_mb_samples.clone();
+        let cpu_handle = cpu_seconds_samples.clone();
+        let handle = tokio::spawn(async move {
+            let mut last_cpu_seconds = MemoryManager::sample_cpu_seconds();
+            loop {
+                tokio::time::sleep(interval).await;
+
+                memory_handle.lock().unwrap().push(MemoryManager::sample_rss_mb());
+
+                let cpu_seconds = MemoryManager::sample_cpu_seconds();
+                cpu_handle.lock().unwrap().push((cpu_seconds - last_cpu_seconds).max(0.0));
+                last_cpu_seconds = cpu_seconds;
+            }
+        });
+
+        *active = Some(ActiveSampler {
+            phase: phase.to_string(),
+            handle,
+            memory_mb_samples,
+            cpu_seconds_samples,
+        });
+    }
+
+    fn on_phase_end(&self, phase: &str, duration_secs: f64) {
+        let sampler = {
+            let mut active = self.active.lock().unwrap();
+            match active.take() {
+                Some(sampler) if sampler.phase == phase => sampler,
+                Some(other) => {
+                    // A mismatched end (e.g. a phase never started); put it back untouched.
+                    *active = Some(other);
+                    return;
+                }
+                None => return,
+            }
+        };
+
+        sampler.handle.abort();
+
+        self.profiles.lock().unwrap().push(PhaseProfile {
+            phase: sampler.phase,
+            duration_secs,
+            memory_mb_samples: sampler.memory_mb_samples.lock().unwrap().clone(),
+            cpu_seconds_samples: sampler.cpu_seconds_samples.lock().unwrap().clone(),
+        });
+    }
+
+    fn phase_profiles(&self) -> Vec<PhaseProfile> {
+        self.profiles.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noop_profiler_collects_nothing() {
+        let profiler = NoOpProfiler;
+        profiler.on_phase_start("warm-up");
+        profiler.on_phase_end("warm-up", 1.0);
+
+        assert!(profiler.phase_profiles().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_system_monitor_profiler_records_a_profile_per_phase() {
+        let profiler = SystemMonitorProfiler::new();
+
+        profiler.on_phase_start("warm-up");
+        tokio::time::sleep(Duration::from_millis(450)).await;
+        profiler.on_phase_end("warm-up", 0.45);
+
+        let profiles = profiler.phase_profiles();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].phase, "warm-up");
+        assert!(!profiles[0].memory_mb_samples.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_mismatched_phase_end_is_ignored() {
+        let profiler = SystemMonitorProfiler::new();
+        profiler.on_phase_start("warm-up");
+        profiler.on_phase_end("cache-check", 0.1);
+
+        assert!(profiler.phase_profiles().is_empty());
+    }
+}
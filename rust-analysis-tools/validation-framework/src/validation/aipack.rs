@@ -2,12 +2,34 @@
 ///
 /// This module provides utilities for validating AIPACK configuration files.
 /// It ensures the configuration is correct and ready for evaluation.
+///
+/// Configuration is assembled from one or more `ConfigSource`s layered over a
+/// set of built-in defaults, in the order given to `AipackValidator::new`: a
+/// source later in the list overrides keys set by an earlier one. This lets a
+/// base `.aipack/config.toml` be overridden per-run by environment variables
+/// without editing the file.
 
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 use serde::Deserialize;
+use serde_json::Value;
 use anyhow::{Result, Context};
 
+/// One layer of AIPACK configuration to merge, in precedence order
+#[derive(Debug, Clone)]
+pub enum ConfigSource {
+    /// A config file on disk; format (TOML, YAML, or JSON) is inferred from
+    /// its extension, defaulting to TOML if unrecognized. A missing file is
+    /// skipped rather than treated as an error; a file that fails to parse
+    /// is fatal.
+    File(PathBuf),
+    /// Environment variables beginning with `prefix`. `COAGENT_OPTIONS_MODEL`
+    /// maps onto `options.model` by lower-casing and splitting the remainder
+    /// on `_` into nested keys.
+    Environment { prefix: String },
+}
+
 /// AIPACK configuration structure for deserialization
 #[derive(Debug, Deserialize)]
 struct AipackConfig {
@@ -16,6 +38,23 @@ struct AipackConfig {
     models: HashMap<String, String>,
 }
 
+impl AipackConfig {
+    /// Every distinct model name the config references: the default model
+    /// plus every alias target in `models`, deduplicated
+    fn configured_models(&self) -> Vec<String> {
+        let mut seen = std::collections::HashSet::new();
+        let mut models = Vec::new();
+
+        for model in std::iter::once(&self.options.model).chain(self.models.values()) {
+            if seen.insert(model.clone()) {
+                models.push(model.clone());
+            }
+        }
+
+        models
+    }
+}
+
 /// Options section for AIPACK configuration
 #[derive(Debug, Deserialize)]
 struct AipackOptions {
@@ -29,11 +68,36 @@ pub struct AipackValidationResult {
     pub is_valid: bool,
     /// Default model specified in the configuration
     pub default_model: Option<String>,
+    /// Which source ultimately supplied `default_model` (e.g. a file path,
+    /// `"environment (COAGENT_*)"`, or `"built-in defaults"`), for `--verbose`
+    /// provenance output
+    pub default_model_source: Option<String>,
     /// Error messages encountered during validation
     pub error_messages: Vec<String>,
+    /// Every model name the config references: the default model plus every
+    /// alias target in `models`, deduplicated. Empty if the config failed to parse.
+    pub configured_models: Vec<String>,
+    /// Which `configured_models` are confirmed present in Ollama; populated
+    /// by `cross_check_models`, empty until then
+    pub available_models: Vec<String>,
+    /// Which `configured_models` are not present in Ollama; populated by
+    /// `cross_check_models`, empty until then
+    pub missing_models: Vec<String>,
 }
 
 impl AipackValidationResult {
+    /// Cross-check `configured_models` against the models Ollama actually
+    /// has available, populating `available_models` and `missing_models`
+    pub fn cross_check_models(&mut self, ollama_available_models: &[String]) {
+        let (available, missing) = self.configured_models
+            .iter()
+            .cloned()
+            .partition(|model| ollama_available_models.contains(model));
+
+        self.available_models = available;
+        self.missing_models = missing;
+    }
+
     /// Convert validation result to metadata
     pub fn into_metadata(self) -> HashMap<String, String> {
         let mut metadata = HashMap::new();
@@ -41,46 +105,197 @@ impl AipackValidationResult {
         if let Some(model) = self.default_model {
             metadata.insert("default_model".to_string(), model);
         }
+        if let Some(source) = self.default_model_source {
+            metadata.insert("default_model_source".to_string(), source);
+        }
         if !self.error_messages.is_empty() {
             metadata.insert("error_messages".to_string(), self.error_messages.join("; "));
         }
+        if !self.configured_models.is_empty() {
+            metadata.insert("configured_models".to_string(), self.configured_models.join(","));
+        }
+        if !self.available_models.is_empty() {
+            metadata.insert("configured_models_available".to_string(), self.available_models.join(","));
+        }
+        if !self.missing_models.is_empty() {
+            metadata.insert("configured_models_missing".to_string(), self.missing_models.join(","));
+        }
         metadata
     }
 }
 
 /// AIPACK configuration validator
 pub struct AipackValidator {
-    config_path: String,
+    sources: Vec<ConfigSource>,
 }
 
 impl AipackValidator {
-    /// Create a new AIPACK configuration validator
-    pub fn new(config_path: &str) -> Self {
-        Self {
-            config_path: config_path.to_string(),
-        }
+    /// Create a new AIPACK configuration validator from a layered list of
+    /// sources, merged over the built-in defaults in the order given
+    pub fn new(sources: Vec<ConfigSource>) -> Self {
+        Self { sources }
+    }
+
+    /// Convenience constructor mirroring the single-file API this validator
+    /// used to have: `config_path` layered over `COAGENT_`-prefixed
+    /// environment overrides
+    pub fn from_config_path(config_path: &str) -> Self {
+        Self::new(vec![
+            ConfigSource::File(PathBuf::from(config_path)),
+            ConfigSource::Environment { prefix: "COAGENT_".to_string() },
+        ])
     }
 
-    /// Validate the AIPACK configuration file
+    /// Validate the merged AIPACK configuration
     pub async fn validate_config(&self) -> Result<AipackValidationResult> {
-        let config_content = fs::read_to_string(&self.config_path).await
-            .context("Failed to read AIPACK configuration file")?;
+        let mut merged = builtin_defaults();
+        let mut default_model_source = "built-in defaults".to_string();
+
+        for source in &self.sources {
+            let Some((label, layer)) = Self::load_layer(source).await? else {
+                continue;
+            };
+
+            if model_override(&layer).is_some() {
+                default_model_source = label;
+            }
 
-        let config: Result<AipackConfig, _> = toml::from_str(&config_content);
+            merge_into(&mut merged, layer);
+        }
+
+        let config: Result<AipackConfig, _> = serde_json::from_value(merged);
 
         match config {
-            Ok(cfg) => Ok(AipackValidationResult {
-                is_valid: true,
-                default_model: Some(cfg.options.model),
-                error_messages: Vec::new(),
-            }),
+            Ok(cfg) => {
+                let configured_models = cfg.configured_models();
+                Ok(AipackValidationResult {
+                    is_valid: true,
+                    default_model: Some(cfg.options.model),
+                    default_model_source: Some(default_model_source),
+                    error_messages: Vec::new(),
+                    configured_models,
+                    available_models: Vec::new(),
+                    missing_models: Vec::new(),
+                })
+            },
             Err(e) => Ok(AipackValidationResult {
                 is_valid: false,
                 default_model: None,
+                default_model_source: None,
                 error_messages: vec![format!("Configuration parsing error: {}", e)],
+                configured_models: Vec::new(),
+                available_models: Vec::new(),
+                missing_models: Vec::new(),
             }),
         }
     }
+
+    /// Load one source into a labeled JSON value, or `None` if it's a file
+    /// source that doesn't exist
+    async fn load_layer(source: &ConfigSource) -> Result<Option<(String, Value)>> {
+        match source {
+            ConfigSource::File(path) => {
+                let content = match fs::read_to_string(path).await {
+                    Ok(content) => content,
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                    Err(e) => {
+                        return Err(e).with_context(|| {
+                            format!("Failed to read AIPACK configuration file {}", path.display())
+                        })
+                    }
+                };
+
+                let value = parse_by_extension(path, &content).with_context(|| {
+                    format!("Failed to parse AIPACK configuration file {}", path.display())
+                })?;
+
+                Ok(Some((path.display().to_string(), value)))
+            }
+            ConfigSource::Environment { prefix } => {
+                Ok(Some((format!("environment ({prefix}*)"), env_layer(prefix))))
+            }
+        }
+    }
+}
+
+/// The configuration applied before any `ConfigSource` is merged in
+fn builtin_defaults() -> Value {
+    serde_json::json!({
+        "options": { "model": "llama3.2:3b" },
+        "models": {},
+    })
+}
+
+/// Parse `content` using the format implied by `path`'s extension,
+/// defaulting to TOML for an unrecognized or missing extension
+fn parse_by_extension(path: &Path, content: &str) -> Result<Value> {
+    match path.extension().and_then(|ext| ext.to_str()).unwrap_or("").to_ascii_lowercase().as_str() {
+        "yaml" | "yml" => serde_yaml::from_str(content).context("Invalid YAML"),
+        "json" => serde_json::from_str(content).context("Invalid JSON"),
+        _ => toml::from_str(content).context("Invalid TOML"),
+    }
+}
+
+/// Turn every `prefix`-prefixed environment variable into a nested JSON
+/// object, e.g. `COAGENT_OPTIONS_MODEL=foo` becomes `{"options": {"model": "foo"}}`
+fn env_layer(prefix: &str) -> Value {
+    let mut root = Value::Object(serde_json::Map::new());
+
+    for (key, value) in std::env::vars() {
+        let Some(rest) = key.strip_prefix(prefix) else { continue };
+        if rest.is_empty() {
+            continue;
+        }
+
+        let path: Vec<String> = rest.split('_').map(|segment| segment.to_ascii_lowercase()).collect();
+        set_path(&mut root, &path, Value::String(value));
+    }
+
+    root
+}
+
+/// Insert `value` at the nested object path `path`, creating intermediate
+/// objects as needed
+fn set_path(root: &mut Value, path: &[String], value: Value) {
+    let Value::Object(map) = root else { return };
+
+    if path.len() == 1 {
+        map.insert(path[0].clone(), value);
+        return;
+    }
+
+    let child = map
+        .entry(path[0].clone())
+        .or_insert_with(|| Value::Object(serde_json::Map::new()));
+    set_path(child, &path[1..], value);
+}
+
+/// The value at `options.model`, if `layer` sets it
+fn model_override(layer: &Value) -> Option<&str> {
+    layer.get("options")?.get("model")?.as_str()
+}
+
+/// Recursively merge `overlay` into `base`: objects are merged key-wise,
+/// everything else (scalars, arrays) is replaced wholesale by the overlay
+fn merge_into(base: &mut Value, overlay: Value) {
+    match overlay {
+        Value::Object(overlay_map) => {
+            if !base.is_object() {
+                *base = Value::Object(serde_json::Map::new());
+            }
+            let Value::Object(base_map) = base else { unreachable!() };
+
+            for (key, overlay_value) in overlay_map {
+                match base_map.get_mut(&key) {
+                    Some(existing) => merge_into(existing, overlay_value),
+                    None => {
+                        base_map.insert(key, overlay_value);
+                    }
+                }
+            }
+        }
+        other => *base = other,
+    }
 }
 
 #[cfg(test)]
@@ -97,7 +312,7 @@ mod tests {
         let mut file = fs::File::create(&config_path).await.expect("Unable to create file");
         file.write_all(b"[options]\nmodel = \"codellama:7b\"").await.expect("Unable to write data");
 
-        let validator = AipackValidator::new(config_path.to_str().unwrap());
+        let validator = AipackValidator::new(vec![ConfigSource::File(config_path)]);
         let result = validator.validate_config().await.expect("Validation failed");
 
         assert!(result.is_valid);
@@ -112,10 +327,114 @@ mod tests {
         let mut file = fs::File::create(&config_path).await.expect("Unable to create file");
         file.write_all(b"[not_options]\nmodel = \"codellama:7b\"").await.expect("Unable to write data");
 
-        let validator = AipackValidator::new(config_path.to_str().unwrap());
+        let validator = AipackValidator::new(vec![ConfigSource::File(config_path)]);
         let result = validator.validate_config().await.expect("Validation failed");
 
         assert!(!result.is_valid);
     }
-}
 
+    #[tokio::test]
+    async fn test_missing_file_source_falls_back_to_defaults() {
+        let validator = AipackValidator::new(vec![ConfigSource::File(PathBuf::from(
+            "/nonexistent/aipack/config.toml",
+        ))]);
+        let result = validator.validate_config().await.expect("Validation failed");
+
+        assert!(result.is_valid);
+        assert_eq!(result.default_model.unwrap(), "llama3.2:3b");
+        assert_eq!(result.default_model_source.unwrap(), "built-in defaults");
+    }
+
+    #[tokio::test]
+    async fn test_malformed_file_source_is_fatal_with_path_in_error() {
+        let tmp_dir = env::temp_dir();
+        let config_path = tmp_dir.join("aipack_malformed.toml");
+
+        let mut file = fs::File::create(&config_path).await.expect("Unable to create file");
+        file.write_all(b"not = [valid toml").await.expect("Unable to write data");
+
+        let validator = AipackValidator::new(vec![ConfigSource::File(config_path.clone())]);
+        let err = validator.validate_config().await.expect_err("Expected malformed file to be fatal");
+
+        assert!(err.to_string().contains(&config_path.display().to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_later_file_source_overrides_model_and_reports_provenance() {
+        let tmp_dir = env::temp_dir();
+        let base_path = tmp_dir.join("aipack_override_base.toml");
+        let override_path = tmp_dir.join("aipack_override_override.json");
+
+        fs::File::create(&base_path).await.expect("Unable to create file")
+            .write_all(b"[options]\nmodel = \"codellama:7b\"\n[models]\nfast = \"llama3.2:3b\"")
+            .await.expect("Unable to write data");
+        fs::File::create(&override_path).await.expect("Unable to create file")
+            .write_all(br#"{"options": {"model": "mixtral:8x7b"}}"#)
+            .await.expect("Unable to write data");
+
+        let validator = AipackValidator::new(vec![
+            ConfigSource::File(base_path),
+            ConfigSource::File(override_path.clone()),
+        ]);
+        let result = validator.validate_config().await.expect("Validation failed");
+
+        assert_eq!(result.default_model.unwrap(), "mixtral:8x7b");
+        assert_eq!(result.default_model_source.unwrap(), override_path.display().to_string());
+    }
+
+    #[tokio::test]
+    async fn test_environment_source_overrides_model_by_nested_key() {
+        let tmp_dir = env::temp_dir();
+        let config_path = tmp_dir.join("aipack_env_override.toml");
+
+        fs::File::create(&config_path).await.expect("Unable to create file")
+            .write_all(b"[options]\nmodel = \"codellama:7b\"")
+            .await.expect("Unable to write data");
+
+        env::set_var("AIPACK_TEST_OPTIONS_MODEL", "mixtral:8x7b");
+        let validator = AipackValidator::new(vec![
+            ConfigSource::File(config_path),
+            ConfigSource::Environment { prefix: "AIPACK_TEST_".to_string() },
+        ]);
+        let result = validator.validate_config().await.expect("Validation failed");
+        env::remove_var("AIPACK_TEST_OPTIONS_MODEL");
+
+        assert_eq!(result.default_model.unwrap(), "mixtral:8x7b");
+        assert_eq!(result.default_model_source.unwrap(), "environment (AIPACK_TEST_*)");
+    }
+
+    #[tokio::test]
+    async fn test_configured_models_includes_default_and_alias_targets() {
+        let tmp_dir = env::temp_dir();
+        let config_path = tmp_dir.join("aipack_configured_models.toml");
+
+        fs::File::create(&config_path).await.expect("Unable to create file")
+            .write_all(b"[options]\nmodel = \"codellama:7b\"\n[models]\nfast = \"llama3.2:3b\"\nalso_default = \"codellama:7b\"")
+            .await.expect("Unable to write data");
+
+        let validator = AipackValidator::new(vec![ConfigSource::File(config_path)]);
+        let result = validator.validate_config().await.expect("Validation failed");
+
+        assert_eq!(result.configured_models.len(), 2);
+        assert!(result.configured_models.contains(&"codellama:7b".to_string()));
+        assert!(result.configured_models.contains(&"llama3.2:3b".to_string()));
+    }
+
+    #[test]
+    fn test_cross_check_models_partitions_available_and_missing() {
+        let mut result = AipackValidationResult {
+            is_valid: true,
+            default_model: Some("codellama:7b".to_string()),
+            default_model_source: Some("built-in defaults".to_string()),
+            error_messages: Vec::new(),
+            configured_models: vec!["codellama:7b".to_string(), "llama3.2:3b".to_string()],
+            available_models: Vec::new(),
+            missing_models: Vec::new(),
+        };
+
+        result.cross_check_models(&["codellama:7b".to_string()]);
+
+        assert_eq!(result.available_models, vec!["codellama:7b".to_string()]);
+        assert_eq!(result.missing_models, vec!["llama3.2:3b".to_string()]);
+    }
+}
@@ -1,25 +1,32 @@
 /// # Disk Space Validation Module
-/// 
+///
 /// This module checks for sufficient available disk space to store evaluation outputs.
 /// It provides a set of utility functions for:
-/// - Checking available disk space for a given directory
+/// - Checking available disk space for a given directory via a direct
+///   `statvfs`/`GetDiskFreeSpaceEx` syscall (no `df` subprocess, so it works
+///   on Windows and isn't sensitive to locale/column formatting)
 /// - Estimating space requirements based on model outputs
+/// - Tracking outstanding `DiskReservation`s so concurrent evaluation jobs
+///   writing to the same volume don't each assume the full free space is theirs
 
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::{Mutex, OnceLock};
 use anyhow::{Result, Context};
 use tokio::fs;
-use tokio::process::Command;
 
 /// Disk space validation result
 #[derive(Debug, Clone)]
 pub struct DiskValidationResult {
-    /// Available space in GB
+    /// Physical available space in GB, straight from the syscall
     pub available_space_gb: f64,
     /// Required space in GB
     pub required_space_gb: f64,
     /// Whether the required space is available
     pub sufficient_space: bool,
+    /// `available_space_gb` minus every outstanding `DiskReservation` taken
+    /// against this path, via `DiskValidator::reserve`
+    pub reservation_adjusted_free_gb: f64,
 }
 
 impl DiskValidationResult {
@@ -29,10 +36,45 @@ impl DiskValidationResult {
         metadata.insert("available_space_gb".to_string(), format!("{:.2}", self.available_space_gb));
         metadata.insert("required_space_gb".to_string(), format!("{:.2}", self.required_space_gb));
         metadata.insert("sufficient_space".to_string(), self.sufficient_space.to_string());
+        metadata.insert("reservation_adjusted_free_gb".to_string(), format!("{:.2}", self.reservation_adjusted_free_gb));
         metadata
     }
 }
 
+/// Process-wide outstanding disk-space reservations, keyed by the
+/// canonicalized path they were taken against
+static RESERVATIONS: OnceLock<Mutex<HashMap<String, f64>>> = OnceLock::new();
+
+fn reservations() -> &'static Mutex<HashMap<String, f64>> {
+    RESERVATIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// RAII guard for an outstanding disk-space reservation taken via
+/// `DiskValidator::reserve`; releases its share of the reservation when dropped
+pub struct DiskReservation {
+    key: String,
+    gb: f64,
+}
+
+impl DiskReservation {
+    /// Amount of space, in GB, this guard has reserved
+    pub fn gb(&self) -> f64 {
+        self.gb
+    }
+}
+
+impl Drop for DiskReservation {
+    fn drop(&mut self) {
+        let mut reservations = reservations().lock().unwrap();
+        if let Some(remaining) = reservations.get_mut(&self.key) {
+            *remaining -= self.gb;
+            if *remaining <= 0.0 {
+                reservations.remove(&self.key);
+            }
+        }
+    }
+}
+
 /// Disk space validation logic
 pub struct DiskValidator;
 
@@ -41,21 +83,63 @@ impl DiskValidator {
     pub fn new() -> Self {
         DiskValidator
     }
-    
-    /// Validate if the directory has sufficient space for evaluation results
+
+    /// Reserve `gb` gigabytes of space under `path` so concurrent callers
+    /// checking the same volume see it as already spoken for. Dropping the
+    /// returned guard releases the reservation.
+    pub fn reserve(&self, path: &str, gb: f64) -> DiskReservation {
+        let key = Self::reservation_key(path);
+        let mut reservations = reservations().lock().unwrap();
+        *reservations.entry(key.clone()).or_insert(0.0) += gb;
+        DiskReservation { key, gb }
+    }
+
+    /// Canonicalize `path` to key reservations by the real filesystem
+    /// location rather than whatever relative string a caller passed;
+    /// falls back to the raw string if the path doesn't exist yet
+    fn reservation_key(path: &str) -> String {
+        std::fs::canonicalize(path)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|_| path.to_string())
+    }
+
+    fn reserved_gb(path: &str) -> f64 {
+        let key = Self::reservation_key(path);
+        reservations().lock().unwrap().get(&key).copied().unwrap_or(0.0)
+    }
+
+    /// Validate if the directory has sufficient physical space for
+    /// evaluation results. `sufficient_space` is based on physical free
+    /// space alone; see `validate_space_with_reservations` for a variant
+    /// that treats already-reserved space as unavailable.
     pub async fn validate_space(&self, path: &str, space_needed_gb: f64) -> Result<DiskValidationResult> {
         let path_obj = Path::new(path);
         let available_space_gb = self.get_available_disk_space_gb(path_obj).await?;
+        let reserved_gb = Self::reserved_gb(path);
+        let reservation_adjusted_free_gb = (available_space_gb - reserved_gb).max(0.0);
         let sufficient_space = available_space_gb >= space_needed_gb;
 
         Ok(DiskValidationResult {
             available_space_gb,
             required_space_gb: space_needed_gb,
             sufficient_space,
+            reservation_adjusted_free_gb,
         })
     }
 
-    /// Get the available disk space in GB for a given path
+    /// `validate_space`, but `sufficient_space` reflects
+    /// `reservation_adjusted_free_gb` instead of the raw physical free
+    /// space, so a job that already reserved space elsewhere on this
+    /// volume doesn't pass validation based on space another job has claimed
+    pub async fn validate_space_with_reservations(&self, path: &str, space_needed_gb: f64) -> Result<DiskValidationResult> {
+        let mut result = self.validate_space(path, space_needed_gb).await?;
+        result.sufficient_space = result.reservation_adjusted_free_gb >= space_needed_gb;
+        Ok(result)
+    }
+
+    /// Get the available disk space in GB for a given path via a direct
+    /// `statvfs`/`GetDiskFreeSpaceEx` syscall, run on a blocking thread
+    /// since it isn't `async` itself
     async fn get_available_disk_space_gb(&self, path: &Path) -> Result<f64> {
         // Create directory if it doesn't exist for checking
         if !path.exists() {
@@ -64,59 +148,121 @@ impl DiskValidator {
             }
         }
 
-        // Use `df` command which works on both Linux and macOS
-        let output = Command::new("df")
-            .arg("-k") // Output in 1K blocks
-            .arg(path)
-            .output()
+        let path = path.to_path_buf();
+        let free_bytes = tokio::task::spawn_blocking(move || free_bytes(&path))
             .await
-            .context("Failed to execute df command")?;
+            .context("Disk space query task panicked")?
+            .context("Failed to query available disk space")?;
 
-        if !output.status.success() {
-            anyhow::bail!("df command failed: {}", String::from_utf8_lossy(&output.stderr));
-        }
+        // Convert from bytes to GB (1024^3 bytes per GB)
+        Ok(free_bytes as f64 / 1_073_741_824.0)
+    }
+}
+
+impl Default for DiskValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-        let output_str = String::from_utf8(output.stdout)
-            .context("Failed to parse df output as UTF-8")?;
+/// Query free bytes available to the current user on the volume containing
+/// `path`, via the platform's native filesystem-stats syscall
+#[cfg(unix)]
+fn free_bytes(path: &Path) -> Result<u64> {
+    use std::ffi::CString;
+    use std::mem::MaybeUninit;
+    use std::os::unix::ffi::OsStrExt;
 
-        // Parse df output - second line contains the data we need
-        let lines: Vec<&str> = output_str.trim().lines().collect();
-        if lines.len() < 2 {
-            anyhow::bail!("Unexpected df output format");
-        }
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .context("Path contains an interior NUL byte")?;
 
-        // df output format: Filesystem 1K-blocks Used Available Use% Mounted-on
-        // We want the "Available" column (index 3)
-        let data_line = lines[1];
-        let fields: Vec<&str> = data_line.split_whitespace().collect();
-        
-        if fields.len() < 4 {
-            anyhow::bail!("Unable to parse available space from df output");
-        }
+    let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+    let result = unsafe { libc::statvfs(c_path.as_ptr(), stat.as_mut_ptr()) };
+    if result != 0 {
+        anyhow::bail!("statvfs failed for {}: {}", path.display(), std::io::Error::last_os_error());
+    }
+
+    let stat = unsafe { stat.assume_init() };
+    Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(windows)]
+fn free_bytes(path: &Path) -> Result<u64> {
+    use std::os::windows::ffi::OsStrExt;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetDiskFreeSpaceExW(
+            lp_directory_name: *const u16,
+            lp_free_bytes_available_to_caller: *mut u64,
+            lp_total_number_of_bytes: *mut u64,
+            lp_total_number_of_free_bytes: *mut u64,
+        ) -> i32;
+    }
 
-        let available_kb = fields[3].parse::<f64>()
-            .context("Failed to parse available space as number")?;
-        
-        // Convert from KB to GB
-        Ok(available_kb / 1_048_576.0) // 1024 * 1024 = 1,048,576 KB per GB
+    let wide_path: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+
+    let mut free_available_to_caller: u64 = 0;
+    let mut total_bytes: u64 = 0;
+    let mut total_free_bytes: u64 = 0;
+
+    let ok = unsafe {
+        GetDiskFreeSpaceExW(
+            wide_path.as_ptr(),
+            &mut free_available_to_caller,
+            &mut total_bytes,
+            &mut total_free_bytes,
+        )
+    };
+
+    if ok == 0 {
+        anyhow::bail!("GetDiskFreeSpaceExW failed for {}: {}", path.display(), std::io::Error::last_os_error());
     }
+
+    Ok(free_available_to_caller)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn free_bytes(path: &Path) -> Result<u64> {
+    anyhow::bail!("Disk space query is not supported on this platform (path: {})", path.display())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_disk_validation_result_metadata() {
         let result = DiskValidationResult {
             available_space_gb: 20.0,
             required_space_gb: 5.0,
             sufficient_space: true,
+            reservation_adjusted_free_gb: 15.0,
         };
 
         let metadata = result.into_metadata();
         assert_eq!(metadata["available_space_gb"], "20.00");
         assert_eq!(metadata["required_space_gb"], "5.00");
         assert!(metadata["sufficient_space"].parse::<bool>().unwrap());
+        assert_eq!(metadata["reservation_adjusted_free_gb"], "15.00");
+    }
+
+    #[test]
+    fn test_reservation_reduces_adjusted_free_space_and_releases_on_drop() {
+        let validator = DiskValidator::new();
+        let path = std::env::temp_dir();
+        let path_str = path.to_string_lossy().to_string();
+
+        let before = DiskValidator::reserved_gb(&path_str);
+        let reservation = validator.reserve(&path_str, 2.5);
+        assert_eq!(reservation.gb(), 2.5);
+        assert_eq!(DiskValidator::reserved_gb(&path_str), before + 2.5);
+
+        drop(reservation);
+        assert_eq!(DiskValidator::reserved_gb(&path_str), before);
     }
 }
@@ -11,17 +11,19 @@
 use std::collections::HashMap;
 use std::time::Duration;
 use serde::{Deserialize, Serialize};
-use anyhow::Result;
+use anyhow::{Result, Context};
 use reqwest::Client;
 use jiff::Zoned;
 
 pub mod ollama;
 pub mod disk;
 pub mod aipack;
+pub mod monitor;
 
 use ollama::OllamaValidator;
 use disk::DiskValidator;
-use aipack::AipackValidator;
+use aipack::{AipackValidator, ConfigSource};
+use data_compactor::{DataCompactor, EfficientCompactor, GraphCompactor, TrulyEfficientCompactor};
 
 /// Comprehensive validation result containing all pre-flight check outcomes
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +40,11 @@ pub struct ValidationResult {
     pub critical_issues: Vec<String>,
     /// Warnings that should be addressed
     pub warnings: Vec<String>,
+    /// Per-model timeout (seconds) recommended by
+    /// `ollama::compute_timeout_for_model`, derived from each model's
+    /// on-disk size and measured cold-start latency rather than the fixed
+    /// `timeout_seconds` baked into the HTTP client
+    pub recommended_model_timeouts: HashMap<String, u64>,
     /// Validation timestamp
     pub timestamp: Zoned,
 }
@@ -57,13 +64,22 @@ pub struct ValidationSummary {
     pub ollama_response_time_ms: u64,
     /// AIPACK configuration validation status
     pub aipack_config_valid: bool,
+    /// Whether every registered compactor round-tripped
+    /// `validate_compaction_roundtrip`'s built-in sample without losing
+    /// resources/keys or inflating the payload
+    pub compaction_roundtrip_passed: bool,
 }
 
 /// Individual validation check results
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationChecks {
-    /// Ollama service connectivity and health
+    /// Ollama service connectivity and health, aggregated across every
+    /// configured endpoint
     pub ollama_service: CheckResult,
+    /// Per-endpoint breakdown backing `ollama_service`, one entry per
+    /// configured host in order (`ollama_url` first, then
+    /// `additional_ollama_endpoints`)
+    pub ollama_endpoints: Vec<CheckResult>,
     /// Model availability verification
     pub model_availability: CheckResult,
     /// Disk space validation
@@ -72,6 +88,11 @@ pub struct ValidationChecks {
     pub aipack_config: CheckResult,
     /// System resource validation
     pub system_resources: CheckResult,
+    /// Real-inference health check of available models
+    pub model_health: CheckResult,
+    /// Compaction subsystem round-trip self-check (see
+    /// `ValidationCoordinator::validate_compaction_roundtrip`)
+    pub compaction_roundtrip: CheckResult,
 }
 
 /// Individual check result with detailed information
@@ -89,6 +110,14 @@ pub struct CheckResult {
     pub metadata: HashMap<String, String>,
 }
 
+/// One compactor's outcome from `ValidationCoordinator::validate_compaction_roundtrip`
+#[derive(Debug, Clone)]
+struct CompactorRoundtripOutcome {
+    name: &'static str,
+    compression_ratio: f32,
+    round_trips: bool,
+}
+
 /// Validation recommendation for configuration optimization
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationRecommendation {
@@ -111,12 +140,49 @@ pub struct ValidationConfig {
     pub timeout_seconds: u64,
     /// Minimum required disk space in GB
     pub min_disk_space_gb: f64,
+    /// Minimum required available system memory in GB
+    pub min_memory_gb: f64,
     /// Path to AIPACK configuration file
     pub aipack_config_path: String,
     /// Output directory for evaluation results
     pub output_directory: String,
     /// Whether to perform deep model validation
     pub deep_model_validation: bool,
+    /// Whether to run the real-inference model health check stage at all
+    /// (disable for fast, static-only runs, e.g. via `--no-health`)
+    pub health_check_enabled: bool,
+    /// Per-model timeout for the health-check prompt, in seconds
+    pub health_check_timeout_seconds: u64,
+    /// Whether to warm up every available model and measure cold-start
+    /// latency (two real inference requests per model, so off by default)
+    pub warmup_models: bool,
+    /// Cold-start latency, in milliseconds, above which a model produces a
+    /// "Performance" recommendation
+    pub cold_start_warn_ms: u64,
+    /// Context window (`num_ctx`) the evaluation intends to run models at;
+    /// any available model whose detected context window is smaller raises
+    /// a critical issue rather than risking silent prompt truncation
+    pub num_ctx: usize,
+    /// Extra Ollama endpoints to validate alongside `ollama_url`, for
+    /// deployments that spread models across several hosts. Each is checked
+    /// concurrently with the primary; `available_models` becomes the union
+    /// across every reachable host, and a model missing from every host is
+    /// reported the same way a missing model on a single host would be.
+    pub additional_ollama_endpoints: Vec<String>,
+    /// Base contribution (seconds) to `compute_timeout_for_model`'s
+    /// per-model timeout formula, before size-based scaling
+    pub model_timeout_base_seconds: u64,
+    /// Additional seconds of timeout per GB of model size
+    pub model_timeout_seconds_per_gb: f64,
+    /// Lowest per-model timeout `compute_timeout_for_model` will return
+    pub model_timeout_floor_seconds: u64,
+    /// Highest per-model timeout `compute_timeout_for_model` will return
+    pub model_timeout_ceiling_seconds: u64,
+    /// Whether to run every registered compactor over a small built-in
+    /// sample and assert `reconstruct` preserves the resource count and key
+    /// set, catching a broken or regressed compactor before it affects a
+    /// real evaluation run
+    pub compaction_roundtrip_check: bool,
 }
 
 impl Default for ValidationConfig {
@@ -125,18 +191,114 @@ impl Default for ValidationConfig {
             ollama_url: "http://localhost:11434".to_string(),
             timeout_seconds: 30,
             min_disk_space_gb: 5.0,
+            min_memory_gb: 1.0,
             aipack_config_path: ".aipack/config.toml".to_string(),
             output_directory: "evaluation-results".to_string(),
             deep_model_validation: true,
+            health_check_enabled: true,
+            health_check_timeout_seconds: 10,
+            warmup_models: false,
+            cold_start_warn_ms: 5_000,
+            num_ctx: 4096,
+            additional_ollama_endpoints: Vec::new(),
+            model_timeout_base_seconds: 30,
+            model_timeout_seconds_per_gb: 2.0,
+            model_timeout_floor_seconds: 30,
+            model_timeout_ceiling_seconds: 600,
+            compaction_roundtrip_check: true,
+        }
+    }
+}
+
+/// Named sets of validation thresholds tuned for where the binary is running
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValidationProfile {
+    /// Constrained CI containers: loosened disk/memory minimums, and skips
+    /// `deep_model_validation` since CI rarely has every model pulled
+    CI,
+    /// A developer's own machine - the existing defaults
+    Local,
+    /// Full eval machines: tightened minimums, nothing skipped
+    Production,
+}
+
+impl ValidationProfile {
+    /// Parse `COAGENT_VALIDATION_PROFILE` ("ci", "local", "production"),
+    /// case-insensitively; anything unrecognized falls back to `Local`
+    fn from_env_str(value: &str) -> Self {
+        match value.to_ascii_lowercase().as_str() {
+            "ci" => ValidationProfile::CI,
+            "production" | "prod" => ValidationProfile::Production,
+            _ => ValidationProfile::Local,
+        }
+    }
+
+    /// Apply this profile's thresholds onto `config`
+    fn apply(self, config: &mut ValidationConfig) {
+        match self {
+            ValidationProfile::CI => {
+                config.min_disk_space_gb = 2.0;
+                config.min_memory_gb = 0.5;
+                config.deep_model_validation = false;
+            },
+            ValidationProfile::Local => {
+                // `ValidationConfig::default()` already fits local development
+            },
+            ValidationProfile::Production => {
+                config.min_disk_space_gb = 20.0;
+                config.min_memory_gb = 8.0;
+                config.deep_model_validation = true;
+            },
+        }
+    }
+}
+
+impl ValidationConfig {
+    /// Build a config for the current environment: start from
+    /// `ValidationProfile`'s thresholds (selected via
+    /// `COAGENT_VALIDATION_PROFILE`, defaulting to `Local`), then layer
+    /// `COAGENT_OLLAMA_URL`, `COAGENT_MIN_DISK_GB`, `COAGENT_MIN_MEMORY_GB`
+    /// and `COAGENT_TIMEOUT_SECS` on top where set - so the same binary
+    /// runs unmodified in a constrained CI container or on a full eval
+    /// machine.
+    pub fn from_env() -> Self {
+        let profile = std::env::var("COAGENT_VALIDATION_PROFILE")
+            .map(|value| ValidationProfile::from_env_str(&value))
+            .unwrap_or(ValidationProfile::Local);
+
+        let mut config = Self::default();
+        profile.apply(&mut config);
+
+        if let Ok(url) = std::env::var("COAGENT_OLLAMA_URL") {
+            config.ollama_url = url;
         }
+        if let Some(value) = env_parsed("COAGENT_MIN_DISK_GB") {
+            config.min_disk_space_gb = value;
+        }
+        if let Some(value) = env_parsed("COAGENT_MIN_MEMORY_GB") {
+            config.min_memory_gb = value;
+        }
+        if let Some(value) = env_parsed("COAGENT_TIMEOUT_SECS") {
+            config.timeout_seconds = value;
+        }
+
+        config
     }
 }
 
+/// Read and parse an environment variable, treating unset or unparsable as absent
+fn env_parsed<T: std::str::FromStr>(key: &str) -> Option<T> {
+    std::env::var(key).ok()?.parse().ok()
+}
+
 /// Main validation coordinator that orchestrates all pre-flight checks
 pub struct ValidationCoordinator {
     config: ValidationConfig,
     http_client: Client,
     ollama_validator: OllamaValidator,
+    /// One validator per `ValidationConfig::additional_ollama_endpoints` entry,
+    /// paired with its URL, checked alongside `ollama_validator`
+    additional_ollama_validators: Vec<(String, OllamaValidator)>,
     disk_validator: DiskValidator,
     aipack_validator: AipackValidator,
 }
@@ -155,38 +317,47 @@ impl ValidationCoordinator {
             .expect("Failed to create HTTP client");
 
         let ollama_validator = OllamaValidator::new(&config.ollama_url, &http_client);
+        let additional_ollama_validators = config.additional_ollama_endpoints.iter()
+            .map(|url| (url.clone(), OllamaValidator::new(url, &http_client)))
+            .collect();
         let disk_validator = DiskValidator::new();
-        let aipack_validator = AipackValidator::new(&config.aipack_config_path);
+        let aipack_validator = AipackValidator::new(vec![
+            ConfigSource::File(std::path::PathBuf::from(&config.aipack_config_path)),
+            ConfigSource::Environment { prefix: "COAGENT_".to_string() },
+        ]);
 
         Self {
             config,
             http_client,
             ollama_validator,
+            additional_ollama_validators,
             disk_validator,
             aipack_validator,
         }
     }
 
     /// Run comprehensive validation of all prerequisites for evaluation
-    /// 
-    /// This is the main entry point for validation that coordinates all checks:
-    /// 1. Ollama service health and responsiveness
-    /// 2. Model availability and size detection
-    /// 3. Disk space validation for outputs
-    /// 4. AIPACK configuration validity
-    /// 5. System resource checks
-    /// 
+    ///
+    /// Validation proceeds in two ordered stages, following the
+    /// validate-config/build-components/run-health-checks model: a "static"
+    /// stage of concurrent, no-inference checks (Ollama reachability, disk
+    /// space, AIPACK config, system resources), followed by a "health" stage
+    /// that sends a real prompt to every available model. The health stage
+    /// only runs if the static stage found no critical problems - there's
+    /// nothing useful to probe if, say, Ollama itself isn't reachable - and
+    /// can also be disabled outright via `ValidationConfig::health_check_enabled`.
+    ///
     /// Returns detailed validation results with recommendations
     pub async fn validate_all(&self) -> Result<ValidationResult> {
         let start_time = jiff::Zoned::now();
-        
+
         log::info!("🔍 Starting comprehensive evaluation pre-flight validation");
-        
-        // Run all validation checks concurrently for efficiency
+
+        // Stage 1: static checks, run concurrently since none depend on another
         let (
-            ollama_result,
+            (ollama_result, available_models, ollama_endpoints),
             disk_result,
-            aipack_result,
+            (mut aipack_result, configured_models),
             system_result
         ) = tokio::try_join!(
             self.validate_ollama_service(),
@@ -195,22 +366,69 @@ impl ValidationCoordinator {
             self.validate_system_resources()
         )?;
 
+        // Cross-check AIPACK's configured models (default + every `models`
+        // alias target) against what Ollama actually has, now that both
+        // results are in. Skipped if Ollama itself wasn't reachable, since
+        // every model would otherwise be reported as missing.
+        if ollama_result.passed {
+            let (aipack_models_available, aipack_models_missing): (Vec<String>, Vec<String>) = configured_models
+                .into_iter()
+                .partition(|model| available_models.contains(model));
+
+            if !aipack_models_missing.is_empty() {
+                aipack_result.metadata.insert("configured_models_missing".to_string(), aipack_models_missing.join(","));
+            }
+            if !aipack_models_available.is_empty() {
+                aipack_result.metadata.insert("configured_models_available".to_string(), aipack_models_available.join(","));
+            }
+        }
+
+        // Compaction subsystem self-check: a small, synchronous, CPU-only
+        // sample round trip, so it runs alongside the static stage rather
+        // than inside `tokio::try_join!` or gating the health-check stage
+        let compaction_roundtrip = if self.config.compaction_roundtrip_check {
+            self.validate_compaction_roundtrip()
+        } else {
+            Self::skipped_compaction_roundtrip_check("skipped: compaction round-trip check disabled via configuration")
+        };
+
+        let static_stage_passed = ollama_result.passed && disk_result.passed && aipack_result.passed;
+
+        // Stage 2: health check, only meaningful once the static stage is clean
+        let model_health = if !static_stage_passed {
+            Self::skipped_health_check("skipped: an earlier validation stage reported a critical failure")
+        } else if !self.config.health_check_enabled {
+            Self::skipped_health_check("skipped: health checks disabled via configuration")
+        } else {
+            self.validate_model_health(&available_models).await?
+        };
+
         // Determine overall validation status
-        let is_valid = ollama_result.passed && 
-                      disk_result.passed && 
-                      aipack_result.passed && 
-                      system_result.passed;
+        let is_valid = ollama_result.passed &&
+                      disk_result.passed &&
+                      aipack_result.passed &&
+                      system_result.passed &&
+                      model_health.passed &&
+                      compaction_roundtrip.passed;
 
         let checks = ValidationChecks {
             ollama_service: ollama_result.clone(),
+            ollama_endpoints,
             model_availability: ollama_result.clone(), // Model availability is part of Ollama validation
             disk_space: disk_result.clone(),
             aipack_config: aipack_result.clone(),
             system_resources: system_result,
+            model_health,
+            compaction_roundtrip,
         };
 
+        // Derive a per-model timeout from size and cold-start latency,
+        // rather than leaving every model stuck with one fixed HTTP timeout
+        let recommended_model_timeouts = self.compute_model_timeouts(&checks);
+
         // Generate recommendations based on check results
-        let recommendations = self.generate_recommendations(&checks).await?;
+        let mut recommendations = self.generate_recommendations(&checks).await?;
+        recommendations.extend(self.timeout_recommendations(&recommended_model_timeouts));
 
         // Collect critical issues and warnings
         let (critical_issues, warnings) = self.categorize_issues(&checks);
@@ -220,7 +438,7 @@ impl ValidationCoordinator {
 
         let end_time = jiff::Zoned::now();
         let duration_ms = start_time.until(&end_time).unwrap().total(jiff::Unit::Millisecond).unwrap_or(0.0) as u64;
-        
+
         let result = ValidationResult {
             is_valid,
             summary,
@@ -228,6 +446,7 @@ impl ValidationCoordinator {
             recommendations,
             critical_issues,
             warnings,
+            recommended_model_timeouts,
             timestamp: start_time,
         };
         
@@ -240,32 +459,228 @@ impl ValidationCoordinator {
         Ok(result)
     }
 
-    /// Validate Ollama service health and model availability
-    async fn validate_ollama_service(&self) -> Result<CheckResult> {
+    /// Validate Ollama service health and model availability across every
+    /// configured endpoint (`ollama_url` plus `additional_ollama_endpoints`),
+    /// concurrently, returning an aggregate `CheckResult`, the union of
+    /// models available on at least one reachable host for the health-check
+    /// stage, and the per-host breakdown backing the aggregate
+    async fn validate_ollama_service(&self) -> Result<(CheckResult, Vec<String>, Vec<CheckResult>)> {
         let start_time = std::time::Instant::now();
-        
-        match self.ollama_validator.validate_service().await {
-            Ok(ollama_result) => {
-                Ok(CheckResult {
-                    passed: true,
+        let warmup_models = self.config.warmup_models;
+        let num_ctx = self.config.num_ctx;
+
+        // `tokio::spawn` starts each task immediately, so awaiting the
+        // handles below in order is still concurrent - a slow or
+        // unreachable host doesn't hold up the others.
+        let mut handles = Vec::with_capacity(1 + self.additional_ollama_validators.len());
+        for (url, validator) in std::iter::once((&self.config.ollama_url, &self.ollama_validator))
+            .chain(self.additional_ollama_validators.iter().map(|(url, validator)| (url, validator)))
+        {
+            let url = url.clone();
+            let validator = validator.clone();
+            handles.push((
+                url,
+                tokio::spawn(async move { Self::check_ollama_endpoint(&validator, warmup_models, num_ctx).await }),
+            ));
+        }
+
+        let mut endpoint_checks: Vec<(String, CheckResult, Vec<String>)> = Vec::with_capacity(handles.len());
+        for (url, handle) in handles {
+            let (check, models) = handle.await.context("Ollama endpoint check task panicked")??;
+            endpoint_checks.push((url, check, models));
+        }
+
+        // Union of models available on at least one reachable host, and
+        // which host(s) can serve each one
+        let mut available_models: Vec<String> = Vec::new();
+        let mut model_hosts: HashMap<String, Vec<String>> = HashMap::new();
+        for (url, check, models) in &endpoint_checks {
+            if !check.passed {
+                continue;
+            }
+            for model in models {
+                if !available_models.contains(model) {
+                    available_models.push(model.clone());
+                }
+                model_hosts.entry(model.clone()).or_default().push(url.clone());
+            }
+        }
+
+        let hosts_passed = endpoint_checks.iter().filter(|(_, check, _)| check.passed).count();
+        let any_host_passed = hosts_passed > 0;
+
+        // Single-endpoint deployments (the common case, and the only case
+        // before `additional_ollama_endpoints` existed) keep the exact
+        // message and metadata their one `CheckResult` already produced
+        let (message, mut metadata) = if endpoint_checks.len() == 1 {
+            let (_, check, _) = &endpoint_checks[0];
+            (check.message.clone(), check.metadata.clone())
+        } else {
+            let mut metadata = endpoint_checks[0].1.metadata.clone();
+            for (_, check, _) in &endpoint_checks[1..] {
+                for (key, value) in &check.metadata {
+                    metadata.entry(key.clone())
+                        .and_modify(|existing| *existing = format!("{},{}", existing, value))
+                        .or_insert_with(|| value.clone());
+                }
+            }
+
+            let message = format!(
+                "{}/{} Ollama host(s) reachable, {} unique model(s) available",
+                hosts_passed, endpoint_checks.len(), available_models.len()
+            );
+
+            (message, metadata)
+        };
+
+        if !model_hosts.is_empty() {
+            let serialized: Vec<String> = model_hosts.iter()
+                .map(|(model, hosts)| format!("{}={}", model, hosts.join("|")))
+                .collect();
+            metadata.insert("model_hosts".to_string(), serialized.join(","));
+        }
+
+        let check = CheckResult {
+            passed: any_host_passed,
+            description: "Ollama service health and model availability".to_string(),
+            message,
+            duration_ms: start_time.elapsed().as_millis() as u64,
+            metadata,
+        };
+
+        let ollama_endpoints = endpoint_checks.into_iter().map(|(_, check, _)| check).collect();
+
+        Ok((check, available_models, ollama_endpoints))
+    }
+
+    /// Validate a single Ollama endpoint: service reachability, model
+    /// warm-up (if enabled), and context-window requirements
+    async fn check_ollama_endpoint(
+        validator: &OllamaValidator,
+        warmup_models: bool,
+        num_ctx: usize,
+    ) -> Result<(CheckResult, Vec<String>)> {
+        let start_time = std::time::Instant::now();
+
+        match validator.validate_service_with_warmup(warmup_models).await {
+            Ok(mut ollama_result) => {
+                let available_models: Vec<String> = ollama_result.available_models.iter()
+                    .map(|model| model.name.clone())
+                    .collect();
+
+                let context_check = validator
+                    .validate_context_requirements(&mut ollama_result, num_ctx)
+                    .await?;
+
+                let overflowing = !context_check.overflowing_models.is_empty();
+                let message = if overflowing {
+                    format!(
+                        "Service responsive, {} models available, {} below required num_ctx={}",
+                        ollama_result.available_models.len(),
+                        context_check.overflowing_models.len(),
+                        num_ctx
+                    )
+                } else {
+                    format!("Service responsive, {} models available", ollama_result.available_models.len())
+                };
+
+                let mut metadata = ollama_result.into_metadata();
+                if !context_check.overflowing_models.is_empty() {
+                    metadata.insert("context_window_overflow".to_string(), context_check.overflowing_models.join(","));
+                }
+                if !context_check.undetectable_models.is_empty() {
+                    metadata.insert("context_window_undetectable".to_string(), context_check.undetectable_models.join(","));
+                }
+
+                let check = CheckResult {
+                    // A model whose detected context window can't fit the
+                    // configured num_ctx would silently truncate prompts
+                    // during evaluation, so it fails this check outright
+                    // rather than merely warning.
+                    passed: !overflowing,
                     description: "Ollama service health and model availability".to_string(),
-                    message: format!("Service responsive, {} models available", ollama_result.available_models.len()),
+                    message,
                     duration_ms: start_time.elapsed().as_millis() as u64,
-                    metadata: ollama_result.into_metadata(),
-                })
+                    metadata,
+                };
+
+                Ok((check, available_models))
             },
             Err(e) => {
-                Ok(CheckResult {
+                let check = CheckResult {
                     passed: false,
                     description: "Ollama service health and model availability".to_string(),
                     message: format!("Ollama validation failed: {}", e),
                     duration_ms: start_time.elapsed().as_millis() as u64,
                     metadata: HashMap::new(),
-                })
+                };
+
+                Ok((check, Vec::new()))
             }
         }
     }
 
+    /// Send a real, deterministic prompt to every available model and
+    /// record per-model latency and success, catching models that are
+    /// present on disk but fail to load or run
+    async fn validate_model_health(&self, models: &[String]) -> Result<CheckResult> {
+        let start_time = std::time::Instant::now();
+        let per_model_timeout = Duration::from_secs(self.config.health_check_timeout_seconds);
+
+        let mut metadata = HashMap::new();
+        let mut healthy_count = 0;
+        let mut unhealthy_models = Vec::new();
+
+        for model in models {
+            let check = self.ollama_validator.check_model_health(model, per_model_timeout).await;
+
+            if check.healthy {
+                healthy_count += 1;
+                if let Some(latency_ms) = check.latency_ms {
+                    metadata.insert(format!("latency_ms::{}", model), latency_ms.to_string());
+                }
+            } else {
+                unhealthy_models.push(model.clone());
+                if let Some(error) = check.error {
+                    metadata.insert(format!("error::{}", model), error);
+                }
+            }
+        }
+
+        metadata.insert("models_checked".to_string(), models.len().to_string());
+        metadata.insert("models_healthy".to_string(), healthy_count.to_string());
+        if !unhealthy_models.is_empty() {
+            metadata.insert("unhealthy_models".to_string(), unhealthy_models.join(","));
+        }
+
+        Ok(CheckResult {
+            passed: unhealthy_models.is_empty(),
+            description: "Real-inference health check of available models".to_string(),
+            message: if models.is_empty() {
+                "No available models to health-check".to_string()
+            } else {
+                format!("{}/{} models responded to a health-check prompt", healthy_count, models.len())
+            },
+            duration_ms: start_time.elapsed().as_millis() as u64,
+            metadata,
+        })
+    }
+
+    /// A passing, zero-duration `CheckResult` for when the health-check
+    /// stage didn't run at all
+    fn skipped_health_check(reason: &str) -> CheckResult {
+        let mut metadata = HashMap::new();
+        metadata.insert("skipped".to_string(), "true".to_string());
+
+        CheckResult {
+            passed: true,
+            description: "Real-inference health check of available models".to_string(),
+            message: reason.to_string(),
+            duration_ms: 0,
+            metadata,
+        }
+    }
+
     /// Validate available disk space for evaluation outputs
     async fn validate_disk_space(&self) -> Result<CheckResult> {
         let start_time = std::time::Instant::now();
@@ -297,31 +712,39 @@ impl ValidationCoordinator {
     }
 
     /// Validate AIPACK configuration file validity
-    async fn validate_aipack_config(&self) -> Result<CheckResult> {
+    /// Validate AIPACK configuration validity, also returning every model it
+    /// references (default plus `models` alias targets) for the subsequent
+    /// Ollama cross-check
+    async fn validate_aipack_config(&self) -> Result<(CheckResult, Vec<String>)> {
         let start_time = std::time::Instant::now();
-        
+
         match self.aipack_validator.validate_config().await {
             Ok(config_result) => {
                 let is_valid = config_result.is_valid;
                 let default_model = config_result.default_model.clone().unwrap_or("None".to_string());
+                let configured_models = config_result.configured_models.clone();
                 let metadata = config_result.into_metadata();
-                
-                Ok(CheckResult {
+
+                let check = CheckResult {
                     passed: is_valid,
                     description: "AIPACK configuration validity".to_string(),
                     message: format!("Config valid: {}, Default model: {}", is_valid, default_model),
                     duration_ms: start_time.elapsed().as_millis() as u64,
                     metadata,
-                })
+                };
+
+                Ok((check, configured_models))
             },
             Err(e) => {
-                Ok(CheckResult {
+                let check = CheckResult {
                     passed: false,
                     description: "AIPACK configuration validity".to_string(),
                     message: format!("AIPACK config validation failed: {}", e),
                     duration_ms: start_time.elapsed().as_millis() as u64,
                     metadata: HashMap::new(),
-                })
+                };
+
+                Ok((check, Vec::new()))
             }
         }
     }
@@ -334,7 +757,8 @@ impl ValidationCoordinator {
         let available_memory = self.get_available_memory().await.unwrap_or(0);
         let cpu_count = num_cpus::get();
         
-        let sufficient_resources = available_memory > 1_000_000_000 && cpu_count > 0; // 1GB minimum
+        let min_memory_bytes = self.config.min_memory_gb * 1_000_000_000.0;
+        let sufficient_resources = available_memory as f64 > min_memory_bytes && cpu_count > 0;
         
         let mut metadata = HashMap::new();
         metadata.insert("available_memory_bytes".to_string(), available_memory.to_string());
@@ -376,6 +800,272 @@ impl ValidationCoordinator {
         Ok(4_000_000_000)
     }
 
+    /// Run every registered compactor (`DataCompactor`, `EfficientCompactor`,
+    /// `TrulyEfficientCompactor`, `GraphCompactor`) over
+    /// `compaction_sample`, asserting `reconstruct` preserves the resource
+    /// count and key set and recording each compactor's compression ratio,
+    /// so a broken or regressed compactor is caught pre-flight rather than
+    /// mid-evaluation
+    fn validate_compaction_roundtrip(&self) -> CheckResult {
+        let start_time = std::time::Instant::now();
+        let sample = Self::compaction_sample();
+        let original_size = serde_json::to_string(&sample).map(|s| s.len()).unwrap_or(0);
+        let expected = Self::resource_signature(&sample);
+
+        let mut data_compactor = DataCompactor::new();
+        let data_outcome = Self::compactor_roundtrip_outcome(
+            "DataCompactor",
+            original_size,
+            &expected,
+            (|| {
+                let compacted = data_compactor.compact_comprehensive_data(&sample)?;
+                let compacted_size = serde_json::to_string(&compacted)?.len();
+                let mut reconstructed = data_compactor.decompress(&compacted)?;
+                // `decompress` keeps the rows grouped by type under
+                // `subresources_grouped`; flatten them back into `subresources`
+                if let Some(serde_json::Value::Object(groups)) = reconstructed.get("subresources_grouped").cloned() {
+                    let rows: Vec<serde_json::Value> = groups.into_iter()
+                        .filter_map(|(_, rows)| match rows {
+                            serde_json::Value::Array(rows) => Some(rows),
+                            _ => None,
+                        })
+                        .flatten()
+                        .collect();
+                    reconstructed["subresources"] = serde_json::Value::Array(rows);
+                }
+                Ok((compacted_size, reconstructed))
+            })(),
+        );
+
+        let mut efficient_compactor = EfficientCompactor::new();
+        let efficient_outcome = Self::compactor_roundtrip_outcome(
+            "EfficientCompactor",
+            original_size,
+            &expected,
+            (|| {
+                let compacted = efficient_compactor.compact_comprehensive_data(&sample)?;
+                let compacted_size = serde_json::to_string(&compacted)?.len();
+                let reconstructed = EfficientCompactor::reconstruct_data(&compacted)?;
+                Ok((compacted_size, reconstructed))
+            })(),
+        );
+
+        let mut truly_efficient_compactor = TrulyEfficientCompactor::new();
+        let truly_efficient_outcome = Self::compactor_roundtrip_outcome(
+            "TrulyEfficientCompactor",
+            original_size,
+            &expected,
+            (|| {
+                let compacted = truly_efficient_compactor.compact(&sample)?;
+                let compacted_size = serde_json::to_string(&compacted)?.len();
+                let reconstructed = TrulyEfficientCompactor::reconstruct(&compacted)?;
+                Ok((compacted_size, reconstructed))
+            })(),
+        );
+
+        let mut graph_compactor = GraphCompactor::new();
+        let graph_outcome = Self::compactor_roundtrip_outcome(
+            "GraphCompactor",
+            original_size,
+            &expected,
+            (|| {
+                let compacted = graph_compactor.compact(&sample)?;
+                let compacted_size = serde_json::to_string(&compacted)?.len();
+                let reconstructed = GraphCompactor::reconstruct(&compacted)?;
+                Ok((compacted_size, reconstructed))
+            })(),
+        );
+
+        let outcomes = [data_outcome, efficient_outcome, truly_efficient_outcome, graph_outcome];
+
+        let mut metadata = HashMap::new();
+        let ratios: Vec<String> = outcomes.iter()
+            .map(|o| format!("{}={:.3}", o.name, o.compression_ratio))
+            .collect();
+        metadata.insert("compactor_compression_ratios".to_string(), ratios.join(","));
+
+        let broken: Vec<&str> = outcomes.iter().filter(|o| !o.round_trips).map(|o| o.name).collect();
+        if !broken.is_empty() {
+            metadata.insert("broken_compactors".to_string(), broken.join(","));
+        }
+
+        let inflating: Vec<&str> = outcomes.iter()
+            .filter(|o| o.round_trips && o.compression_ratio <= 0.0)
+            .map(|o| o.name)
+            .collect();
+        if !inflating.is_empty() {
+            metadata.insert("inflating_compactors".to_string(), inflating.join(","));
+        }
+
+        let passed = broken.is_empty() && inflating.is_empty();
+
+        CheckResult {
+            passed,
+            description: "Compaction subsystem round-trip self-check".to_string(),
+            message: if passed {
+                "All registered compactors round-tripped the built-in sample and compressed it".to_string()
+            } else {
+                format!(
+                    "{} compactor(s) failed to round-trip or inflated the built-in sample",
+                    broken.len() + inflating.len()
+                )
+            },
+            duration_ms: start_time.elapsed().as_millis() as u64,
+            metadata,
+        }
+    }
+
+    /// Turn one compactor's `(compacted_size, reconstructed)` result (or
+    /// error) into a `CompactorRoundtripOutcome`, comparing the
+    /// reconstructed resource signature against `expected`
+    fn compactor_roundtrip_outcome(
+        name: &'static str,
+        original_size: usize,
+        expected: &(usize, Vec<String>),
+        result: Result<(usize, serde_json::Value)>,
+    ) -> CompactorRoundtripOutcome {
+        match result {
+            Ok((compacted_size, reconstructed)) => {
+                let compression_ratio = if original_size > 0 {
+                    (original_size as f32 - compacted_size as f32) / original_size as f32
+                } else {
+                    0.0
+                };
+                CompactorRoundtripOutcome {
+                    name,
+                    compression_ratio,
+                    round_trips: Self::resource_signature(&reconstructed) == *expected,
+                }
+            },
+            Err(_) => CompactorRoundtripOutcome {
+                name,
+                compression_ratio: 0.0,
+                round_trips: false,
+            },
+        }
+    }
+
+    /// `(resource count, sorted deduplicated key set across every resource)`
+    /// for a `{"subresources": [...]}`-shaped document. Compared instead of
+    /// deep equality since several compactors intentionally reorder or
+    /// re-type values (e.g. interned ids) during their round trip.
+    fn resource_signature(data: &serde_json::Value) -> (usize, Vec<String>) {
+        let empty = Vec::new();
+        let subresources = data.get("subresources").and_then(|v| v.as_array()).unwrap_or(&empty);
+
+        let mut keys: Vec<String> = subresources.iter()
+            .filter_map(|r| r.as_object())
+            .flat_map(|obj| obj.keys().cloned())
+            .collect();
+        keys.sort();
+        keys.dedup();
+
+        (subresources.len(), keys)
+    }
+
+    /// Small, self-contained Atomic-Data-shaped sample used purely to
+    /// exercise each compactor's `compact`/`reconstruct` round trip during
+    /// pre-flight validation; not meant to resemble any real evaluation payload
+    fn compaction_sample() -> serde_json::Value {
+        serde_json::json!({
+            "subresources": [
+                {
+                    "url": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/resource/a",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/step",
+                    "https://atomicdata.dev/properties/isA": ["https://atomicdata.dev/classes/Step"],
+                    "https://atomicdata.dev/properties/parent": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/resource/root",
+                    "https://atomicdata.dev/properties/lastCommit": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/commit/1"
+                },
+                {
+                    "url": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/resource/b",
+                    "resource_type": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/class/step",
+                    "https://atomicdata.dev/properties/isA": ["https://atomicdata.dev/classes/Step"],
+                    "https://atomicdata.dev/properties/parent": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/resource/root",
+                    "https://atomicdata.dev/properties/lastCommit": "https://common.terraphim.io/01jxw2jx8qze6yakh4fz24mnhy/commit/2"
+                }
+            ]
+        })
+    }
+
+    /// A passing, zero-duration `CheckResult` for when the compaction
+    /// round-trip check didn't run at all
+    fn skipped_compaction_roundtrip_check(reason: &str) -> CheckResult {
+        let mut metadata = HashMap::new();
+        metadata.insert("skipped".to_string(), "true".to_string());
+
+        CheckResult {
+            passed: true,
+            description: "Compaction subsystem round-trip self-check".to_string(),
+            message: reason.to_string(),
+            duration_ms: 0,
+            metadata,
+        }
+    }
+
+    /// Derive a recommended per-request timeout (seconds) for every
+    /// available model, from the `model_size_bytes`/`cold_start_ms`
+    /// metadata `validate_ollama_service` attaches to its `CheckResult`
+    fn compute_model_timeouts(&self, checks: &ValidationChecks) -> HashMap<String, u64> {
+        let parse_per_model = |metadata_value: &str| -> HashMap<String, u64> {
+            metadata_value
+                .split(',')
+                .filter_map(|entry| {
+                    let (model, value) = entry.split_once('=')?;
+                    Some((model.to_string(), value.parse().ok()?))
+                })
+                .collect()
+        };
+
+        let sizes = checks.ollama_service.metadata.get("model_size_bytes")
+            .map(|v| parse_per_model(v))
+            .unwrap_or_default();
+        let cold_starts = checks.ollama_service.metadata.get("cold_start_ms")
+            .map(|v| parse_per_model(v))
+            .unwrap_or_default();
+
+        let params = ollama::TimeoutParams {
+            base_seconds: self.config.model_timeout_base_seconds,
+            seconds_per_gb: self.config.model_timeout_seconds_per_gb,
+            floor_seconds: self.config.model_timeout_floor_seconds,
+            ceiling_seconds: self.config.model_timeout_ceiling_seconds,
+        };
+
+        sizes.iter()
+            .map(|(model, size_bytes)| {
+                let cold_start_ms = cold_starts.get(model).copied();
+                let timeout = ollama::compute_timeout_for_model(*size_bytes, cold_start_ms, &params);
+                (model.clone(), timeout.as_secs())
+            })
+            .collect()
+    }
+
+    /// Flag models whose computed timeout exceeds the fixed
+    /// `timeout_seconds` the HTTP client was built with, so a downstream
+    /// runner knows to apply a more generous per-model timeout rather than
+    /// the one-size-fits-all client default
+    fn timeout_recommendations(&self, recommended_model_timeouts: &HashMap<String, u64>) -> Vec<ValidationRecommendation> {
+        let mut recommendations = Vec::new();
+
+        for (model, &recommended_seconds) in recommended_model_timeouts {
+            if recommended_seconds > self.config.timeout_seconds {
+                recommendations.push(ValidationRecommendation {
+                    category: "Timeout".to_string(),
+                    description: format!(
+                        "Model '{}' needs an estimated {}s timeout, above the configured {}s client timeout",
+                        model, recommended_seconds, self.config.timeout_seconds
+                    ),
+                    priority: "Medium".to_string(),
+                    action: format!(
+                        "Apply a per-model timeout of at least {}s for '{}' rather than the default client timeout",
+                        recommended_seconds, model
+                    ),
+                });
+            }
+        }
+
+        recommendations
+    }
+
     /// Generate actionable recommendations based on validation results
     async fn generate_recommendations(&self, checks: &ValidationChecks) -> Result<Vec<ValidationRecommendation>> {
         let mut recommendations = Vec::new();
@@ -410,6 +1100,32 @@ impl ValidationCoordinator {
             });
         }
 
+        // AIPACK-configured model recommendations: surface exactly which
+        // configured aliases are unusable, rather than discovering it only
+        // when an evaluation run fails mid-flight
+        if let Some(missing) = checks.aipack_config.metadata.get("configured_models_missing") {
+            for model in missing.split(',') {
+                recommendations.push(ValidationRecommendation {
+                    category: "Model Availability".to_string(),
+                    description: format!("AIPACK-configured model '{}' is not installed", model),
+                    priority: "High".to_string(),
+                    action: format!("ollama pull {}", model),
+                });
+            }
+        }
+
+        // Model health recommendations
+        if !checks.model_health.passed {
+            if let Some(unhealthy) = checks.model_health.metadata.get("unhealthy_models") {
+                recommendations.push(ValidationRecommendation {
+                    category: "Model Health".to_string(),
+                    description: format!("Model(s) present but not responding: {}", unhealthy),
+                    priority: "High".to_string(),
+                    action: "Check 'ollama ps'/'ollama logs' for the affected model(s), or re-pull if the weights are corrupted".to_string(),
+                });
+            }
+        }
+
         // Performance recommendations based on response times
         if let Some(response_time) = checks.ollama_service.metadata.get("response_time_ms") {
             if let Ok(time_ms) = response_time.parse::<u64>() {
@@ -424,6 +1140,101 @@ impl ValidationCoordinator {
             }
         }
 
+        // Cold-start recommendations for models that were warmed up
+        if let Some(cold_starts) = checks.ollama_service.metadata.get("cold_start_ms") {
+            for entry in cold_starts.split(',') {
+                let Some((model, ms)) = entry.split_once('=') else { continue };
+                let Ok(cold_start_ms) = ms.parse::<u64>() else { continue };
+
+                if cold_start_ms > self.config.cold_start_warn_ms {
+                    recommendations.push(ValidationRecommendation {
+                        category: "Performance".to_string(),
+                        description: format!(
+                            "Model '{}' took {}ms to cold-start, above the {}ms threshold",
+                            model, cold_start_ms, self.config.cold_start_warn_ms
+                        ),
+                        priority: "Medium".to_string(),
+                        action: format!(
+                            "Pre-warm '{}' before evaluation or raise its configured timeout",
+                            model
+                        ),
+                    });
+                }
+            }
+        }
+
+        // Context-window recommendations: flag models whose detected window
+        // can't fit the evaluation's configured num_ctx, and models where
+        // the window couldn't be detected at all via `/api/show`
+        if let Some(overflowing) = checks.ollama_service.metadata.get("context_window_overflow") {
+            let context_lengths = checks.ollama_service.metadata.get("context_length_tokens");
+            for model in overflowing.split(',') {
+                let detected = context_lengths
+                    .and_then(|lengths| lengths.split(',').find_map(|entry| {
+                        let (name, ctx) = entry.split_once('=')?;
+                        (name == model).then(|| ctx.to_string())
+                    }));
+
+                recommendations.push(ValidationRecommendation {
+                    category: "Context Window".to_string(),
+                    description: match &detected {
+                        Some(ctx) => format!(
+                            "Model '{}' has a {}-token context window, below the required {} tokens",
+                            model, ctx, self.config.num_ctx
+                        ),
+                        None => format!(
+                            "Model '{}' has a context window below the required {} tokens",
+                            model, self.config.num_ctx
+                        ),
+                    },
+                    priority: "High".to_string(),
+                    action: format!(
+                        "Raise num_ctx for '{}' or choose a model with a larger context window",
+                        model
+                    ),
+                });
+            }
+        }
+
+        if let Some(undetectable) = checks.ollama_service.metadata.get("context_window_undetectable") {
+            for model in undetectable.split(',') {
+                recommendations.push(ValidationRecommendation {
+                    category: "Context Window".to_string(),
+                    description: format!("Could not determine context window for model '{}'", model),
+                    priority: "Medium".to_string(),
+                    action: format!(
+                        "Verify '{}' supports num_ctx={} manually before evaluating",
+                        model, self.config.num_ctx
+                    ),
+                });
+            }
+        }
+
+        // Compaction round-trip recommendations: call out exactly which
+        // compactor(s) failed to reconstruct the sample, or made it bigger,
+        // rather than just reporting "compaction check failed"
+        if let Some(broken) = checks.compaction_roundtrip.metadata.get("broken_compactors") {
+            for name in broken.split(',') {
+                recommendations.push(ValidationRecommendation {
+                    category: "Compaction".to_string(),
+                    description: format!("{} failed to reconstruct the built-in compaction sample", name),
+                    priority: "High".to_string(),
+                    action: format!("Disable {} for this payload shape until the round-trip bug is fixed", name),
+                });
+            }
+        }
+
+        if let Some(inflating) = checks.compaction_roundtrip.metadata.get("inflating_compactors") {
+            for name in inflating.split(',') {
+                recommendations.push(ValidationRecommendation {
+                    category: "Compaction".to_string(),
+                    description: format!("{} inflated the built-in compaction sample instead of compressing it", name),
+                    priority: "Medium".to_string(),
+                    action: format!("Disable {} for this payload shape in favor of a compactor with a positive compression ratio", name),
+                });
+            }
+        }
+
         Ok(recommendations)
     }
 
@@ -433,7 +1244,14 @@ impl ValidationCoordinator {
         let mut warnings = Vec::new();
 
         if !checks.ollama_service.passed {
-            critical_issues.push("Ollama service is not accessible - evaluation cannot proceed".to_string());
+            if let Some(overflowing) = checks.ollama_service.metadata.get("context_window_overflow") {
+                critical_issues.push(format!(
+                    "Model(s) have a context window smaller than the configured num_ctx={}: {}",
+                    self.config.num_ctx, overflowing
+                ));
+            } else {
+                critical_issues.push("Ollama service is not accessible - evaluation cannot proceed".to_string());
+            }
         }
 
         if !checks.aipack_config.passed {
@@ -448,6 +1266,34 @@ impl ValidationCoordinator {
             warnings.push("System resources may be insufficient for optimal performance".to_string());
         }
 
+        if let Some(missing) = checks.aipack_config.metadata.get("configured_models_missing") {
+            warnings.push(format!(
+                "AIPACK-configured model(s) not found in Ollama: {}",
+                missing
+            ));
+        }
+
+        if !checks.model_health.passed {
+            let detail = checks.model_health.metadata.get("unhealthy_models")
+                .cloned()
+                .unwrap_or_else(|| "one or more models".to_string());
+            critical_issues.push(format!(
+                "Model(s) failed the real-inference health check: {}",
+                detail
+            ));
+        }
+
+        if !checks.compaction_roundtrip.passed {
+            let detail = checks.compaction_roundtrip.metadata.get("broken_compactors")
+                .or_else(|| checks.compaction_roundtrip.metadata.get("inflating_compactors"))
+                .cloned()
+                .unwrap_or_else(|| "one or more compactors".to_string());
+            critical_issues.push(format!(
+                "Compaction subsystem round-trip self-check failed: {}",
+                detail
+            ));
+        }
+
         (critical_issues, warnings)
     }
 
@@ -485,6 +1331,7 @@ impl ValidationCoordinator {
             estimated_space_needed_gb,
             ollama_response_time_ms,
             aipack_config_valid: checks.aipack_config.passed,
+            compaction_roundtrip_passed: checks.compaction_roundtrip.passed,
         })
     }
 }
@@ -517,6 +1364,127 @@ mod tests {
         let config = ValidationConfig::default();
         assert_eq!(config.ollama_url, "http://localhost:11434");
         assert_eq!(config.min_disk_space_gb, 5.0);
+        assert_eq!(config.min_memory_gb, 1.0);
         assert_eq!(config.aipack_config_path, ".aipack/config.toml");
+        assert!(config.health_check_enabled);
+        assert_eq!(config.health_check_timeout_seconds, 10);
+        assert!(!config.warmup_models);
+        assert_eq!(config.cold_start_warn_ms, 5_000);
+        assert_eq!(config.num_ctx, 4096);
+        assert!(config.additional_ollama_endpoints.is_empty());
+        assert_eq!(config.model_timeout_base_seconds, 30);
+        assert_eq!(config.model_timeout_seconds_per_gb, 2.0);
+        assert_eq!(config.model_timeout_floor_seconds, 30);
+        assert_eq!(config.model_timeout_ceiling_seconds, 600);
+        assert!(config.compaction_roundtrip_check);
+    }
+
+    #[test]
+    fn test_ci_profile_loosens_minimums_and_skips_deep_validation() {
+        let mut config = ValidationConfig::default();
+        ValidationProfile::CI.apply(&mut config);
+
+        assert!(config.min_disk_space_gb < ValidationConfig::default().min_disk_space_gb);
+        assert!(config.min_memory_gb < ValidationConfig::default().min_memory_gb);
+        assert!(!config.deep_model_validation);
+    }
+
+    #[test]
+    fn test_production_profile_tightens_minimums() {
+        let mut config = ValidationConfig::default();
+        ValidationProfile::Production.apply(&mut config);
+
+        assert!(config.min_disk_space_gb > ValidationConfig::default().min_disk_space_gb);
+        assert!(config.min_memory_gb > ValidationConfig::default().min_memory_gb);
+        assert!(config.deep_model_validation);
+    }
+
+    #[test]
+    fn test_from_env_layers_explicit_overrides_over_profile_defaults() {
+        std::env::set_var("COAGENT_VALIDATION_PROFILE", "ci");
+        std::env::set_var("COAGENT_OLLAMA_URL", "http://example-test-host:11434");
+        std::env::set_var("COAGENT_MIN_DISK_GB", "3.5");
+        std::env::set_var("COAGENT_TIMEOUT_SECS", "45");
+
+        let config = ValidationConfig::from_env();
+
+        std::env::remove_var("COAGENT_VALIDATION_PROFILE");
+        std::env::remove_var("COAGENT_OLLAMA_URL");
+        std::env::remove_var("COAGENT_MIN_DISK_GB");
+        std::env::remove_var("COAGENT_TIMEOUT_SECS");
+
+        assert_eq!(config.ollama_url, "http://example-test-host:11434");
+        assert_eq!(config.min_disk_space_gb, 3.5);
+        assert_eq!(config.timeout_seconds, 45);
+        assert!(!config.deep_model_validation); // from the CI profile, not overridden explicitly
+    }
+
+    #[tokio::test]
+    async fn test_skipped_health_check_passes_without_running() {
+        let check = ValidationCoordinator::skipped_health_check("skipped: disabled via configuration");
+        assert!(check.passed);
+        assert_eq!(check.duration_ms, 0);
+        assert_eq!(check.metadata.get("skipped").map(String::as_str), Some("true"));
+    }
+
+    #[test]
+    fn test_compute_model_timeouts_reads_size_and_cold_start_metadata() {
+        let coordinator = ValidationCoordinator::new();
+
+        let mut ollama_service = CheckResult {
+            passed: true,
+            description: "Ollama service health and model availability".to_string(),
+            message: String::new(),
+            duration_ms: 0,
+            metadata: HashMap::new(),
+        };
+        ollama_service.metadata.insert("model_size_bytes".to_string(), "llama3.2:3b=2147483648".to_string());
+        ollama_service.metadata.insert("cold_start_ms".to_string(), "llama3.2:3b=90000".to_string());
+
+        let checks = ValidationChecks {
+            ollama_service,
+            ollama_endpoints: Vec::new(),
+            model_availability: ValidationCoordinator::skipped_health_check("n/a"),
+            disk_space: ValidationCoordinator::skipped_health_check("n/a"),
+            aipack_config: ValidationCoordinator::skipped_health_check("n/a"),
+            system_resources: ValidationCoordinator::skipped_health_check("n/a"),
+            model_health: ValidationCoordinator::skipped_health_check("n/a"),
+            compaction_roundtrip: ValidationCoordinator::skipped_compaction_roundtrip_check("n/a"),
+        };
+
+        let timeouts = coordinator.compute_model_timeouts(&checks);
+        // Size alone implies ~34s (30 base + 2*2GB), but the 90s cold start wins out
+        assert_eq!(timeouts.get("llama3.2:3b"), Some(&90));
+    }
+
+    #[test]
+    fn test_compaction_roundtrip_passes_for_every_registered_compactor() {
+        let coordinator = ValidationCoordinator::new();
+        let check = coordinator.validate_compaction_roundtrip();
+
+        assert!(check.passed, "expected all compactors to round-trip the built-in sample: {:?}", check.metadata);
+        let ratios = check.metadata.get("compactor_compression_ratios").expect("ratios recorded");
+        for name in ["DataCompactor", "EfficientCompactor", "TrulyEfficientCompactor", "GraphCompactor"] {
+            assert!(ratios.contains(name), "missing ratio for {name}: {ratios}");
+        }
+        assert!(check.metadata.get("broken_compactors").is_none());
+    }
+
+    #[test]
+    fn test_skipped_compaction_roundtrip_check_passes_without_running() {
+        let check = ValidationCoordinator::skipped_compaction_roundtrip_check("skipped: disabled via configuration");
+        assert!(check.passed);
+        assert_eq!(check.duration_ms, 0);
+        assert_eq!(check.metadata.get("skipped").map(String::as_str), Some("true"));
+    }
+
+    #[test]
+    fn test_resource_signature_ignores_value_types_but_not_key_set() {
+        let a = serde_json::json!({ "subresources": [{"url": 1, "parent": 2}] });
+        let b = serde_json::json!({ "subresources": [{"url": "a", "parent": "b"}] });
+        let c = serde_json::json!({ "subresources": [{"url": "a"}] });
+
+        assert_eq!(ValidationCoordinator::resource_signature(&a), ValidationCoordinator::resource_signature(&b));
+        assert_ne!(ValidationCoordinator::resource_signature(&a), ValidationCoordinator::resource_signature(&c));
     }
 }
@@ -0,0 +1,285 @@
+/// # System Resource Monitor
+///
+/// `validate_system_resources` in the parent module takes a single snapshot
+/// before an evaluation starts. This module complements it with
+/// `SystemMonitorService`, a background sampler that keeps watching memory,
+/// CPU utilization and disk I/O for the lifetime of a run, so a slow or
+/// failed evaluation can be correlated against resource pressure instead of
+/// a one-time pass/fail. Modeled on `performance::memory::MemoryManager`'s
+/// sample-and-accumulate approach, but reading `/proc` directly rather than
+/// `getrusage`, since this tracks whole-system resources rather than just
+/// the current process.
+///
+/// Network counters are not sampled yet; memory, CPU and disk cover the
+/// cases that have actually caused confusing eval failures so far.
+use std::sync::Arc;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+/// How often memory/CPU/disk samples are taken while a monitor is running
+const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Running min/avg/max accumulator for a single sampled metric
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct MetricStats {
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+    pub sample_count: u64,
+}
+
+impl MetricStats {
+    fn record(&mut self, value: f64) {
+        if self.sample_count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+
+        self.avg = (self.avg * self.sample_count as f64 + value) / (self.sample_count + 1) as f64;
+        self.sample_count += 1;
+    }
+}
+
+/// Min/avg/max resource utilization accumulated over an evaluation run,
+/// attachable to the eval report for after-the-fact correlation
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ResourceTimeline {
+    pub memory_available_bytes: MetricStats,
+    pub cpu_utilization_percent: MetricStats,
+    pub disk_read_bytes_per_sec: MetricStats,
+    pub disk_write_bytes_per_sec: MetricStats,
+}
+
+/// Previous-sample state needed to turn cumulative `/proc` counters into
+/// per-interval deltas (CPU ticks and disk sectors are both monotonic totals)
+#[derive(Default)]
+struct PreviousSample {
+    cpu_idle_total_ticks: Option<(u64, u64)>,
+    disk_sectors: Option<(u64, u64)>,
+}
+
+struct MonitorState {
+    timeline: ResourceTimeline,
+    previous: PreviousSample,
+}
+
+/// Background sampler tracking memory, CPU and disk I/O for the lifetime of
+/// an evaluation run
+pub struct SystemMonitorService {
+    sample_interval: Duration,
+    state: Arc<Mutex<MonitorState>>,
+    sampler: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl Default for SystemMonitorService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemMonitorService {
+    /// Create a monitor sampling at `DEFAULT_SAMPLE_INTERVAL` (1s)
+    pub fn new() -> Self {
+        Self::with_interval(DEFAULT_SAMPLE_INTERVAL)
+    }
+
+    /// Create a monitor sampling at a custom interval
+    pub fn with_interval(sample_interval: Duration) -> Self {
+        Self {
+            sample_interval,
+            state: Arc::new(Mutex::new(MonitorState {
+                timeline: ResourceTimeline::default(),
+                previous: PreviousSample::default(),
+            })),
+            sampler: Mutex::new(None),
+        }
+    }
+
+    /// Start the background sampling task; a no-op if already running.
+    /// Call `stop` once the evaluation run that needed it completes.
+    pub async fn start(&self) {
+        let mut sampler = self.sampler.lock().await;
+        if sampler.is_some() {
+            return;
+        }
+
+        let interval = self.sample_interval;
+        let state = self.state.clone();
+
+        *sampler = Some(tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                Self::sample_once(&state, interval).await;
+            }
+        }));
+    }
+
+    /// Stop the background sampling task started by `start`
+    pub async fn stop(&self) {
+        if let Some(handle) = self.sampler.lock().await.take() {
+            handle.abort();
+        }
+    }
+
+    /// The min/avg/max resource usage accumulated so far
+    pub async fn timeline(&self) -> ResourceTimeline {
+        self.state.lock().await.timeline.clone()
+    }
+
+    async fn sample_once(state: &Arc<Mutex<MonitorState>>, interval: Duration) {
+        let memory_available = read_mem_available_bytes().unwrap_or(0) as f64;
+
+        let mut guard = state.lock().await;
+
+        guard.timeline.memory_available_bytes.record(memory_available);
+
+        if let Some(cpu_utilization) = read_cpu_utilization_percent(&mut guard.previous.cpu_idle_total_ticks) {
+            guard.timeline.cpu_utilization_percent.record(cpu_utilization);
+        }
+
+        let first_disk_sample = guard.previous.disk_sectors.is_none();
+        if let Some((read_bytes, write_bytes)) = read_disk_bytes_per_sec(
+            &mut guard.previous.disk_sectors,
+            first_disk_sample,
+        ) {
+            let seconds = interval.as_secs_f64();
+            guard.timeline.disk_read_bytes_per_sec.record(read_bytes / seconds);
+            guard.timeline.disk_write_bytes_per_sec.record(write_bytes / seconds);
+        }
+    }
+}
+
+/// Read `MemAvailable` from `/proc/meminfo`, in bytes
+#[cfg(target_os = "linux")]
+fn read_mem_available_bytes() -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    for line in meminfo.lines() {
+        if let Some(rest) = line.strip_prefix("MemAvailable:") {
+            let kb: u64 = rest.split_whitespace().next()?.parse().ok()?;
+            return Some(kb * 1024);
+        }
+    }
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_mem_available_bytes() -> Option<u64> {
+    None
+}
+
+/// Compute CPU utilization (0-100) between the previous call and now, by
+/// diffing cumulative tick counters from `/proc/stat`'s aggregate `cpu` line
+#[cfg(target_os = "linux")]
+fn read_cpu_utilization_percent(previous: &mut Option<(u64, u64)>) -> Option<f64> {
+    let stat = std::fs::read_to_string("/proc/stat").ok()?;
+    let cpu_line = stat.lines().find(|line| line.starts_with("cpu "))?;
+    let ticks: Vec<u64> = cpu_line
+        .split_whitespace()
+        .skip(1)
+        .filter_map(|field| field.parse().ok())
+        .collect();
+
+    // user, nice, system, idle, iowait, irq, softirq, steal, guest, guest_nice
+    let idle = ticks.get(3)? + ticks.get(4).unwrap_or(&0);
+    let total: u64 = ticks.iter().sum();
+
+    let result = match previous {
+        Some((prev_idle, prev_total)) => {
+            let idle_delta = idle.saturating_sub(*prev_idle);
+            let total_delta = total.saturating_sub(*prev_total);
+            if total_delta == 0 {
+                None
+            } else {
+                Some(100.0 * (1.0 - idle_delta as f64 / total_delta as f64))
+            }
+        },
+        None => None,
+    };
+
+    *previous = Some((idle, total));
+    result
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_cpu_utilization_percent(_previous: &mut Option<(u64, u64)>) -> Option<f64> {
+    None
+}
+
+/// Compute aggregate disk bytes read/written between the previous call and
+/// now (the caller divides by the sample interval), by diffing the cumulative sector counters summed
+/// across every device in `/proc/diskstats`. `first_sample` suppresses the
+/// misleadingly huge delta a process would otherwise report on its very
+/// first reading, where "previous" is really "since boot".
+#[cfg(target_os = "linux")]
+fn read_disk_bytes_per_sec(previous: &mut Option<(u64, u64)>, first_sample: bool) -> Option<(f64, f64)> {
+    const SECTOR_SIZE_BYTES: u64 = 512;
+
+    let diskstats = std::fs::read_to_string("/proc/diskstats").ok()?;
+    let (mut read_sectors, mut write_sectors) = (0u64, 0u64);
+
+    for line in diskstats.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        // 0-indexed fields 5 (sectors read) and 9 (sectors written), per the
+        // kernel's Documentation/admin-guide/iostats.rst field layout
+        if fields.len() < 10 {
+            continue;
+        }
+        read_sectors += fields[5].parse::<u64>().unwrap_or(0);
+        write_sectors += fields[9].parse::<u64>().unwrap_or(0);
+    }
+
+    let result = match (previous.as_ref(), first_sample) {
+        (Some((prev_read, prev_write)), false) => Some((
+            (read_sectors.saturating_sub(*prev_read) * SECTOR_SIZE_BYTES) as f64,
+            (write_sectors.saturating_sub(*prev_write) * SECTOR_SIZE_BYTES) as f64,
+        )),
+        _ => None,
+    };
+
+    *previous = Some((read_sectors, write_sectors));
+    result
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_disk_bytes_per_sec(_previous: &mut Option<(u64, u64)>, _first_sample: bool) -> Option<(f64, f64)> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metric_stats_tracks_min_avg_max() {
+        let mut stats = MetricStats::default();
+        stats.record(10.0);
+        stats.record(30.0);
+        stats.record(20.0);
+
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.max, 30.0);
+        assert_eq!(stats.avg, 20.0);
+        assert_eq!(stats.sample_count, 3);
+    }
+
+    #[tokio::test]
+    async fn test_timeline_is_empty_before_any_sample() {
+        let monitor = SystemMonitorService::new();
+        let timeline = monitor.timeline().await;
+        assert_eq!(timeline.memory_available_bytes.sample_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_start_is_idempotent_when_already_running() {
+        let monitor = SystemMonitorService::with_interval(Duration::from_secs(60));
+        monitor.start().await;
+        monitor.start().await;
+        assert!(monitor.sampler.lock().await.is_some());
+        monitor.stop().await;
+    }
+}
@@ -8,12 +8,16 @@
 /// - Performance metrics collection
 
 use std::collections::HashMap;
+use std::fmt;
 use std::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use anyhow::{Result, Context, bail};
 use reqwest::Client;
 use tokio::time::timeout;
 
+use super::ValidationRecommendation;
+
 /// Ollama API response structure for model list
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct OllamaModelInfo {
@@ -47,6 +51,71 @@ struct OllamaVersionResponse {
     version: String,
 }
 
+/// Classification of failures from the Ollama HTTP API, so callers can react
+/// programmatically instead of string-matching `error_messages`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OllamaError {
+    /// The service could not be reached at all (connection error or timeout)
+    Unreachable(String),
+    /// The service kept returning HTTP 429 after exhausting retries
+    RateLimited,
+    /// The service kept returning a 5xx error after exhausting retries
+    ServerError(u16),
+    /// A request referenced a model that does not exist
+    ModelNotFound(String),
+}
+
+impl fmt::Display for OllamaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OllamaError::Unreachable(reason) => write!(f, "Ollama service unreachable: {}", reason),
+            OllamaError::RateLimited => write!(f, "Ollama service is rate-limiting requests (HTTP 429)"),
+            OllamaError::ServerError(status) => write!(f, "Ollama service returned a server error (HTTP {})", status),
+            OllamaError::ModelNotFound(model) => write!(f, "Model '{}' was not found", model),
+        }
+    }
+}
+
+impl std::error::Error for OllamaError {}
+
+/// Whether an HTTP status represents a transient failure worth retrying
+fn is_transient_status(status: reqwest::StatusCode) -> bool {
+    status.as_u16() == 429 || status.is_server_error()
+}
+
+/// Retry an Ollama API call with exponential backoff, but only for transient
+/// failures (rate-limiting, 5xx); permanent failures return immediately
+async fn with_retry<F, Fut, T>(max_attempts: u32, mut operation: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                let transient = e
+                    .downcast_ref::<OllamaError>()
+                    .map(|oe| matches!(oe, OllamaError::RateLimited | OllamaError::ServerError(_)))
+                    .unwrap_or(false);
+
+                if !transient || attempt >= max_attempts {
+                    return Err(e);
+                }
+
+                let backoff_ms = 200u64 * 2u64.pow(attempt - 1);
+                log::warn!(
+                    "Transient Ollama error on attempt {}/{}, retrying in {}ms: {}",
+                    attempt, max_attempts, backoff_ms, e
+                );
+                tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
 /// Result of Ollama service validation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OllamaValidationResult {
@@ -62,6 +131,9 @@ pub struct OllamaValidationResult {
     pub response_time_ms: u64,
     /// Total models available
     pub total_models_available: usize,
+    /// Embedding models that were probed for dimensionality, if any
+    #[serde(default)]
+    pub embedding_models: Vec<EmbeddingModelInfo>,
     /// Validation error messages if any
     pub error_messages: Vec<String>,
 }
@@ -81,6 +153,12 @@ pub struct OllamaModel {
     pub parameter_info: Option<String>,
     /// Last modified timestamp
     pub modified_at: String,
+    /// Time to load the model into memory and produce its first token, in milliseconds
+    pub cold_start_ms: Option<u64>,
+    /// Steady-state response time once the model is already loaded, in milliseconds
+    pub warm_response_ms: Option<u64>,
+    /// Advertised/default context window in tokens, from `/api/show`
+    pub context_length: Option<usize>,
 }
 
 /// Model size categories for timeout adjustment
@@ -120,6 +198,39 @@ impl ModelSizeCategory {
     }
 }
 
+/// Parameters controlling `compute_timeout_for_model`'s size/cold-start-driven formula
+#[derive(Debug, Clone, Copy)]
+pub struct TimeoutParams {
+    /// Timeout floor contributed before any size-based scaling, in seconds
+    pub base_seconds: u64,
+    /// Additional seconds of timeout per GB of model size
+    pub seconds_per_gb: f64,
+    /// Lowest timeout this formula will ever return, in seconds
+    pub floor_seconds: u64,
+    /// Highest timeout this formula will ever return, in seconds
+    pub ceiling_seconds: u64,
+}
+
+/// Derive a per-request timeout for a model from its on-disk size and, if
+/// measured, its cold-start latency - rather than `ModelSizeCategory`'s
+/// coarse four-bucket timeout. Cold-start latency is a more direct signal
+/// than size alone of how long a model takes to respond, so the two are
+/// combined by taking whichever implies the longer timeout, then clamping
+/// to `[floor_seconds, ceiling_seconds]` so a tiny model never gets a
+/// near-zero timeout and a huge one never blocks indefinitely.
+pub fn compute_timeout_for_model(size_bytes: u64, cold_start_ms: Option<u64>, params: &TimeoutParams) -> Duration {
+    const GB: f64 = 1_073_741_824.0;
+
+    let size_driven_seconds = params.base_seconds as f64 + params.seconds_per_gb * (size_bytes as f64 / GB);
+    let cold_start_seconds = cold_start_ms.map(|ms| ms as f64 / 1000.0).unwrap_or(0.0);
+
+    let seconds = size_driven_seconds
+        .max(cold_start_seconds)
+        .clamp(params.floor_seconds as f64, params.ceiling_seconds as f64);
+
+    Duration::from_secs_f64(seconds)
+}
+
 impl OllamaValidationResult {
     /// Convert validation result to metadata HashMap for CheckResult
     pub fn into_metadata(self) -> HashMap<String, String> {
@@ -146,10 +257,49 @@ impl OllamaValidationResult {
         metadata.insert("large_models_count".to_string(), large_count.to_string());
         metadata.insert("xl_models_count".to_string(), xl_count.to_string());
         
+        // Add each model's on-disk size, so the coordinator can derive a
+        // per-model timeout via `compute_timeout_for_model` without needing
+        // the full `OllamaModel` list
+        let model_sizes: Vec<String> = self.available_models.iter()
+            .map(|m| format!("{}={}", m.name, m.size_bytes))
+            .collect();
+        if !model_sizes.is_empty() {
+            metadata.insert("model_size_bytes".to_string(), model_sizes.join(","));
+        }
+
+        // Add cold-start / warm-response latency for any models that were warmed up
+        let cold_starts: Vec<String> = self.available_models.iter()
+            .filter_map(|m| m.cold_start_ms.map(|ms| format!("{}={}", m.name, ms)))
+            .collect();
+        if !cold_starts.is_empty() {
+            metadata.insert("cold_start_ms".to_string(), cold_starts.join(","));
+        }
+        let warm_responses: Vec<String> = self.available_models.iter()
+            .filter_map(|m| m.warm_response_ms.map(|ms| format!("{}={}", m.name, ms)))
+            .collect();
+        if !warm_responses.is_empty() {
+            metadata.insert("warm_response_ms".to_string(), warm_responses.join(","));
+        }
+
+        // Add detected context window and parameter size for any model that
+        // was introspected via `/api/show`
+        let context_lengths: Vec<String> = self.available_models.iter()
+            .filter_map(|m| m.context_length.map(|ctx| format!("{}={}", m.name, ctx)))
+            .collect();
+        if !context_lengths.is_empty() {
+            metadata.insert("context_length_tokens".to_string(), context_lengths.join(","));
+        }
+        let parameter_sizes: Vec<String> = self.available_models.iter()
+            .filter_map(|m| m.parameter_info.as_ref().map(|size| format!("{}={}", m.name, size)))
+            .collect();
+        if !parameter_sizes.is_empty() {
+            metadata.insert("parameter_sizes".to_string(), parameter_sizes.join(","));
+        }
+
         // Add available model names
         let model_names: Vec<String> = self.available_models.into_iter().map(|m| m.name).collect();
         metadata.insert("available_models".to_string(), model_names.join(","));
-        
+
         if !self.missing_models.is_empty() {
             metadata.insert("missing_models".to_string(), self.missing_models.join(","));
         }
@@ -163,6 +313,7 @@ impl OllamaValidationResult {
 }
 
 /// Ollama service validator with comprehensive health and model checks
+#[derive(Clone)]
 pub struct OllamaValidator {
     /// Base URL for Ollama service
     ollama_url: String,
@@ -170,8 +321,17 @@ pub struct OllamaValidator {
     http_client: Client,
     /// Timeout for API requests
     request_timeout: Duration,
+    /// Optional bearer token sent as `Authorization: Bearer <token>` on every request
+    bearer_token: Option<String>,
+    /// Additional static headers attached to every request (e.g. proxy auth)
+    extra_headers: HashMap<String, String>,
+    /// Maximum attempts for transient-error retries (rate-limiting, 5xx)
+    max_retry_attempts: u32,
 }
 
+/// Default number of attempts before a transient Ollama error is treated as final
+const DEFAULT_MAX_RETRY_ATTEMPTS: u32 = 3;
+
 impl OllamaValidator {
     /// Create new Ollama validator with specified URL and HTTP client
     pub fn new(ollama_url: &str, http_client: &Client) -> Self {
@@ -179,6 +339,9 @@ impl OllamaValidator {
             ollama_url: ollama_url.to_string(),
             http_client: http_client.clone(),
             request_timeout: Duration::from_secs(30),
+            bearer_token: None,
+            extra_headers: HashMap::new(),
+            max_retry_attempts: DEFAULT_MAX_RETRY_ATTEMPTS,
         }
     }
 
@@ -188,6 +351,70 @@ impl OllamaValidator {
             ollama_url: ollama_url.to_string(),
             http_client: http_client.clone(),
             request_timeout: timeout,
+            bearer_token: None,
+            extra_headers: HashMap::new(),
+            max_retry_attempts: DEFAULT_MAX_RETRY_ATTEMPTS,
+        }
+    }
+
+    /// Create new Ollama validator with bearer-token and/or custom-header authentication
+    ///
+    /// Use this when Ollama sits behind a reverse proxy that requires an
+    /// `Authorization: Bearer <token>` header or other static headers before
+    /// forwarding requests.
+    pub fn with_auth(
+        ollama_url: &str,
+        http_client: &Client,
+        bearer_token: Option<String>,
+        extra_headers: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            ollama_url: ollama_url.to_string(),
+            http_client: http_client.clone(),
+            request_timeout: Duration::from_secs(30),
+            bearer_token,
+            extra_headers,
+            max_retry_attempts: DEFAULT_MAX_RETRY_ATTEMPTS,
+        }
+    }
+
+    /// Create new Ollama validator with a custom transient-error retry budget
+    ///
+    /// Use this to tune how many attempts are made (with exponential backoff)
+    /// before a rate-limited or 5xx response is treated as final.
+    pub fn with_retry_attempts(ollama_url: &str, http_client: &Client, max_retry_attempts: u32) -> Self {
+        Self {
+            ollama_url: ollama_url.to_string(),
+            http_client: http_client.clone(),
+            request_timeout: Duration::from_secs(30),
+            bearer_token: None,
+            extra_headers: HashMap::new(),
+            max_retry_attempts,
+        }
+    }
+
+    /// Apply the configured bearer token and extra headers to an outgoing request
+    fn apply_auth(&self, mut builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        if let Some(token) = &self.bearer_token {
+            builder = builder.bearer_auth(token);
+        }
+        for (key, value) in &self.extra_headers {
+            builder = builder.header(key, value);
+        }
+        builder
+    }
+
+    /// Check whether a response status indicates an authentication/authorization failure
+    fn is_auth_failure(status: reqwest::StatusCode) -> bool {
+        status.as_u16() == 401 || status.as_u16() == 403
+    }
+
+    /// Classify a transient HTTP status into the matching `OllamaError` variant
+    fn classify_transient_status(status: reqwest::StatusCode) -> OllamaError {
+        if status.as_u16() == 429 {
+            OllamaError::RateLimited
+        } else {
+            OllamaError::ServerError(status.as_u16())
         }
     }
 
@@ -288,6 +515,7 @@ impl OllamaValidator {
             missing_models,
             response_time_ms,
             total_models_available,
+            embedding_models: Vec::new(),
             error_messages,
         })
     }
@@ -317,51 +545,173 @@ impl OllamaValidator {
                     result.missing_models.join(", ")
                 );
                 
-                result.error_messages.push(format!(
-                    "{} required models are not available: {}", 
-                    result.missing_models.len(),
-                    result.missing_models.join(", ")
-                ));
+                for missing in &result.missing_models {
+                    result.error_messages.push(OllamaError::ModelNotFound(missing.clone()).to_string());
+                }
             }
         }
 
         Ok(result)
     }
 
+    /// Validate required models, optionally auto-pulling any that are missing
+    ///
+    /// When `auto_pull` is true, missing models are downloaded via `/api/pull`
+    /// (progress logged through `log::info!`) and availability is re-checked
+    /// afterward. When `auto_pull` is false, each missing model instead produces
+    /// a High-priority recommendation with the exact `ollama pull <model>` command,
+    /// so the pre-flight never silently proceeds without it.
+    pub async fn validate_required_models_with_pull(
+        &self,
+        required_models: &[String],
+        auto_pull: bool,
+    ) -> Result<(OllamaValidationResult, Vec<ValidationRecommendation>)> {
+        let mut result = self.validate_required_models(required_models).await?;
+        let mut recommendations = Vec::new();
+
+        if result.missing_models.is_empty() {
+            return Ok((result, recommendations));
+        }
+
+        if auto_pull {
+            let mut still_missing = Vec::new();
+            for model_name in &result.missing_models {
+                log::info!("ðŸ“¥ Auto-pulling missing model '{}'", model_name);
+
+                if let Err(e) = self.pull_model(model_name).await {
+                    result.error_messages.push(format!("Failed to pull model '{}': {}", model_name, e));
+                    still_missing.push(model_name.clone());
+                    continue;
+                }
+
+                match self.is_model_available(model_name).await {
+                    Ok(true) => log::info!("âœ… Successfully pulled '{}'", model_name),
+                    Ok(false) => {
+                        result.error_messages.push(OllamaError::ModelNotFound(model_name.clone()).to_string());
+                        still_missing.push(model_name.clone());
+                    },
+                    Err(e) => {
+                        result.error_messages.push(format!("Failed to verify pull of '{}': {}", model_name, e));
+                        still_missing.push(model_name.clone());
+                    }
+                }
+            }
+            result.missing_models = still_missing;
+        } else {
+            for model_name in &result.missing_models {
+                recommendations.push(ValidationRecommendation {
+                    category: "Model Availability".to_string(),
+                    description: format!("Required model '{}' is not installed", model_name),
+                    priority: "High".to_string(),
+                    action: format!("ollama pull {}", model_name),
+                });
+            }
+        }
+
+        Ok((result, recommendations))
+    }
+
+    /// Download a model via Ollama's `/api/pull` endpoint, logging progress as it streams
+    ///
+    /// The response body is newline-delimited JSON progress updates; this is not
+    /// subject to `request_timeout` since pulls can legitimately take much longer
+    /// than a typical API call.
+    async fn pull_model(&self, model_name: &str) -> Result<()> {
+        let pull_url = format!("{}/api/pull", self.ollama_url);
+        let body = serde_json::json!({ "model": model_name });
+        let request = self.apply_auth(self.http_client.post(&pull_url).json(&body));
+
+        let response = request.send().await
+            .map_err(|e| OllamaError::Unreachable(e.to_string()))?;
+
+        if Self::is_auth_failure(response.status()) {
+            bail!("Authentication failed ({}) - check bearer token / headers", response.status());
+        }
+        if !response.status().is_success() {
+            bail!("Pull endpoint returned status: {}", response.status());
+        }
+
+        let body_text = response.text().await.context("Failed to read pull response body")?;
+        for line in body_text.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<Value>(line) {
+                Ok(progress) => {
+                    let status = progress.get("status").and_then(|v| v.as_str()).unwrap_or("pulling");
+                    log::info!("ðŸ“¥ Pulling '{}': {}", model_name, status);
+                },
+                Err(_) => log::debug!("Unparsable pull progress line for '{}': {}", model_name, line),
+            }
+        }
+
+        Ok(())
+    }
+
     /// Check if Ollama service is healthy and responding
+    ///
+    /// Transient failures (rate-limiting, 5xx) are retried with exponential backoff
+    /// before giving up; the final classification is returned as an `OllamaError`.
     async fn check_service_health(&self) -> Result<bool> {
+        with_retry(self.max_retry_attempts, || self.check_service_health_once()).await
+    }
+
+    async fn check_service_health_once(&self) -> Result<bool> {
         let health_url = format!("{}/api/tags", self.ollama_url);
-        
-        match timeout(self.request_timeout, self.http_client.get(&health_url).send()).await {
+
+        let request = self.apply_auth(self.http_client.get(&health_url));
+        match timeout(self.request_timeout, request.send()).await {
             Ok(Ok(response)) => {
+                if Self::is_auth_failure(response.status()) {
+                    bail!(
+                        "Authentication failed ({}) - check bearer token / headers",
+                        response.status()
+                    );
+                }
+                if is_transient_status(response.status()) {
+                    return Err(Self::classify_transient_status(response.status()).into());
+                }
                 let is_healthy = response.status().is_success();
-                log::debug!("ðŸ¥ Ollama health check: {} (status: {})", 
-                    if is_healthy { "HEALTHY" } else { "UNHEALTHY" }, 
+                log::debug!("ðŸ¥ Ollama health check: {} (status: {})",
+                    if is_healthy { "HEALTHY" } else { "UNHEALTHY" },
                     response.status()
                 );
                 Ok(is_healthy)
             },
             Ok(Err(e)) => {
                 log::debug!("ðŸ¥ Ollama health check failed: {}", e);
-                Err(e.into())
+                Err(OllamaError::Unreachable(e.to_string()).into())
             },
             Err(_) => {
                 log::debug!("ðŸ¥ Ollama health check timed out after {}s", self.request_timeout.as_secs());
-                bail!("Health check request timed out")
+                Err(OllamaError::Unreachable("request timed out".to_string()).into())
             }
         }
     }
 
     /// Get Ollama version information
     async fn get_ollama_version(&self) -> Result<String> {
+        with_retry(self.max_retry_attempts, || self.get_ollama_version_once()).await
+    }
+
+    async fn get_ollama_version_once(&self) -> Result<String> {
         let version_url = format!("{}/api/version", self.ollama_url);
-        
-        let response = timeout(
-            self.request_timeout,
-            self.http_client.get(&version_url).send()
-        ).await
-            .context("Version request timed out")?
-            .context("Failed to send version request")?;
+
+        let request = self.apply_auth(self.http_client.get(&version_url));
+        let response = timeout(self.request_timeout, request.send()).await
+            .map_err(|_| OllamaError::Unreachable("version request timed out".to_string()))?
+            .map_err(|e| OllamaError::Unreachable(e.to_string()))?;
+
+        if Self::is_auth_failure(response.status()) {
+            bail!(
+                "Authentication failed ({}) - check bearer token / headers",
+                response.status()
+            );
+        }
+
+        if is_transient_status(response.status()) {
+            return Err(Self::classify_transient_status(response.status()).into());
+        }
 
         if !response.status().is_success() {
             bail!("Version endpoint returned status: {}", response.status());
@@ -375,14 +725,27 @@ impl OllamaValidator {
 
     /// Get list of available models from Ollama
     async fn get_available_models(&self) -> Result<Vec<OllamaModel>> {
+        with_retry(self.max_retry_attempts, || self.get_available_models_once()).await
+    }
+
+    async fn get_available_models_once(&self) -> Result<Vec<OllamaModel>> {
         let models_url = format!("{}/api/tags", self.ollama_url);
-        
-        let response = timeout(
-            self.request_timeout,
-            self.http_client.get(&models_url).send()
-        ).await
-            .context("Models list request timed out")?
-            .context("Failed to send models list request")?;
+
+        let request = self.apply_auth(self.http_client.get(&models_url));
+        let response = timeout(self.request_timeout, request.send()).await
+            .map_err(|_| OllamaError::Unreachable("models list request timed out".to_string()))?
+            .map_err(|e| OllamaError::Unreachable(e.to_string()))?;
+
+        if Self::is_auth_failure(response.status()) {
+            bail!(
+                "Authentication failed ({}) - check bearer token / headers",
+                response.status()
+            );
+        }
+
+        if is_transient_status(response.status()) {
+            return Err(Self::classify_transient_status(response.status()).into());
+        }
 
         if !response.status().is_success() {
             bail!("Models endpoint returned status: {}", response.status());
@@ -420,6 +783,9 @@ impl OllamaValidator {
                 recommended_timeout_seconds: recommended_timeout,
                 parameter_info,
                 modified_at: model_info.modified_at,
+                cold_start_ms: None,
+                warm_response_ms: None,
+                context_length: None,
             });
         }
 
@@ -448,6 +814,333 @@ impl OllamaValidator {
         let models = self.get_available_models().await?;
         Ok(models.iter().any(|m| m.name == model_name))
     }
+
+    /// Validate Ollama service health and model availability, optionally
+    /// warming up every available model to measure cold-start latency
+    ///
+    /// When `warmup_models` is true, each available model is warmed up via
+    /// `validate_model_warmup`, populating `cold_start_ms`/`warm_response_ms`
+    /// on its `OllamaModel` entry (and so on `into_metadata`'s `cold_start_ms`
+    /// / `warm_response_ms` keys). A model that fails to warm up gets an
+    /// error message rather than aborting the rest of the check.
+    pub async fn validate_service_with_warmup(&self, warmup_models: bool) -> Result<OllamaValidationResult> {
+        let mut result = self.validate_service().await?;
+
+        if warmup_models && result.service_accessible {
+            for model in &mut result.available_models {
+                match self.validate_model_warmup(&model.name).await {
+                    Ok(warmup) => {
+                        model.cold_start_ms = Some(warmup.cold_start_ms);
+                        model.warm_response_ms = Some(warmup.warm_response_ms);
+                    },
+                    Err(e) => {
+                        result.error_messages.push(format!("Warm-up failed for model '{}': {}", model.name, e));
+                    }
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Warm up a model and measure cold-start vs. steady-state latency
+    ///
+    /// Issues a minimal `/api/generate` request with an empty prompt,
+    /// `num_predict: 1` and `keep_alive` set so the model stays resident,
+    /// recording the time to load the model and answer (`cold_start_ms`). A second
+    /// identical request against the now-loaded model measures steady-state
+    /// latency (`warm_response_ms`).
+    pub async fn validate_model_warmup(&self, model_name: &str) -> Result<ModelWarmupResult> {
+        let cold_start_ms = self.probe_generate(model_name).await
+            .context("Cold-start warm-up request failed")?;
+        let warm_response_ms = self.probe_generate(model_name).await
+            .context("Warm steady-state request failed")?;
+
+        Ok(ModelWarmupResult {
+            model_name: model_name.to_string(),
+            cold_start_ms,
+            warm_response_ms,
+        })
+    }
+
+    /// Issue a minimal generate request and return its elapsed time in milliseconds
+    async fn probe_generate(&self, model_name: &str) -> Result<u64> {
+        let generate_url = format!("{}/api/generate", self.ollama_url);
+        let body = OllamaGenerateRequest {
+            model: model_name.to_string(),
+            prompt: "".to_string(),
+            stream: false,
+            keep_alive: "5m".to_string(),
+            options: OllamaGenerateOptions { num_predict: 1 },
+        };
+
+        let request = self.apply_auth(self.http_client.post(&generate_url).json(&body));
+        let start = Instant::now();
+        let response = timeout(self.request_timeout, request.send()).await
+            .context("Generate request timed out")?
+            .context("Failed to send generate request")?;
+
+        if Self::is_auth_failure(response.status()) {
+            bail!(
+                "Authentication failed ({}) - check bearer token / headers",
+                response.status()
+            );
+        }
+
+        if !response.status().is_success() {
+            bail!("Generate endpoint returned status: {}", response.status());
+        }
+
+        // Drain the body so the elapsed time reflects a completed response
+        response.bytes().await.context("Failed to read generate response body")?;
+
+        Ok(start.elapsed().as_millis() as u64)
+    }
+}
+
+impl OllamaValidator {
+    /// Look up the advertised/default context window for a model via `/api/show`
+    ///
+    /// Ollama exposes no dedicated "max tokens" API, but `/api/show` returns a
+    /// `model_info` object whose keys include a family-prefixed
+    /// `*.context_length` entry (e.g. `llama.context_length`). We scan for the
+    /// first such key rather than hardcoding a family name.
+    pub async fn get_context_length(&self, model_name: &str) -> Result<Option<usize>> {
+        let show_url = format!("{}/api/show", self.ollama_url);
+        let body = serde_json::json!({ "model": model_name });
+
+        let request = self.apply_auth(self.http_client.post(&show_url).json(&body));
+        let response = timeout(self.request_timeout, request.send()).await
+            .context("Show request timed out")?
+            .context("Failed to send show request")?;
+
+        if Self::is_auth_failure(response.status()) {
+            bail!(
+                "Authentication failed ({}) - check bearer token / headers",
+                response.status()
+            );
+        }
+
+        if !response.status().is_success() {
+            bail!("Show endpoint returned status: {}", response.status());
+        }
+
+        let show_response: Value = response.json().await
+            .context("Failed to parse show response")?;
+
+        Ok(Self::extract_context_length(&show_response))
+    }
+
+    /// Extract a `*.context_length` value from a parsed `/api/show` response
+    fn extract_context_length(show_response: &Value) -> Option<usize> {
+        let model_info = show_response.get("model_info")?.as_object()?;
+        model_info.iter()
+            .find(|(key, _)| key.ends_with(".context_length"))
+            .and_then(|(_, value)| value.as_u64())
+            .map(|v| v as usize)
+    }
+
+    /// Flag any requested model whose advertised context window is below what an evaluation needs
+    ///
+    /// Calls `/api/show` for each model in `result.available_models`,
+    /// recording its detected `context_length` on the model entry and
+    /// comparing it against `required_ctx`. Models that are too small are
+    /// collected into `ContextWindowCheck::overflowing_models` so the
+    /// coordinator can raise a critical issue rather than a mere
+    /// recommendation; models whose window couldn't be determined go into
+    /// `undetectable_models` instead.
+    pub async fn validate_context_requirements(
+        &self,
+        result: &mut OllamaValidationResult,
+        required_ctx: usize,
+    ) -> Result<ContextWindowCheck> {
+        let mut check = ContextWindowCheck::default();
+
+        if !result.service_accessible {
+            return Ok(check);
+        }
+
+        for model in &mut result.available_models {
+            match self.get_context_length(&model.name).await {
+                Ok(Some(context_length)) => {
+                    model.context_length = Some(context_length);
+                    if context_length < required_ctx {
+                        check.overflowing_models.push(model.name.clone());
+                    }
+                },
+                Ok(None) => {
+                    check.undetectable_models.push(model.name.clone());
+                },
+                Err(e) => {
+                    result.error_messages.push(format!(
+                        "Failed to query context window for model '{}': {}",
+                        model.name, e
+                    ));
+                    check.undetectable_models.push(model.name.clone());
+                }
+            }
+        }
+
+        Ok(check)
+    }
+}
+
+/// Outcome of cross-checking every available model's context window against
+/// an evaluation's required `num_ctx`
+#[derive(Debug, Clone, Default)]
+pub struct ContextWindowCheck {
+    /// Models whose detected context window is below the required size
+    pub overflowing_models: Vec<String>,
+    /// Models whose context window could not be determined via `/api/show`
+    pub undetectable_models: Vec<String>,
+}
+
+/// Minimal request body for Ollama's `/api/generate` endpoint
+#[derive(Debug, Clone, Serialize)]
+struct OllamaGenerateRequest {
+    model: String,
+    prompt: String,
+    stream: bool,
+    keep_alive: String,
+    options: OllamaGenerateOptions,
+}
+
+/// Generation options relevant to warm-up probing
+#[derive(Debug, Clone, Serialize)]
+struct OllamaGenerateOptions {
+    num_predict: u32,
+}
+
+/// Result of warming up a single model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelWarmupResult {
+    pub model_name: String,
+    /// Time to load the model and produce the first token, in milliseconds
+    pub cold_start_ms: u64,
+    /// Time for a subsequent request once the model is already resident, in milliseconds
+    pub warm_response_ms: u64,
+}
+
+/// Dimensionality of an embedding model, inferred by probing it directly
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingModelInfo {
+    pub name: String,
+    pub dimensions: usize,
+}
+
+impl OllamaValidator {
+    /// Validate that a model produces embeddings and infer their dimensionality
+    ///
+    /// Sends a fixed probe prompt to `/api/embeddings` and reports the length of the
+    /// returned vector, since Ollama does not otherwise expose embedding dimensions.
+    pub async fn validate_embedding_model(&self, model_name: &str) -> Result<EmbeddingModelInfo> {
+        let embeddings_url = format!("{}/api/embeddings", self.ollama_url);
+        let body = serde_json::json!({ "model": model_name, "prompt": "test" });
+        let request = self.apply_auth(self.http_client.post(&embeddings_url).json(&body));
+
+        let response = timeout(self.request_timeout, request.send())
+            .await
+            .context("Embeddings request timed out")?
+            .context("Failed to send embeddings request")?;
+
+        if Self::is_auth_failure(response.status()) {
+            bail!("Authentication failed ({}) - check bearer token / headers", response.status());
+        }
+        if !response.status().is_success() {
+            bail!("Embeddings endpoint returned status: {}", response.status());
+        }
+
+        let embeddings_response: Value = response.json().await
+            .context("Failed to parse embeddings response")?;
+
+        let dimensions = Self::extract_embedding_dimensions(&embeddings_response).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Model '{}' did not return an embedding - it may not be an embedding model",
+                model_name
+            )
+        })?;
+
+        if dimensions == 0 {
+            bail!("Model '{}' returned an empty embedding vector", model_name);
+        }
+
+        Ok(EmbeddingModelInfo { name: model_name.to_string(), dimensions })
+    }
+
+    fn extract_embedding_dimensions(embeddings_response: &Value) -> Option<usize> {
+        embeddings_response.get("embedding")?.as_array().map(|v| v.len())
+    }
+}
+
+/// Outcome of a single model's real-inference health check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelHealthCheck {
+    pub model_name: String,
+    /// Whether the model replied successfully within the configured timeout
+    pub healthy: bool,
+    /// Round-trip time for the health-check prompt, if it succeeded
+    pub latency_ms: Option<u64>,
+    /// Failure reason, if it didn't
+    pub error: Option<String>,
+}
+
+impl OllamaValidator {
+    /// Send a tiny, deterministic prompt to `model_name` and report whether
+    /// it replies within `per_model_timeout`
+    ///
+    /// This catches the common case where a model is present on disk (and
+    /// so passes `validate_service`'s availability check) but fails to
+    /// actually load or run.
+    pub async fn check_model_health(&self, model_name: &str, per_model_timeout: Duration) -> ModelHealthCheck {
+        let start = Instant::now();
+
+        match self.probe_health(model_name, per_model_timeout).await {
+            Ok(()) => ModelHealthCheck {
+                model_name: model_name.to_string(),
+                healthy: true,
+                latency_ms: Some(start.elapsed().as_millis() as u64),
+                error: None,
+            },
+            Err(e) => ModelHealthCheck {
+                model_name: model_name.to_string(),
+                healthy: false,
+                latency_ms: None,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+
+    /// Issue a minimal, deterministic generate request with a caller-supplied
+    /// timeout, discarding the response - only whether it arrived in time matters
+    async fn probe_health(&self, model_name: &str, per_model_timeout: Duration) -> Result<()> {
+        let generate_url = format!("{}/api/generate", self.ollama_url);
+        let body = OllamaGenerateRequest {
+            model: model_name.to_string(),
+            prompt: "Reply with OK.".to_string(),
+            stream: false,
+            keep_alive: "5m".to_string(),
+            options: OllamaGenerateOptions { num_predict: 8 },
+        };
+
+        let request = self.apply_auth(self.http_client.post(&generate_url).json(&body));
+        let response = timeout(per_model_timeout, request.send()).await
+            .context("Health-check request timed out")?
+            .context("Failed to send health-check request")?;
+
+        if Self::is_auth_failure(response.status()) {
+            bail!(
+                "Authentication failed ({}) - check bearer token / headers",
+                response.status()
+            );
+        }
+
+        if !response.status().is_success() {
+            bail!("Generate endpoint returned status: {}", response.status());
+        }
+
+        response.bytes().await.context("Failed to read health-check response body")?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -469,4 +1162,141 @@ mod tests {
         assert_eq!(ModelSizeCategory::Large.recommended_timeout(), 120);
         assert_eq!(ModelSizeCategory::XL.recommended_timeout(), 300);
     }
+
+    #[test]
+    fn test_compute_timeout_for_model_scales_with_size() {
+        let params = TimeoutParams {
+            base_seconds: 30,
+            seconds_per_gb: 2.0,
+            floor_seconds: 30,
+            ceiling_seconds: 600,
+        };
+
+        let small = compute_timeout_for_model(1_073_741_824, None, &params); // 1GB
+        let large = compute_timeout_for_model(20 * 1_073_741_824, None, &params); // 20GB
+
+        assert_eq!(small, Duration::from_secs(32));
+        assert_eq!(large, Duration::from_secs(70));
+        assert!(large > small);
+    }
+
+    #[test]
+    fn test_compute_timeout_for_model_uses_cold_start_when_slower_than_size() {
+        let params = TimeoutParams {
+            base_seconds: 30,
+            seconds_per_gb: 2.0,
+            floor_seconds: 30,
+            ceiling_seconds: 600,
+        };
+
+        // 1GB model implies ~32s, but a 90s cold start should win out
+        let timeout = compute_timeout_for_model(1_073_741_824, Some(90_000), &params);
+        assert_eq!(timeout, Duration::from_secs(90));
+    }
+
+    #[test]
+    fn test_compute_timeout_for_model_clamps_to_ceiling() {
+        let params = TimeoutParams {
+            base_seconds: 30,
+            seconds_per_gb: 2.0,
+            floor_seconds: 30,
+            ceiling_seconds: 120,
+        };
+
+        let timeout = compute_timeout_for_model(100 * 1_073_741_824, None, &params); // 100GB
+        assert_eq!(timeout, Duration::from_secs(120));
+    }
+
+    #[test]
+    fn test_extract_context_length_from_model_info() {
+        let show_response = serde_json::json!({
+            "model_info": {
+                "llama.context_length": 8192,
+                "llama.embedding_length": 4096
+            }
+        });
+        assert_eq!(OllamaValidator::extract_context_length(&show_response), Some(8192));
+    }
+
+    #[test]
+    fn test_extract_context_length_missing() {
+        let show_response = serde_json::json!({ "model_info": { "llama.embedding_length": 4096 } });
+        assert_eq!(OllamaValidator::extract_context_length(&show_response), None);
+    }
+
+    #[tokio::test]
+    async fn test_validate_context_requirements_skips_when_service_inaccessible() {
+        let validator = OllamaValidator::new("http://localhost:1", &Client::new());
+        let mut result = OllamaValidationResult {
+            service_accessible: false,
+            version: None,
+            available_models: vec![OllamaModel {
+                name: "llama3.2:3b".to_string(),
+                size_bytes: 0,
+                size_category: ModelSizeCategory::Small,
+                recommended_timeout_seconds: 30,
+                parameter_info: None,
+                modified_at: String::new(),
+                cold_start_ms: None,
+                warm_response_ms: None,
+                context_length: None,
+            }],
+            missing_models: Vec::new(),
+            response_time_ms: 0,
+            total_models_available: 1,
+            embedding_models: Vec::new(),
+            error_messages: Vec::new(),
+        };
+
+        let check = validator.validate_context_requirements(&mut result, 4096).await.expect("should not error");
+
+        assert!(check.overflowing_models.is_empty());
+        assert!(check.undetectable_models.is_empty());
+        assert_eq!(result.available_models[0].context_length, None);
+    }
+
+    #[test]
+    fn test_extract_embedding_dimensions() {
+        let embeddings_response = serde_json::json!({ "embedding": [0.1, 0.2, 0.3, 0.4] });
+        assert_eq!(OllamaValidator::extract_embedding_dimensions(&embeddings_response), Some(4));
+    }
+
+    #[test]
+    fn test_extract_embedding_dimensions_missing() {
+        let embeddings_response = serde_json::json!({ "error": "model does not support embeddings" });
+        assert_eq!(OllamaValidator::extract_embedding_dimensions(&embeddings_response), None);
+    }
+
+    #[test]
+    fn test_is_transient_status() {
+        assert!(is_transient_status(reqwest::StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_transient_status(reqwest::StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(is_transient_status(reqwest::StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_transient_status(reqwest::StatusCode::NOT_FOUND));
+        assert!(!is_transient_status(reqwest::StatusCode::OK));
+    }
+
+    #[test]
+    fn test_classify_transient_status() {
+        assert_eq!(
+            OllamaValidator::classify_transient_status(reqwest::StatusCode::TOO_MANY_REQUESTS),
+            OllamaError::RateLimited
+        );
+        assert_eq!(
+            OllamaValidator::classify_transient_status(reqwest::StatusCode::BAD_GATEWAY),
+            OllamaError::ServerError(502)
+        );
+    }
+
+    #[test]
+    fn test_ollama_error_display() {
+        assert_eq!(
+            OllamaError::ModelNotFound("llama3".to_string()).to_string(),
+            "Model 'llama3' was not found"
+        );
+        assert_eq!(
+            OllamaError::RateLimited.to_string(),
+            "Ollama service is rate-limiting requests (HTTP 429)"
+        );
+    }
 }